@@ -2,6 +2,26 @@ app-title = Next Meeting for COSMIC
 no-meetings = No upcoming meetings
 no-meetings-panel = No meetings
 loading-meetings = Loading meetings...
+stale-meetings = Showing meetings as of { $time }
+calendars-unavailable = Couldn't read any calendar: { $reason }
+
+# Calendar errors
+error-unreachable = Calendar service not reachable
+error-open-failed = Couldn't open calendar ({ $message })
+error-query-failed = Couldn't read events ({ $message })
+error-timed-out = Calendar didn't respond in time
+error-auth-failed = Sign-in was rejected
+error-http-status = Server answered with status { $status }
+error-invalid-url = The address isn't valid
+error-unreadable = Couldn't read file ({ $message })
+error-invalid-data = The server didn't return a calendar
+error-invalid-events = { $count ->
+    [one] { $count } event couldn't be read
+    *[other] { $count } events couldn't be read
+}
+error-read-only = The calendar can't be changed
+error-not-invited = You're not on the guest list
+error-write-failed = Couldn't save the change ({ $message })
 settings = Settings
 open-calendar = Calendar
 back = Back
@@ -15,11 +35,38 @@ display-format-day-time = Day & time
 display-format-relative = Relative time
 upcoming-events-section = Show additional meetings
 join = Join
+join-choose = Choose a meeting link
+meeting-links = Meeting links
+meeting-link-numbered = { $name } { $number }
+meeting-details = Meeting details
+meeting-details-missing = This meeting is no longer in the list.
+meeting-organizer = Organized by { $name }
+meeting-description = Description
+attendees-accepted = Attending ({ $count })
+attendees-tentative = Maybe ({ $count })
+attendees-awaiting = Awaiting reply ({ $count })
+attendees-declined = Declined ({ $count })
+participant-chair = Chair
+participant-optional = Optional
+participant-non-participant = For information
+participant-room = Room
+participant-resource = Resource
+participant-group = Group
+rsvp-section = Going?
+rsvp-accept = Yes
+rsvp-tentative = Maybe
+rsvp-decline = No
+rsvp-failed = Couldn't send your reply: { $reason }
+invitation-pending = Awaiting your reply
+dial-in-section = Join by phone
+dial-in-pin = PIN { $pin }
+dial-in-label-pin = { $label } · PIN { $pin }
+dial-in-copy = Copy number and PIN
 join-button-section = Join button
 join-button-visibility = Visibility
 join-button-description = When enabled, a "Join" button will appear for meetings with a detected video call URL.
 formatting-section = Formatting
-url-patterns-description = Regex patterns used to detect video call URLs in meeting descriptions and locations. These patterns apply to the Join button in both the panel and dropdown.
+url-patterns-description = Each provider has regex patterns that detect its video call URLs in meeting descriptions and locations. They apply to the Join button in both the panel and dropdown.
 panel-join-button = In panel
 popup-join-button = In popup
 join-hide = Hide
@@ -30,6 +77,17 @@ join-show-15m = Show if within 15m
 join-show-5m = Show if within 5m
 url-patterns = URL Patterns
 add-pattern = Add pattern
+meeting-providers = Meeting providers
+add-provider = Add provider
+remove-provider = Remove provider
+provider-custom = Custom provider
+provider-name = Name
+provider-icon = Icon
+provider-open-in-app = Open in desktop app
+provider-opens-in-app = Desktop app
+provider-command = Open with
+provider-command-placeholder = Default browser
+provider-command-description = A command to open this provider's meetings with, such as chromium --profile-directory=Work {"{"}url{"}"}. Leave it empty to use the default browser. It's used by the Join buttons and --join-next.
 location-section = Indicate location
 location-description = When enabled, the physical location of the meeting (e.g. a room name) will be shown if provided.
 panel-location = In panel
@@ -64,6 +122,8 @@ in-progress-5m = <5m after start
 in-progress-10m = <10m after start
 in-progress-15m = <15m after start
 in-progress-30m = <30m after start
+in-progress-60m = <1h after start
+in-progress-until-end = Until it ends
 panel-started = started
 time-until-section = Filter by time until
 time-until-all = All events
@@ -72,10 +132,24 @@ time-until-6h = Within 6 hours
 time-until-same-day = Same day
 time-until-1d = Within 1 day
 time-until-2d = Within 2 days
+time-until-7d = Within 7 days
+look-behind-section = Look back
+look-ahead-section = Look ahead
+window-minutes = { $minutes } minutes
+window-hours = { $hours ->
+    [one] { $hours } hour
+    *[other] { $hours } hours
+}
+window-days = { $days ->
+    [one] { $days } day
+    *[other] { $days } days
+}
+fetch-window-description = Meetings are read from your calendars this far back and ahead. Longer windows allow longer in-progress and time-until options.
 status-filter-section = Filter by status
 status-filter-all = All events
 status-filter-accepted = Accepted only
 status-filter-accepted-tentative = Accepted & tentative
+hide-free-events = Skip events marked as free
 filter-summary-all = Show all events
 filter-summary-no-all-day = No all-day
 filter-summary-accepted = Accepted
@@ -98,6 +172,30 @@ version = Version { $version }
 author = by { $author }
 website = Website
 report-bug = Report bug
+
+# Diagnostics
+diagnostics = Diagnostics
+diagnostics-running = Querying calendars...
+diagnostics-no-calendars = No calendars were found.
+diagnostics-copy = Copy report
+diagnostics-run-again = Run again
+diagnostics-backend = Backend: { $backend }
+diagnostics-revision = Revision: { $revision }
+diagnostics-fetch-ok = Fetched in { $millis } ms
+diagnostics-fetch-failed = Fetch failed: { $error }
+diagnostics-counts = { $objects } objects, { $instances } instances
+diagnostics-filtered = { $count } hidden: { $reason }
+filter-reason-not-event = not an event
+filter-reason-invalid-times = ends before it starts
+filter-reason-past = already over
+filter-reason-after-window = beyond the lookahead window
+filter-reason-duplicate = duplicate instance
+filter-reason-all-day = all-day events hidden
+filter-reason-in-progress = in-progress meetings hidden
+filter-reason-time-until = starts too far ahead
+filter-reason-attendance = attendance status filtered
+filter-reason-cancelled = cancelled
+filter-reason-free = free events hidden
 no-calendars = No calendars
 no-calendars-description = Configure a calendar account using GNOME Online Accounts or a calendar app like Evolution.
 refresh-section = Force remote sync
@@ -123,6 +221,20 @@ updated-days-ago = Updated { $days }d ago
 vertical-panel-notice = This applet works best in a horizontal panel where meeting info can be displayed inline.
 account-needs-attention = { $identity } needs to be re-authenticated. Open Online Accounts to fix this.
 calendars-setup-tip = Remember to set up any online calendars you want to pull from in GNOME calendar/Online Accounts.
+ics-files-section = Calendar files
+ics-files-description = Paths to .ics files, or folders of .ics files, to show alongside your Evolution Data Server calendars. Changes to these files are picked up automatically.
+add-ics-path = Add file or folder
+vdir-section = vdir collections
+vdir-description = Folders synced by tools like vdirsyncer, with one .ics file per event. Add a single collection or a folder containing several; names and colors are read from their displayname and color files.
+add-vdir-path = Add folder
+webcal-section = Subscriptions
+webcal-description = Read-only calendar feeds, such as holiday or on-call calendars. They're downloaded again at the refresh interval, and the last copy is kept for when you're offline.
+add-webcal-url = Add subscription URL
+caldav-section = CalDAV accounts
+caldav-url-placeholder = https://caldav.example.com
+caldav-username-placeholder = Username
+caldav-description = Read calendars straight from a CalDAV server, without Evolution Data Server. Passwords are read from your keyring; store one with: secret-tool store --label="Next Meeting CalDAV" application com.dangrover.next-meeting-app url <server URL> username <username>
+add-caldav-account = Add account
 keyboard-shortcut = Keyboard shortcut
 keyboard-shortcut-description = You can set up a system keyboard shortcut to instantly join your next meeting.
 keyboard-shortcut-instructions = Open COSMIC Settings → Keyboard → Custom Shortcuts and add a new shortcut with the following command:
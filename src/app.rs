@@ -1,15 +1,25 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::calendar::{CalendarInfo, Meeting, extract_meeting_url, get_physical_location};
-use crate::config::{Config, DisplayFormat, InProgressMeeting, JoinButtonVisibility};
+use crate::calendar::{
+    AttendanceStatus, CalendarDiagnostics, CalendarError, CalendarInfo, FilterReason, Meeting,
+    MeetingQuery, MeetingsFetch, QueryWindow, SourceSet, get_physical_location,
+};
+use crate::config::{
+    Config, DEFAULT_PROVIDER_ICON, DisplayFormat, InProgressMeeting, JoinButtonVisibility,
+    LOOK_AHEAD_OPTIONS, LOOK_BEHIND_OPTIONS, MeetingProvider, ProviderKind,
+};
 use crate::fl;
 use crate::formatting::{
-    format_backend_name, format_last_updated, format_panel_time, format_relative_time, format_time,
-    parse_hex_color,
+    format_backend_name, format_calendar_error, format_filter_reason, format_in_progress,
+    format_last_updated, format_look_ahead, format_look_behind, format_meeting_link,
+    format_panel_time, format_participant_role, format_provider_name, format_relative_time,
+    format_short_url, format_time, format_time_until, parse_hex_color,
 };
 use crate::widgets::{
-    calendar_color_dot, display_format_options, email_input_id, secondary_text_style,
-    settings_nav_row, settings_nav_row_with_icon, settings_page_header, spacing,
+    caldav_url_input_id, calendar_color_dot, display_format_options, editable_list, email_input_id,
+    ics_path_input_id, provider_icon, secondary_text_style, settings_nav_row,
+    settings_nav_row_with_icon, settings_page_header, spacing, vdir_path_input_id,
+    webcal_url_input_id,
 };
 use cosmic::cosmic_config::{self, CosmicConfigEntry};
 use cosmic::iced::{Length, Limits, Subscription, clipboard, window::Id};
@@ -17,6 +27,7 @@ use cosmic::iced_winit::commands::popup::{destroy_popup, get_popup};
 use cosmic::prelude::*;
 use cosmic::widget;
 use futures_util::SinkExt;
+use std::collections::HashMap;
 
 /// The application model stores app-specific state used to describe its interface and
 /// drive its logic.
@@ -30,8 +41,16 @@ pub struct AppModel {
     config: Config,
     /// Upcoming meetings to display.
     upcoming_meetings: Vec<Meeting>,
-    /// Available calendars from Evolution Data Server.
+    /// Available calendars from all calendar sources.
     available_calendars: Vec<CalendarInfo>,
+    /// Calendar sources that meetings and calendars are read from.
+    sources: SourceSet,
+    /// Whether calendar paths, CalDAV accounts or subscriptions were edited since
+    /// the sources were last rebuilt.
+    sources_edited: bool,
+    /// Bumped each time the sources are rebuilt, so the subscriptions reading
+    /// them restart.
+    sources_generation: u64,
     /// Config context for saving changes.
     config_context: Option<cosmic_config::Config>,
     /// Current page in popup navigation
@@ -40,8 +59,25 @@ pub struct AppModel {
     is_refreshing: bool,
     /// Whether the initial meeting fetch has completed.
     has_loaded_meetings: bool,
+    /// When the shown meetings were fetched, possibly by an earlier run.
+    fetched_at: Option<chrono::DateTime<chrono::Local>>,
+    /// Whether the shown meetings are out of date, because they came from the
+    /// disk cache or the calendar sources stopped answering.
+    is_stale: bool,
+    /// Problems reading individual calendars in the last fetch, keyed by calendar UID.
+    calendar_errors: HashMap<String, CalendarError>,
+    /// Why the last fetch couldn't read any calendar, if it couldn't.
+    sources_error: Option<CalendarError>,
+    /// Results of the last diagnostics run, or `None` while it's running.
+    diagnostics: Option<Vec<CalendarDiagnostics>>,
     /// Online accounts that need re-authentication.
     accounts_needing_attention: Vec<crate::calendar::AccountNeedingAttention>,
+    /// UID of the meeting whose invitation is being replied to.
+    responding_to: Option<String>,
+    /// Why the last reply to an invitation failed, if it did.
+    response_error: Option<CalendarError>,
+    /// UID of the meeting whose menu of meeting links is open in the popup.
+    join_menu: Option<String>,
 }
 
 /// Navigation state for popup pages
@@ -59,86 +95,171 @@ pub enum PopupPage {
     PopupDisplaySettings,
     PanelJoinButtonSettings,
     PopupJoinButtonSettings,
+    MeetingProviders,
+    /// Settings for the meeting provider at the given index
+    MeetingProvider(usize),
     KeyboardShortcut,
+    Diagnostics,
     About,
+    /// Details of the meeting with the given UID
+    MeetingDetails(String),
 }
 
 impl AppModel {
     /// Get meetings filtered by current settings (all-day events, attendance status, in-progress, time until)
     fn filtered_meetings(&self) -> Vec<&Meeting> {
-        use crate::calendar::AttendanceStatus;
+        let now = chrono::Local::now();
+        self.upcoming_meetings
+            .iter()
+            .filter(|m| self.hidden_reason(m, now).is_none())
+            .collect()
+    }
+
+    /// Which display setting hides a meeting, if any.
+    fn hidden_reason(
+        &self,
+        m: &Meeting,
+        now: chrono::DateTime<chrono::Local>,
+    ) -> Option<FilterReason> {
         use crate::config::{EventStatusFilter, TimeUntilFilter};
-        use chrono::Local;
 
-        let now = Local::now();
+        // Filter out all-day events if disabled
+        if !self.config.show_all_day_events && m.is_all_day {
+            return Some(FilterReason::AllDay);
+        }
 
-        self.upcoming_meetings
+        // Filter in-progress meetings based on config
+        if m.start <= now {
+            // This is an in-progress meeting (already started)
+            let minutes_since_start = now.signed_duration_since(m.start).num_minutes();
+            // Meetings that started before the look-behind window were never fetched
+            let include = match self.config.show_in_progress {
+                InProgressMeeting::Off => false,
+                option => option
+                    .minutes()
+                    .is_none_or(|max| minutes_since_start <= i64::from(max)),
+            };
+            if !include {
+                return Some(FilterReason::InProgress);
+            }
+        }
+
+        // Filter by time until (how far in the future)
+        if m.start > now {
+            let include = match self.config.time_until_filter {
+                TimeUntilFilter::SameDay => m.start.date_naive() == now.date_naive(),
+                filter => filter.hours().is_none_or(|max| {
+                    m.start.signed_duration_since(now).num_hours() < i64::from(max)
+                }),
+            };
+            if !include {
+                return Some(FilterReason::TimeUntil);
+            }
+        }
+
+        if self.config.hide_free_events && m.is_free {
+            return Some(FilterReason::Free);
+        }
+
+        // Filter by attendance status
+        let include = match self.config.event_status_filter {
+            EventStatusFilter::All => true,
+            EventStatusFilter::Accepted => {
+                matches!(
+                    m.attendance_status,
+                    AttendanceStatus::Accepted | AttendanceStatus::None
+                )
+            }
+            EventStatusFilter::AcceptedOrTentative => {
+                matches!(
+                    m.attendance_status,
+                    AttendanceStatus::Accepted
+                        | AttendanceStatus::Tentative
+                        | AttendanceStatus::None
+                )
+            }
+        };
+        (!include).then_some(FilterReason::AttendanceStatus)
+    }
+
+    /// Instances a calendar's diagnostics left out, including those the
+    /// display settings would hide, by reason.
+    fn diagnostics_filtered(
+        &self,
+        diagnostics: &CalendarDiagnostics,
+    ) -> std::collections::BTreeMap<FilterReason, usize> {
+        let now = chrono::Local::now();
+        let mut filtered = diagnostics.filtered.clone();
+        for reason in diagnostics
+            .meetings
             .iter()
-            .filter(|m| {
-                // Filter out all-day events if disabled
-                if !self.config.show_all_day_events && m.is_all_day {
-                    return false;
-                }
+            .filter_map(|m| self.hidden_reason(m, now))
+        {
+            *filtered.entry(reason).or_default() += 1;
+        }
+        filtered
+    }
 
-                // Filter in-progress meetings based on config
-                if m.start <= now {
-                    // This is an in-progress meeting (already started)
-                    let minutes_since_start = now.signed_duration_since(m.start).num_minutes();
-                    let include = match self.config.show_in_progress {
-                        InProgressMeeting::Off => false,
-                        InProgressMeeting::Within5m => minutes_since_start <= 5,
-                        InProgressMeeting::Within10m => minutes_since_start <= 10,
-                        InProgressMeeting::Within15m => minutes_since_start <= 15,
-                        InProgressMeeting::Within30m => minutes_since_start <= 30,
-                    };
-                    if !include {
-                        return false;
-                    }
-                }
+    /// Build a plain-text diagnostics report for bug reports.
+    fn diagnostics_report(&self, diagnostics: &[CalendarDiagnostics]) -> String {
+        use std::fmt::Write;
 
-                // Filter by time until (how far in the future)
-                if m.start > now {
-                    let include = match self.config.time_until_filter {
-                        TimeUntilFilter::All => true,
-                        TimeUntilFilter::Within3Hours => {
-                            m.start.signed_duration_since(now).num_hours() < 3
-                        }
-                        TimeUntilFilter::Within6Hours => {
-                            m.start.signed_duration_since(now).num_hours() < 6
-                        }
-                        TimeUntilFilter::SameDay => m.start.date_naive() == now.date_naive(),
-                        TimeUntilFilter::Within1Day => {
-                            m.start.signed_duration_since(now).num_hours() < 24
-                        }
-                        TimeUntilFilter::Within2Days => {
-                            m.start.signed_duration_since(now).num_hours() < 48
-                        }
-                    };
-                    if !include {
-                        return false;
-                    }
-                }
+        let mut report = format!(
+            "{} {} ({})\n",
+            fl!("app-title"),
+            env!("CARGO_PKG_VERSION"),
+            env!("GIT_HASH")
+        );
+        for calendar in diagnostics {
+            let _ = writeln!(report);
+            let _ = writeln!(report, "{} [{}]", calendar.display_name, calendar.uid);
+            for line in self.diagnostics_lines(calendar) {
+                let _ = writeln!(report, "  {line}");
+            }
+        }
+        report
+    }
 
-                // Filter by attendance status
-                match self.config.event_status_filter {
-                    EventStatusFilter::All => true,
-                    EventStatusFilter::Accepted => {
-                        matches!(
-                            m.attendance_status,
-                            AttendanceStatus::Accepted | AttendanceStatus::None
-                        )
-                    }
-                    EventStatusFilter::AcceptedOrTentative => {
-                        matches!(
-                            m.attendance_status,
-                            AttendanceStatus::Accepted
-                                | AttendanceStatus::Tentative
-                                | AttendanceStatus::None
-                        )
-                    }
+    /// Describe one calendar's diagnostics, one fact per line.
+    fn diagnostics_lines(&self, calendar: &CalendarDiagnostics) -> Vec<String> {
+        let mut lines = vec![fl!(
+            "diagnostics-backend",
+            backend = calendar
+                .backend
+                .as_deref()
+                .map_or("unknown", format_backend_name)
+        )];
+        if let Some(revision) = &calendar.revision {
+            lines.push(fl!("diagnostics-revision", revision = revision.as_str()));
+        }
+        match &calendar.result {
+            Ok(()) => {
+                let millis = calendar.duration.map_or(0, |d| d.as_millis());
+                lines.push(fl!("diagnostics-fetch-ok", millis = millis.to_string()));
+                lines.push(fl!(
+                    "diagnostics-counts",
+                    objects = calendar.raw_objects,
+                    instances = calendar.instances
+                ));
+                if calendar.invalid_objects > 0 {
+                    lines.push(format_calendar_error(&CalendarError::InvalidEvents(
+                        calendar.invalid_objects,
+                    )));
                 }
-            })
-            .collect()
+                for (reason, count) in self.diagnostics_filtered(calendar) {
+                    lines.push(fl!(
+                        "diagnostics-filtered",
+                        count = count,
+                        reason = format_filter_reason(reason)
+                    ));
+                }
+            }
+            Err(error) => lines.push(fl!(
+                "diagnostics-fetch-failed",
+                error = format_calendar_error(error)
+            )),
+        }
+        lines
     }
 
     /// Save config to disk, logging any errors (e.g. Flatpak sandbox write failures).
@@ -150,6 +271,97 @@ impl AppModel {
         }
     }
 
+    /// Open the popup window.
+    fn open_popup(&mut self) -> Task<cosmic::Action<Message>> {
+        let new_id = Id::unique();
+        self.popup.replace(new_id);
+        let mut popup_settings = self.core.applet.get_popup_settings(
+            self.core.main_window_id().unwrap(),
+            new_id,
+            None,
+            None,
+            None,
+        );
+        popup_settings.positioner.size_limits = Limits::NONE;
+        get_popup(popup_settings)
+    }
+
+    /// What a Join button does, and the icon to show beside it: open the
+    /// link from [`Config::join_link`], or else send `choose` so the user can pick.
+    fn join_action<'a>(
+        &self,
+        meeting: &Meeting,
+        links: &[String],
+        icon_size: u16,
+        choose: Message,
+    ) -> (Element<'a, Message>, Message) {
+        match self.config.join_link(meeting, links) {
+            Some(url) => (
+                self.provider_icon(&url, icon_size),
+                Message::JoinMeeting(url),
+            ),
+            None => (provider_icon(DEFAULT_PROVIDER_ICON, icon_size), choose),
+        }
+    }
+
+    /// Menu of a meeting's links, labelled by provider. Picking one joins with
+    /// it and remembers it for the rest of the series.
+    fn view_join_menu(&self, meeting: &Meeting, links: &[String]) -> Element<'_, Message> {
+        let space = spacing();
+        let secondary_text = cosmic::theme::Text::Custom(secondary_text_style);
+        let preferred = self.config.preferred_meeting_links.get(meeting.event_uid());
+        let labels: Vec<String> = links
+            .iter()
+            .map(|url| format_meeting_link(self.config.provider_for_url(url), url))
+            .collect();
+
+        let mut menu = widget::list_column().list_item_padding([space.space_xxs, space.space_xs]);
+        for (i, (url, label)) in links.iter().zip(&labels).enumerate() {
+            // Tell apart links from the same provider, like breakout rooms
+            let label = if labels.iter().filter(|l| *l == label).count() > 1 {
+                let number = labels[..=i].iter().filter(|l| *l == label).count();
+                fl!(
+                    "meeting-link-numbered",
+                    name = label.as_str(),
+                    number = number
+                )
+            } else {
+                label.clone()
+            };
+            let mut row = widget::row::with_capacity(3)
+                .push(self.provider_icon(url, space.space_s))
+                .push(
+                    widget::column::with_capacity(2)
+                        .push(widget::text::body(label))
+                        .push(widget::text::caption(format_short_url(url)).class(secondary_text))
+                        .width(Length::Fill),
+                )
+                .spacing(space.space_s)
+                .align_y(cosmic::iced::Alignment::Center);
+            if preferred == Some(url) {
+                row =
+                    row.push(widget::icon::from_name("object-select-symbolic").size(space.space_s));
+            }
+            menu = menu.add(
+                cosmic::applet::menu_button(row).on_press(Message::JoinMeetingLink(
+                    meeting.event_uid().to_string(),
+                    url.clone(),
+                )),
+            );
+        }
+        menu.into()
+    }
+
+    /// Icon of the provider a meeting link belongs to, shown next to Join buttons.
+    fn provider_icon<'a>(&self, url: &str, size: u16) -> Element<'a, Message> {
+        provider_icon(
+            self.config
+                .provider_for_url(url)
+                .map_or(DEFAULT_PROVIDER_ICON, |p| p.icon.as_str()),
+            size,
+        )
+    }
+
     /// Get UIDs of enabled calendars that are valid meeting sources.
     /// Filters out non-meeting calendars (contacts, weather, birthdays).
     fn enabled_meeting_source_uids(&self) -> Vec<String> {
@@ -182,6 +394,69 @@ impl AppModel {
         }
     }
 
+    /// Build a task that fetches upcoming meetings for the given calendars.
+    fn fetch_meetings_task(&self, enabled_uids: Vec<String>) -> Task<cosmic::Action<Message>> {
+        let sources = self.sources.clone();
+        let query = MeetingQuery {
+            enabled_uids,
+            additional_emails: self.config.additional_emails.clone(),
+            window: QueryWindow::from_config(&self.config),
+        };
+        let limit = self.config.upcoming_events_count as usize + 1;
+        Task::perform(
+            async move { sources.upcoming_meetings(&query, limit).await },
+            |meetings| Message::MeetingsUpdated(meetings).into(),
+        )
+    }
+
+    /// Build a task that queries every calendar for the diagnostics page.
+    fn run_diagnostics_task(&mut self) -> Task<cosmic::Action<Message>> {
+        self.diagnostics = None;
+        let sources = self.sources.clone();
+        let query = MeetingQuery {
+            enabled_uids: self.enabled_meeting_source_uids(),
+            additional_emails: self.config.additional_emails.clone(),
+            window: QueryWindow::from_config(&self.config),
+        };
+        Task::perform(
+            async move { sources.diagnose(&query).await },
+            |diagnostics| Message::DiagnosticsLoaded(diagnostics).into(),
+        )
+    }
+
+    /// Rebuild the calendar sources from config and fetch from them again.
+    fn reload_sources(&mut self) -> Task<cosmic::Action<Message>> {
+        self.sources = SourceSet::from_config(&self.config);
+        self.sources_edited = false;
+        self.sources_generation += 1;
+        Task::batch([
+            self.fetch_calendars_task(),
+            self.fetch_meetings_task(self.enabled_meeting_source_uids()),
+        ])
+    }
+
+    /// Build a task that saves the shown meetings and calendars to the disk cache.
+    fn save_snapshot_task(&self) -> Task<cosmic::Action<Message>> {
+        let Some(fetched_at) = self.fetched_at.filter(|_| !self.is_stale) else {
+            return Task::none();
+        };
+        let snapshot = crate::cache::Snapshot {
+            fetched_at,
+            meetings: self.upcoming_meetings.clone(),
+            calendars: self.available_calendars.clone(),
+        };
+        Task::perform(crate::cache::save(snapshot), |()| Message::Noop.into())
+    }
+
+    /// Build a task that fetches the calendar list from all sources.
+    fn fetch_calendars_task(&self) -> Task<cosmic::Action<Message>> {
+        let sources = self.sources.clone();
+        Task::perform(
+            async move { sources.available_calendars().await },
+            |calendars| Message::CalendarsLoaded(calendars).into(),
+        )
+    }
+
     /// Main popup page showing meeting info and settings nav
     #[allow(clippy::too_many_lines)]
     fn view_main_page(&self) -> Element<'_, Message> {
@@ -208,15 +483,15 @@ impl AppModel {
                 JoinButtonVisibility::ShowIf15m => minutes_until <= 15,
                 JoinButtonVisibility::ShowIf5m => minutes_until <= 5,
             };
-            let meeting_url = if show_join {
-                extract_meeting_url(meeting, &self.config.meeting_url_patterns)
+            let meeting_links = if show_join {
+                self.config.meeting_links(meeting)
             } else {
-                None
+                Vec::new()
             };
 
             // Check for physical location based on popup location visibility settings
             let physical_location = if self.config.popup_show_location {
-                get_physical_location(meeting, &self.config.meeting_url_patterns)
+                get_physical_location(meeting, &self.config.url_patterns())
             } else {
                 None
             };
@@ -235,6 +510,10 @@ impl AppModel {
                 meeting_column =
                     meeting_column.push(widget::text::body(location).class(secondary_text));
             }
+            if meeting.attendance_status == AttendanceStatus::NeedsAction {
+                meeting_column = meeting_column
+                    .push(widget::text::caption(fl!("invitation-pending")).class(secondary_text));
+            }
 
             // Wrap column in row with optional calendar indicator dot (centered vertically)
             let meeting_content: cosmic::Element<'_, Message> =
@@ -256,24 +535,44 @@ impl AppModel {
                     meeting_column.into()
                 };
 
-            let meeting_info = widget::container(meeting_content)
-                .padding([space.space_xxs, space.space_xs])
-                .width(Length::Fill);
+            let meeting_info = cosmic::applet::menu_button(meeting_content).on_press(
+                Message::Navigate(PopupPage::MeetingDetails(meeting.uid.clone())),
+            );
 
-            if let Some(url) = meeting_url {
-                // Row with meeting info and Join button (with horizontal padding)
+            if !meeting_links.is_empty() {
+                // Row with meeting info, provider icon and Join button (with horizontal padding)
+                let toggle_menu = Message::ToggleJoinMenu(meeting.uid.clone());
+                let (icon, on_join) =
+                    self.join_action(meeting, &meeting_links, space.space_s, toggle_menu.clone());
+                let mut row = widget::row::with_capacity(4)
+                    .push(meeting_info)
+                    .push(icon)
+                    .push(widget::button::suggested(fl!("join")).on_press(on_join));
+                // With a link already picked, Join uses it and the menu is one click away
+                if meeting_links.len() > 1
+                    && self.config.join_link(meeting, &meeting_links).is_some()
+                {
+                    row = row.push(widget::tooltip(
+                        widget::button::icon(widget::icon::from_name("pan-down-symbolic"))
+                            .on_press(toggle_menu),
+                        widget::text(fl!("join-choose")),
+                        widget::tooltip::Position::Top,
+                    ));
+                }
                 content = content.push(
-                    widget::row::with_capacity(2)
-                        .push(meeting_info)
-                        .push(
-                            widget::button::suggested(fl!("join")).on_press(Message::OpenUrl(url)),
-                        )
-                        .align_y(cosmic::iced::Alignment::Center)
+                    row.align_y(cosmic::iced::Alignment::Center)
                         .spacing(space.space_xs)
                         .width(Length::Fill)
                         .apply(widget::container)
                         .padding([0, space.space_s]),
                 );
+                if self.join_menu.as_ref() == Some(&meeting.uid) {
+                    content = content.push(
+                        self.view_join_menu(meeting, &meeting_links)
+                            .apply(widget::container)
+                            .padding([space.space_xxs, space.space_s]),
+                    );
+                }
             } else {
                 // Wrap in container with horizontal padding
                 content = content.push(
@@ -352,7 +651,9 @@ impl AppModel {
                         )
                         .push(widget::text::body(time_str).class(secondary_text));
 
-                    content = content.push(cosmic::applet::padded_control(row));
+                    content = content.push(cosmic::applet::menu_button(row).on_press(
+                        Message::Navigate(PopupPage::MeetingDetails(meeting.uid.clone())),
+                    ));
                 }
             }
         } else if !self.has_loaded_meetings {
@@ -384,6 +685,54 @@ impl AppModel {
             ))));
         }
 
+        // Explain why no calendar could be read
+        if let Some(error) = &self.sources_error {
+            content = content.push(cosmic::applet::padded_control(
+                widget::row::with_capacity(2)
+                    .spacing(space.space_xs)
+                    .align_y(cosmic::iced::Alignment::Center)
+                    .push(
+                        widget::container(
+                            widget::icon::from_name("dialog-warning-symbolic").size(space.space_s),
+                        )
+                        .class(cosmic::theme::Container::custom(|theme| {
+                            cosmic::iced_widget::container::Style {
+                                icon_color: Some(theme.cosmic().palette.bright_orange.into()),
+                                ..Default::default()
+                            }
+                        })),
+                    )
+                    .push(
+                        widget::text::caption(fl!(
+                            "calendars-unavailable",
+                            reason = format_calendar_error(error)
+                        ))
+                        .wrapping(cosmic::iced::widget::text::Wrapping::Word),
+                    ),
+            ));
+        }
+
+        // Note that the meetings above may be out of date
+        if self.is_stale
+            && let Some(fetched_at) = self.fetched_at
+        {
+            let secondary_text = cosmic::theme::Text::Custom(secondary_text_style);
+            content = content.push(cosmic::applet::padded_control(
+                widget::row::with_capacity(2)
+                    .spacing(space.space_xs)
+                    .align_y(cosmic::iced::Alignment::Center)
+                    .push(widget::icon::from_name("network-offline-symbolic").size(space.space_s))
+                    .push(
+                        widget::text::caption(fl!(
+                            "stale-meetings",
+                            time = format_time(&fetched_at, true)
+                        ))
+                        .class(secondary_text)
+                        .wrapping(cosmic::iced::widget::text::Wrapping::Word),
+                    ),
+            ));
+        }
+
         // Warning banner for accounts needing re-authentication
         if !self.accounts_needing_attention.is_empty() {
             content = content.push(
@@ -582,6 +931,12 @@ impl AppModel {
                 fl!("dropdown-display"),
                 String::new(),
                 Message::Navigate(PopupPage::PopupDisplaySettings),
+            ))
+            .add(settings_nav_row_with_icon(
+                "camera-web-symbolic",
+                fl!("meeting-providers"),
+                self.config.meeting_providers.len().to_string(),
+                Message::Navigate(PopupPage::MeetingProviders),
             ));
 
         content = content.push(display_section);
@@ -603,6 +958,12 @@ impl AppModel {
         // ===== ABOUT SECTION =====
         let about_section = widget::list_column()
             .list_item_padding([space.space_xxs, space.space_xs])
+            .add(settings_nav_row_with_icon(
+                "utilities-system-monitor-symbolic",
+                fl!("diagnostics"),
+                String::new(),
+                Message::Navigate(PopupPage::Diagnostics),
+            ))
             .add(settings_nav_row_with_icon(
                 "help-about-symbolic",
                 fl!("about"),
@@ -735,6 +1096,32 @@ impl AppModel {
                 name_col = name_col.push(widget::text::caption(line).class(secondary_text));
             }
 
+            // Flag calendars that couldn't be read in the last fetch
+            if let Some(error) = self.calendar_errors.get(&calendar.uid) {
+                name_col = name_col.push(
+                    widget::row::with_capacity(2)
+                        .spacing(space.space_xxs)
+                        .align_y(cosmic::iced::Alignment::Center)
+                        .push(
+                            widget::container(
+                                widget::icon::from_name("dialog-warning-symbolic")
+                                    .size(space.space_s),
+                            )
+                            .class(cosmic::theme::Container::custom(
+                                |theme| cosmic::iced_widget::container::Style {
+                                    icon_color: Some(theme.cosmic().palette.bright_orange.into()),
+                                    ..Default::default()
+                                },
+                            )),
+                        )
+                        .push(
+                            widget::text::caption(format_calendar_error(error))
+                                .class(secondary_text)
+                                .wrapping(cosmic::iced::widget::text::Wrapping::Word),
+                        ),
+                );
+            }
+
             // Create toggler - disabled for non-meeting sources (contacts, weather, birthdays)
             let toggler = if is_meeting_source {
                 widget::toggler(is_enabled).on_toggle(move |_| Message::ToggleCalendar(uid.clone()))
@@ -765,7 +1152,7 @@ impl AppModel {
                 c.backend.as_ref().is_some_and(|b| {
                     !matches!(
                         b.to_lowercase().as_str(),
-                        "local" | "weather" | "contacts" | "birthdays"
+                        "local" | "ics" | "weather" | "contacts" | "birthdays"
                     )
                 })
             });
@@ -794,13 +1181,121 @@ impl AppModel {
             }
         }
 
+        // Calendar files section
+        content = content.push(widget::vertical_space().height(space.space_xs));
+        content = content.push(widget::text::heading(fl!("ics-files-section")));
+
+        if !self.config.ics_calendar_paths.is_empty() {
+            content = content.push(editable_list(
+                &self.config.ics_calendar_paths,
+                "~/Calendars/work.ics",
+                Message::UpdateIcsPath,
+                Message::RemoveIcsPath,
+                ics_path_input_id,
+            ));
+        }
+
+        content = content
+            .push(widget::button::standard(fl!("add-ics-path")).on_press(Message::AddIcsPath));
+        content =
+            content.push(widget::text::caption(fl!("ics-files-description")).class(secondary_text));
+
+        // vdir collections section
+        content = content.push(widget::vertical_space().height(space.space_xs));
+        content = content.push(widget::text::heading(fl!("vdir-section")));
+
+        if !self.config.vdir_paths.is_empty() {
+            content = content.push(editable_list(
+                &self.config.vdir_paths,
+                "~/.calendars",
+                Message::UpdateVdirPath,
+                Message::RemoveVdirPath,
+                vdir_path_input_id,
+            ));
+        }
+
+        content = content
+            .push(widget::button::standard(fl!("add-vdir-path")).on_press(Message::AddVdirPath));
+        content =
+            content.push(widget::text::caption(fl!("vdir-description")).class(secondary_text));
+
+        // Subscriptions section
+        content = content.push(widget::vertical_space().height(space.space_xs));
+        content = content.push(widget::text::heading(fl!("webcal-section")));
+
+        if !self.config.webcal_urls.is_empty() {
+            content = content.push(editable_list(
+                &self.config.webcal_urls,
+                "webcal://example.com/calendar.ics",
+                Message::UpdateWebcalUrl,
+                Message::RemoveWebcalUrl,
+                webcal_url_input_id,
+            ));
+        }
+
+        content = content
+            .push(widget::button::standard(fl!("add-webcal-url")).on_press(Message::AddWebcalUrl));
+        content =
+            content.push(widget::text::caption(fl!("webcal-description")).class(secondary_text));
+
+        // CalDAV accounts section
+        content = content.push(widget::vertical_space().height(space.space_xs));
+        content = content.push(widget::text::heading(fl!("caldav-section")));
+
+        if !self.config.caldav_accounts.is_empty() {
+            let mut accounts_list =
+                widget::list_column().list_item_padding([space.space_xxs, space.space_xs]);
+
+            for (idx, account) in self.config.caldav_accounts.iter().enumerate() {
+                accounts_list = accounts_list.add(
+                    widget::row::with_capacity(2)
+                        .push(
+                            widget::column::with_capacity(2)
+                                .push(
+                                    widget::text_input(fl!("caldav-url-placeholder"), &account.url)
+                                        .on_input(move |s| Message::UpdateCalDavUrl(idx, s))
+                                        .width(Length::Fill)
+                                        .id(caldav_url_input_id(idx)),
+                                )
+                                .push(
+                                    widget::text_input(
+                                        fl!("caldav-username-placeholder"),
+                                        &account.username,
+                                    )
+                                    .on_input(move |s| Message::UpdateCalDavUsername(idx, s))
+                                    .width(Length::Fill),
+                                )
+                                .spacing(space.space_xxs)
+                                .width(Length::Fill),
+                        )
+                        .push(
+                            widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                                .extra_small()
+                                .on_press(Message::RemoveCalDavAccount(idx)),
+                        )
+                        .spacing(space.space_xs)
+                        .align_y(cosmic::iced::Alignment::Center)
+                        .width(Length::Fill),
+                );
+            }
+
+            content = content.push(accounts_list);
+        }
+
+        content = content.push(
+            widget::button::standard(fl!("add-caldav-account")).on_press(Message::AddCalDavAccount),
+        );
+        content =
+            content.push(widget::text::caption(fl!("caldav-description")).class(secondary_text));
+        content = content.push(widget::vertical_space().height(space.space_m));
+
         content.into()
     }
 
     /// Panel join button settings page
     fn view_panel_join_button_settings_page(&self) -> Element<'_, Message> {
         let space = spacing();
-        let mut content = widget::column::with_capacity(8 + self.config.meeting_url_patterns.len())
+        let mut content = widget::column::with_capacity(3)
             .padding(space.space_xs)
             .spacing(space.space_xs)
             .width(Length::Fill);
@@ -847,45 +1342,6 @@ impl AppModel {
             );
 
         content = content.push(settings_list);
-
-        // URL Patterns section
-        let secondary_text = cosmic::theme::Text::Custom(secondary_text_style);
-        content = content.push(widget::vertical_space().height(space.space_s));
-        content = content.push(widget::text::body(fl!("url-patterns")));
-
-        // Pattern list
-        let mut patterns_list =
-            widget::list_column().list_item_padding([space.space_xxs, space.space_xs]);
-
-        for (idx, pattern) in self.config.meeting_url_patterns.iter().enumerate() {
-            patterns_list = patterns_list.add(
-                widget::row::with_capacity(2)
-                    .push(
-                        widget::text_input("https://example.com/meeting/.*", pattern)
-                            .on_input(move |s| Message::UpdatePattern(idx, s))
-                            .width(Length::Fill),
-                    )
-                    .push(
-                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
-                            .extra_small()
-                            .on_press(Message::RemovePattern(idx)),
-                    )
-                    .spacing(space.space_xs)
-                    .align_y(cosmic::iced::Alignment::Center)
-                    .width(Length::Fill),
-            );
-        }
-
-        content = content.push(patterns_list);
-
-        // Add pattern button
-        content = content
-            .push(widget::button::standard(fl!("add-pattern")).on_press(Message::AddPattern));
-
-        // URL patterns description
-        content = content.push(widget::vertical_space().height(space.space_xxs));
-        content = content
-            .push(widget::text::caption(fl!("url-patterns-description")).class(secondary_text));
         content = content.push(widget::vertical_space().height(space.space_m));
 
         content.into()
@@ -894,7 +1350,7 @@ impl AppModel {
     /// Popup join button settings page
     fn view_popup_join_button_settings_page(&self) -> Element<'_, Message> {
         let space = spacing();
-        let mut content = widget::column::with_capacity(8 + self.config.meeting_url_patterns.len())
+        let mut content = widget::column::with_capacity(3)
             .padding(space.space_xs)
             .spacing(space.space_xs)
             .width(Length::Fill);
@@ -941,45 +1397,179 @@ impl AppModel {
             );
 
         content = content.push(settings_list);
+        content = content.push(widget::vertical_space().height(space.space_m));
+
+        content.into()
+    }
 
-        // URL Patterns section
+    /// Meeting providers page, listing each provider with its icon
+    fn view_meeting_providers_page(&self) -> Element<'_, Message> {
+        let space = spacing();
         let secondary_text = cosmic::theme::Text::Custom(secondary_text_style);
+        let mut content = widget::column::with_capacity(5)
+            .padding(space.space_xs)
+            .spacing(space.space_xs)
+            .width(Length::Fill);
+
+        content = content.push(settings_page_header(
+            fl!("settings"),
+            fl!("meeting-providers"),
+            Message::Navigate(PopupPage::Settings),
+        ));
+
+        let mut providers_list =
+            widget::list_column().list_item_padding([space.space_xxs, space.space_xs]);
+        for (idx, provider) in self.config.meeting_providers.iter().enumerate() {
+            let summary = if let Some(program) = provider.launch_command.split_whitespace().next() {
+                program.to_string()
+            } else if provider.open_in_app {
+                fl!("provider-opens-in-app")
+            } else {
+                String::new()
+            };
+            providers_list = providers_list.add(
+                widget::button::custom(
+                    widget::row::with_capacity(5)
+                        .push(provider_icon(&provider.icon, space.space_s))
+                        .push(widget::text::body(format_provider_name(provider)))
+                        .push(widget::horizontal_space())
+                        .push(widget::text::body(summary).class(secondary_text))
+                        .push(widget::icon::from_name("go-next-symbolic").size(16).icon())
+                        .spacing(space.space_s)
+                        .align_y(cosmic::iced::Alignment::Center)
+                        .width(Length::Fill),
+                )
+                .padding(0)
+                .class(cosmic::theme::Button::Transparent)
+                .width(Length::Fill)
+                .on_press(Message::Navigate(PopupPage::MeetingProvider(idx))),
+            );
+        }
+        content = content.push(providers_list);
+
+        content = content
+            .push(widget::button::standard(fl!("add-provider")).on_press(Message::AddProvider));
+        content = content
+            .push(widget::text::caption(fl!("url-patterns-description")).class(secondary_text));
+        content = content.push(widget::vertical_space().height(space.space_m));
+
+        content.into()
+    }
+
+    /// Settings for one meeting provider: name, icon, patterns and how links open
+    fn view_meeting_provider_page(&self, idx: usize) -> Element<'_, Message> {
+        let space = spacing();
+        let secondary_text = cosmic::theme::Text::Custom(secondary_text_style);
+        let mut content = widget::column::with_capacity(11)
+            .padding(space.space_xs)
+            .spacing(space.space_xs)
+            .width(Length::Fill);
+
+        content = content.push(settings_page_header(
+            fl!("meeting-providers"),
+            self.config
+                .meeting_providers
+                .get(idx)
+                .map_or_else(|| fl!("meeting-providers"), format_provider_name),
+            Message::Navigate(PopupPage::MeetingProviders),
+        ));
+        let Some(provider) = self.config.meeting_providers.get(idx) else {
+            return content.into();
+        };
+
+        let mut details = widget::list_column()
+            .list_item_padding([space.space_xxs, space.space_xs])
+            .add(
+                widget::row::with_capacity(3)
+                    .push(widget::text::body(fl!("provider-name")))
+                    .push(
+                        widget::text_input(fl!("provider-custom"), &provider.name)
+                            .on_input(move |s| Message::SetProviderName(idx, s))
+                            .width(Length::Fill),
+                    )
+                    .spacing(space.space_s)
+                    .align_y(cosmic::iced::Alignment::Center)
+                    .width(Length::Fill),
+            )
+            .add(
+                widget::row::with_capacity(3)
+                    .push(widget::text::body(fl!("provider-icon")))
+                    .push(
+                        widget::text_input(DEFAULT_PROVIDER_ICON, &provider.icon)
+                            .on_input(move |s| Message::SetProviderIcon(idx, s))
+                            .width(Length::Fill),
+                    )
+                    .push(provider_icon(&provider.icon, space.space_s))
+                    .spacing(space.space_s)
+                    .align_y(cosmic::iced::Alignment::Center)
+                    .width(Length::Fill),
+            );
+        details = details.add(
+            widget::row::with_capacity(2)
+                .push(widget::text::body(fl!("provider-command")))
+                .push(
+                    widget::text_input(
+                        fl!("provider-command-placeholder"),
+                        &provider.launch_command,
+                    )
+                    .on_input(move |s| Message::SetProviderCommand(idx, s))
+                    .width(Length::Fill),
+                )
+                .spacing(space.space_s)
+                .align_y(cosmic::iced::Alignment::Center)
+                .width(Length::Fill),
+        );
+        if provider.kind.has_native_app() {
+            details = details.add(
+                widget::row::with_capacity(3)
+                    .push(widget::text::body(fl!("provider-open-in-app")))
+                    .push(widget::horizontal_space())
+                    .push(
+                        widget::toggler(provider.open_in_app)
+                            .on_toggle(move |on| Message::SetProviderOpenInApp(idx, on)),
+                    )
+                    .align_y(cosmic::iced::Alignment::Center)
+                    .width(Length::Fill),
+            );
+        }
+        content = content.push(details);
+        content = content
+            .push(widget::text::caption(fl!("provider-command-description")).class(secondary_text));
+
+        // Patterns that detect this provider's links
         content = content.push(widget::vertical_space().height(space.space_s));
         content = content.push(widget::text::body(fl!("url-patterns")));
-
-        // Pattern list
         let mut patterns_list =
             widget::list_column().list_item_padding([space.space_xxs, space.space_xs]);
-
-        for (idx, pattern) in self.config.meeting_url_patterns.iter().enumerate() {
+        for (pattern_idx, pattern) in provider.patterns.iter().enumerate() {
             patterns_list = patterns_list.add(
                 widget::row::with_capacity(2)
                     .push(
                         widget::text_input("https://example.com/meeting/.*", pattern)
-                            .on_input(move |s| Message::UpdatePattern(idx, s))
+                            .on_input(move |s| Message::UpdatePattern(idx, pattern_idx, s))
                             .width(Length::Fill),
                     )
                     .push(
                         widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
                             .extra_small()
-                            .on_press(Message::RemovePattern(idx)),
+                            .on_press(Message::RemovePattern(idx, pattern_idx)),
                     )
                     .spacing(space.space_xs)
                     .align_y(cosmic::iced::Alignment::Center)
                     .width(Length::Fill),
             );
         }
-
         content = content.push(patterns_list);
-
-        // Add pattern button
         content = content
-            .push(widget::button::standard(fl!("add-pattern")).on_press(Message::AddPattern));
+            .push(widget::button::standard(fl!("add-pattern")).on_press(Message::AddPattern(idx)));
 
-        // URL patterns description
-        content = content.push(widget::vertical_space().height(space.space_xxs));
-        content = content
-            .push(widget::text::caption(fl!("url-patterns-description")).class(secondary_text));
+        if provider.kind == ProviderKind::Custom {
+            content = content.push(widget::vertical_space().height(space.space_s));
+            content = content.push(
+                widget::button::destructive(fl!("remove-provider"))
+                    .on_press(Message::RemoveProvider(idx)),
+            );
+        }
         content = content.push(widget::vertical_space().height(space.space_m));
 
         content.into()
@@ -1155,7 +1745,7 @@ impl AppModel {
     /// Filter events settings page
     #[allow(clippy::too_many_lines)]
     fn view_events_to_show_settings_page(&self) -> Element<'_, Message> {
-        use crate::config::{EventStatusFilter, TimeUntilFilter};
+        use crate::config::EventStatusFilter;
 
         let space = spacing();
         let mut content = widget::column::with_capacity(6)
@@ -1191,39 +1781,41 @@ impl AppModel {
             EventStatusFilter::AcceptedOrTentative => Some(2),
         };
 
-        // In-progress meeting dropdown options
-        let in_progress_options = vec![
-            fl!("in-progress-off"),
-            fl!("in-progress-5m"),
-            fl!("in-progress-10m"),
-            fl!("in-progress-15m"),
-            fl!("in-progress-30m"),
-        ];
-        let in_progress_idx = match self.config.show_in_progress {
-            InProgressMeeting::Off => Some(0),
-            InProgressMeeting::Within5m => Some(1),
-            InProgressMeeting::Within10m => Some(2),
-            InProgressMeeting::Within15m => Some(3),
-            InProgressMeeting::Within30m => Some(4),
-        };
+        // Fetch window dropdown options
+        let look_behind_options: Vec<String> = LOOK_BEHIND_OPTIONS
+            .into_iter()
+            .map(format_look_behind)
+            .collect();
+        let look_behind_idx = LOOK_BEHIND_OPTIONS
+            .iter()
+            .position(|m| *m == self.config.look_behind_minutes);
+        let look_ahead_options: Vec<String> = LOOK_AHEAD_OPTIONS
+            .into_iter()
+            .map(format_look_ahead)
+            .collect();
+        let look_ahead_idx = LOOK_AHEAD_OPTIONS
+            .iter()
+            .position(|d| *d == self.config.look_ahead_days);
 
-        // Time until filter dropdown options
-        let time_until_options = vec![
-            fl!("time-until-all"),
-            fl!("time-until-3h"),
-            fl!("time-until-6h"),
-            fl!("time-until-same-day"),
-            fl!("time-until-1d"),
-            fl!("time-until-2d"),
-        ];
-        let time_until_idx = match self.config.time_until_filter {
-            TimeUntilFilter::All => Some(0),
-            TimeUntilFilter::Within3Hours => Some(1),
-            TimeUntilFilter::Within6Hours => Some(2),
-            TimeUntilFilter::SameDay => Some(3),
-            TimeUntilFilter::Within1Day => Some(4),
-            TimeUntilFilter::Within2Days => Some(5),
-        };
+        // In-progress meeting dropdown options, limited to the look-behind window
+        let in_progress_choices = self.config.in_progress_options();
+        let in_progress_idx = in_progress_choices
+            .iter()
+            .position(|o| *o == self.config.show_in_progress);
+        let in_progress_options: Vec<String> = in_progress_choices
+            .into_iter()
+            .map(format_in_progress)
+            .collect();
+
+        // Time until filter dropdown options, limited to the look-ahead window
+        let time_until_choices = self.config.time_until_options();
+        let time_until_idx = time_until_choices
+            .iter()
+            .position(|o| *o == self.config.time_until_filter);
+        let time_until_options: Vec<String> = time_until_choices
+            .into_iter()
+            .map(format_time_until)
+            .collect();
 
         // Email summary for navigation link
         let email_count = self.config.additional_emails.len();
@@ -1244,6 +1836,31 @@ impl AppModel {
                     .align_y(cosmic::iced::Alignment::Center)
                     .width(Length::Fill),
             )
+            // Fetch window dropdowns
+            .add(
+                widget::row::with_capacity(3)
+                    .push(widget::text::body(fl!("look-behind-section")))
+                    .push(widget::horizontal_space())
+                    .push(widget::dropdown(
+                        look_behind_options,
+                        look_behind_idx,
+                        Message::SetLookBehind,
+                    ))
+                    .align_y(cosmic::iced::Alignment::Center)
+                    .width(Length::Fill),
+            )
+            .add(
+                widget::row::with_capacity(3)
+                    .push(widget::text::body(fl!("look-ahead-section")))
+                    .push(widget::horizontal_space())
+                    .push(widget::dropdown(
+                        look_ahead_options,
+                        look_ahead_idx,
+                        Message::SetLookAhead,
+                    ))
+                    .align_y(cosmic::iced::Alignment::Center)
+                    .width(Length::Fill),
+            )
             // In-progress meeting dropdown
             .add(
                 widget::row::with_capacity(3)
@@ -1282,6 +1899,18 @@ impl AppModel {
                     ))
                     .align_y(cosmic::iced::Alignment::Center)
                     .width(Length::Fill),
+            )
+            // Free events toggle
+            .add(
+                widget::row::with_capacity(3)
+                    .push(widget::text::body(fl!("hide-free-events")))
+                    .push(widget::horizontal_space())
+                    .push(
+                        widget::toggler(self.config.hide_free_events)
+                            .on_toggle(Message::SetHideFreeEvents),
+                    )
+                    .align_y(cosmic::iced::Alignment::Center)
+                    .width(Length::Fill),
             );
 
         // Show email settings link only when filtering by status
@@ -1315,6 +1944,8 @@ impl AppModel {
         content = content.push(widget::vertical_space().height(space.space_s));
         content = content
             .push(widget::text::caption(fl!("filter-events-description")).class(secondary_text));
+        content = content
+            .push(widget::text::caption(fl!("fetch-window-description")).class(secondary_text));
         content = content.push(widget::vertical_space().height(space.space_m));
 
         content.into()
@@ -1630,52 +2261,317 @@ impl AppModel {
 
         content = content.push(widget::vertical_space().height(space.space_xxs));
 
-        // Instructions (second paragraph)
-        content = content.push(widget::text::body(fl!("keyboard-shortcut-instructions")));
+        // Instructions (second paragraph)
+        content = content.push(widget::text::body(fl!("keyboard-shortcut-instructions")));
+
+        content = content.push(widget::vertical_space().height(space.space_xs));
+
+        // Command in a styled container (read-only text input for selectability)
+        // Show different command based on whether we're running in Flatpak
+        let command: &'static str = if std::env::var("FLATPAK_ID").is_ok() {
+            "flatpak run com.dangrover.next-meeting-app --join-next"
+        } else {
+            "cosmic-ext-applet-next-meeting --join-next"
+        };
+        content = content.push(
+            widget::container(
+                widget::row::with_capacity(2)
+                    .spacing(space.space_s)
+                    .align_y(cosmic::iced::Alignment::Center)
+                    .push(
+                        widget::text_input("", command)
+                            .on_input(|_| Message::Noop)
+                            .font(cosmic::iced::Font::MONOSPACE)
+                            .width(Length::Fill),
+                    )
+                    .push(
+                        widget::button::text(fl!("keyboard-shortcut-copy"))
+                            .class(cosmic::theme::Button::Standard)
+                            .on_press(Message::CopyToClipboard(command.to_string())),
+                    ),
+            )
+            .padding(space.space_s)
+            .class(cosmic::theme::Container::List),
+        );
+
+        content = content.push(widget::vertical_space().height(space.space_s));
+
+        // Open Settings button
+        content = content.push(
+            widget::container(
+                widget::button::standard(fl!("keyboard-shortcut-open-settings"))
+                    .on_press(Message::OpenCosmicSettings),
+            )
+            .width(Length::Fill)
+            .align_x(cosmic::iced::alignment::Horizontal::Center),
+        );
+
+        content = content.push(widget::vertical_space().height(space.space_m));
+
+        content.into()
+    }
+
+    /// Diagnostics page listing what each calendar returned
+    fn view_diagnostics_page(&self) -> Element<'_, Message> {
+        let space = spacing();
+        let secondary_text = cosmic::theme::Text::Custom(secondary_text_style);
+
+        let mut content = widget::column::with_capacity(4)
+            .padding(space.space_xs)
+            .spacing(space.space_xs)
+            .width(Length::Fill);
+
+        // Back button header
+        content = content.push(settings_page_header(
+            fl!("settings"),
+            fl!("diagnostics"),
+            Message::Navigate(PopupPage::Settings),
+        ));
+
+        let Some(diagnostics) = &self.diagnostics else {
+            content = content.push(widget::text::body(fl!("diagnostics-running")));
+            return content.into();
+        };
+
+        content = content.push(
+            widget::row::with_capacity(2)
+                .spacing(space.space_xs)
+                .push(widget::button::standard(fl!("diagnostics-copy")).on_press(
+                    Message::CopyToClipboard(self.diagnostics_report(diagnostics)),
+                ))
+                .push(
+                    widget::button::text(fl!("diagnostics-run-again"))
+                        .on_press(Message::RunDiagnostics),
+                ),
+        );
+
+        if diagnostics.is_empty() {
+            content = content
+                .push(widget::text::body(fl!("diagnostics-no-calendars")).class(secondary_text));
+            return content.into();
+        }
+
+        let mut list = widget::list_column().list_item_padding([space.space_xxs, space.space_xs]);
+        for calendar in diagnostics {
+            let mut column = widget::column::with_capacity(5)
+                .spacing(space.space_xxxs)
+                .push(widget::text::body(&calendar.display_name));
+            for line in self.diagnostics_lines(calendar) {
+                column = column.push(
+                    widget::text::caption(line)
+                        .class(secondary_text)
+                        .wrapping(cosmic::iced::widget::text::Wrapping::Word),
+                );
+            }
+            list = list.add(column);
+        }
+        content = content.push(list);
+
+        content.into()
+    }
+
+    /// Details of a meeting: when and where, who's attending and the full description
+    fn view_meeting_details_page(&self, uid: &str) -> Element<'_, Message> {
+        let space = spacing();
+        let secondary_text = cosmic::theme::Text::Custom(secondary_text_style);
+
+        let mut content = widget::column::with_capacity(8)
+            .padding(space.space_xs)
+            .spacing(space.space_xs)
+            .width(Length::Fill);
+
+        // The meeting may have dropped out of the list since the page was opened
+        let Some(meeting) = self.upcoming_meetings.iter().find(|m| m.uid == uid) else {
+            content = content.push(settings_page_header(
+                fl!("back"),
+                fl!("meeting-details"),
+                Message::Navigate(PopupPage::Main),
+            ));
+            content = content
+                .push(widget::text::body(fl!("meeting-details-missing")).class(secondary_text));
+            return content.into();
+        };
+
+        content = content.push(settings_page_header(
+            fl!("back"),
+            meeting.title.clone(),
+            Message::Navigate(PopupPage::Main),
+        ));
+
+        let mut when = widget::column::with_capacity(3)
+            .spacing(space.space_xxxs)
+            .push(widget::text::body(format_time(&meeting.start, true)).class(secondary_text));
+        if let Some(location) = get_physical_location(meeting, &self.config.url_patterns()) {
+            when = when.push(
+                widget::text::body(location)
+                    .class(secondary_text)
+                    .wrapping(cosmic::iced::widget::text::Wrapping::Word),
+            );
+        }
+        if let Some(organizer) = &meeting.organizer {
+            when = when.push(
+                widget::text::body(fl!(
+                    "meeting-organizer",
+                    name = organizer.display_name().to_string()
+                ))
+                .class(secondary_text),
+            );
+        }
+        content = content.push(when);
+
+        let meeting_links = self.config.meeting_links(meeting);
+        if let [url] = meeting_links.as_slice() {
+            content = content.push(
+                widget::row::with_capacity(2)
+                    .push(self.provider_icon(url, space.space_s))
+                    .push(
+                        widget::button::suggested(fl!("join"))
+                            .on_press(Message::JoinMeeting(url.clone())),
+                    )
+                    .spacing(space.space_xs)
+                    .align_y(cosmic::iced::Alignment::Center),
+            );
+        } else if meeting_links.len() > 1 {
+            content = content.push(widget::text::heading(fl!("meeting-links")));
+            content = content.push(self.view_join_menu(meeting, &meeting_links));
+        }
+
+        // Phone numbers, each dialing straight into the meeting
+        if !meeting.dial_ins.is_empty() {
+            content = content.push(widget::text::heading(fl!("dial-in-section")));
+            let mut list =
+                widget::list_column().list_item_padding([space.space_xxs, space.space_xs]);
+            for dial_in in &meeting.dial_ins {
+                let mut details = widget::column::with_capacity(2).push(
+                    widget::button::text(dial_in.number.clone())
+                        .class(cosmic::theme::Button::Link)
+                        .padding(space.space_none)
+                        .on_press(Message::OpenUrl(dial_in.tel_uri())),
+                );
+                let caption = match (&dial_in.label, &dial_in.pin) {
+                    (Some(label), Some(pin)) => Some(fl!(
+                        "dial-in-label-pin",
+                        label = label.clone(),
+                        pin = pin.clone()
+                    )),
+                    (Some(label), None) => Some(label.clone()),
+                    (None, Some(pin)) => Some(fl!("dial-in-pin", pin = pin.clone())),
+                    (None, None) => None,
+                };
+                if let Some(caption) = caption {
+                    details = details.push(widget::text::caption(caption).class(secondary_text));
+                }
+                list = list.add(
+                    widget::row::with_capacity(2)
+                        .align_y(cosmic::iced::Alignment::Center)
+                        .push(details.width(Length::Fill))
+                        .push(widget::tooltip(
+                            widget::button::icon(widget::icon::from_name("edit-copy-symbolic"))
+                                .on_press(Message::CopyToClipboard(dial_in.dial_string())),
+                            widget::text(fl!("dial-in-copy")),
+                            widget::tooltip::Position::Top,
+                        )),
+                );
+            }
+            content = content.push(list);
+        }
 
-        content = content.push(widget::vertical_space().height(space.space_xs));
+        // Reply buttons for invitations, with the current reply highlighted
+        if meeting.attendance_status != AttendanceStatus::None
+            && self.sources.can_respond(&meeting.calendar_uid)
+        {
+            let responding = self.responding_to.as_deref() == Some(uid);
+            let mut replies = widget::row::with_capacity(3).spacing(space.space_xs);
+            for (response, label) in [
+                (AttendanceStatus::Accepted, fl!("rsvp-accept")),
+                (AttendanceStatus::Tentative, fl!("rsvp-tentative")),
+                (AttendanceStatus::Declined, fl!("rsvp-decline")),
+            ] {
+                let button = if meeting.attendance_status == response {
+                    widget::button::suggested(label)
+                } else {
+                    widget::button::standard(label)
+                };
+                replies = replies.push(
+                    button.on_press_maybe(
+                        (!responding)
+                            .then(|| Message::RespondToInvitation(meeting.uid.clone(), response)),
+                    ),
+                );
+            }
+            content = content.push(
+                widget::column::with_capacity(2)
+                    .spacing(space.space_xxs)
+                    .push(widget::text::heading(fl!("rsvp-section")))
+                    .push(replies),
+            );
+            if let Some(error) = &self.response_error {
+                content = content.push(
+                    widget::text::caption(fl!(
+                        "rsvp-failed",
+                        reason = format_calendar_error(error)
+                    ))
+                    .class(secondary_text)
+                    .wrapping(cosmic::iced::widget::text::Wrapping::Word),
+                );
+            }
+        }
 
-        // Command in a styled container (read-only text input for selectability)
-        // Show different command based on whether we're running in Flatpak
-        let command: &'static str = if std::env::var("FLATPAK_ID").is_ok() {
-            "flatpak run com.dangrover.next-meeting-app --join-next"
-        } else {
-            "cosmic-ext-applet-next-meeting --join-next"
+        // Attendees, grouped by their reply. No reply means the invitation is still open.
+        let with_status = |statuses: &[AttendanceStatus]| -> Vec<_> {
+            meeting
+                .attendees
+                .iter()
+                .filter(|a| statuses.contains(&a.status))
+                .collect()
         };
-        content = content.push(
-            widget::container(
-                widget::row::with_capacity(2)
-                    .spacing(space.space_s)
+        let accepted = with_status(&[AttendanceStatus::Accepted]);
+        let tentative = with_status(&[AttendanceStatus::Tentative]);
+        let awaiting = with_status(&[AttendanceStatus::NeedsAction, AttendanceStatus::None]);
+        let declined = with_status(&[AttendanceStatus::Declined]);
+        for (heading, attendees) in [
+            (fl!("attendees-accepted", count = accepted.len()), accepted),
+            (
+                fl!("attendees-tentative", count = tentative.len()),
+                tentative,
+            ),
+            (fl!("attendees-awaiting", count = awaiting.len()), awaiting),
+            (fl!("attendees-declined", count = declined.len()), declined),
+        ] {
+            if attendees.is_empty() {
+                continue;
+            }
+            content = content.push(widget::text::heading(heading));
+
+            let mut list =
+                widget::list_column().list_item_padding([space.space_xxs, space.space_xs]);
+            for attendee in attendees {
+                let mut row = widget::row::with_capacity(3)
+                    .spacing(space.space_xs)
                     .align_y(cosmic::iced::Alignment::Center)
                     .push(
-                        widget::text_input("", command)
-                            .on_input(|_| Message::Noop)
-                            .font(cosmic::iced::Font::MONOSPACE)
+                        widget::container(widget::text::body(attendee.display_name()))
                             .width(Length::Fill),
-                    )
-                    .push(
-                        widget::button::text(fl!("keyboard-shortcut-copy"))
-                            .class(cosmic::theme::Button::Standard)
-                            .on_press(Message::CopyToClipboard(command.to_string())),
-                    ),
-            )
-            .padding(space.space_s)
-            .class(cosmic::theme::Container::List),
-        );
-
-        content = content.push(widget::vertical_space().height(space.space_s));
-
-        // Open Settings button
-        content = content.push(
-            widget::container(
-                widget::button::standard(fl!("keyboard-shortcut-open-settings"))
-                    .on_press(Message::OpenCosmicSettings),
-            )
-            .width(Length::Fill)
-            .align_x(cosmic::iced::alignment::Horizontal::Center),
-        );
+                    );
+                if let Some(role) = format_participant_role(attendee) {
+                    row = row.push(widget::text::caption(role).class(secondary_text));
+                }
+                list = list.add(row);
+            }
+            content = content.push(list);
+        }
 
-        content = content.push(widget::vertical_space().height(space.space_m));
+        if let Some(description) = meeting
+            .description
+            .as_deref()
+            .filter(|d| !d.trim().is_empty())
+        {
+            content = content.push(widget::text::heading(fl!("meeting-description")));
+            content = content.push(
+                widget::text::body(description.trim())
+                    .wrapping(cosmic::iced::widget::text::Wrapping::Word),
+            );
+        }
 
         content.into()
     }
@@ -1767,6 +2663,22 @@ pub fn open_url(url: &str) -> bool {
         .is_ok()
 }
 
+/// Open a meeting link the way its provider is set up to: with the provider's
+/// command, in its desktop app, or in the default browser.
+/// Returns true if the command was spawned successfully.
+pub fn join_meeting(config: &Config, url: &str) -> bool {
+    let Some(provider) = config.provider_for_url(url) else {
+        return open_url(url);
+    };
+    let url = provider.launch_url(url);
+    match provider.join_command(&url) {
+        Some(command) => command
+            .split_first()
+            .is_some_and(|(cmd, args)| std::process::Command::new(cmd).args(args).spawn().is_ok()),
+        None => open_url(&url),
+    }
+}
+
 /// Open the system default calendar application.
 fn open_system_calendar() {
     // Query the default calendar application
@@ -1804,7 +2716,7 @@ pub enum Message {
     TogglePopup,
     PopupClosed(Id),
     UpdateConfig(Config),
-    MeetingsUpdated(Vec<Meeting>),
+    MeetingsUpdated(MeetingsFetch),
     CalendarsLoaded(Vec<CalendarInfo>),
     ToggleCalendar(String),
     SelectDisplayFormat(usize),
@@ -1819,16 +2731,48 @@ pub enum Message {
     SetPanelShowLocation(bool),
     SetPanelCalendarIndicator(bool),
     SetPopupCalendarIndicator(bool),
-    UpdatePattern(usize, String),
-    AddPattern,
-    RemovePattern(usize),
+    /// Open a meeting link through its provider
+    JoinMeeting(String),
+    /// Show or hide the menu of a meeting's links in the popup, by meeting UID
+    ToggleJoinMenu(String),
+    /// Open the popup on a meeting's details to pick one of its links, by meeting UID
+    ChooseMeetingLink(String),
+    /// Join with a link picked from the menu and remember it for the event's
+    /// series: event UID, link
+    JoinMeetingLink(String, String),
+    AddProvider,
+    RemoveProvider(usize),
+    SetProviderName(usize, String),
+    SetProviderIcon(usize, String),
+    SetProviderOpenInApp(usize, bool),
+    SetProviderCommand(usize, String),
+    /// Edit a provider's pattern: provider index, pattern index, new pattern
+    UpdatePattern(usize, usize, String),
+    AddPattern(usize),
+    RemovePattern(usize, usize),
     SetShowAllDayEvents(bool),
     SetInProgressMeeting(usize),
     SetEventStatusFilter(usize),
+    SetHideFreeEvents(bool),
     SetTimeUntilFilter(usize),
+    SetLookBehind(usize),
+    SetLookAhead(usize),
     UpdateEmail(usize, String),
     AddEmail,
     RemoveEmail(usize),
+    UpdateIcsPath(usize, String),
+    AddIcsPath,
+    RemoveIcsPath(usize),
+    UpdateVdirPath(usize, String),
+    AddVdirPath,
+    RemoveVdirPath(usize),
+    UpdateWebcalUrl(usize, String),
+    AddWebcalUrl,
+    RemoveWebcalUrl(usize),
+    UpdateCalDavUrl(usize, String),
+    UpdateCalDavUsername(usize, String),
+    AddCalDavAccount,
+    RemoveCalDavAccount(usize),
     RefreshCalendars,
     RefreshCompleted,
     SetAutoRefresh(bool),
@@ -1844,6 +2788,14 @@ pub enum Message {
     AccountsChecked(Vec<crate::calendar::AccountNeedingAttention>),
     /// User clicked to open GNOME Online Accounts settings
     OpenOnlineAccounts,
+    /// Query every calendar again for the diagnostics page
+    RunDiagnostics,
+    /// Diagnostics finished for every calendar
+    DiagnosticsLoaded(Vec<CalendarDiagnostics>),
+    /// Reply to the invitation for the meeting with the given UID
+    RespondToInvitation(String, AttendanceStatus),
+    /// A reply to an invitation was saved, or failed
+    InvitationResponded(Result<(), CalendarError>),
     /// System resumed from sleep or session was unlocked
     SystemResumed,
     SetHideWhenNoMeetings(bool),
@@ -1880,40 +2832,36 @@ impl cosmic::Application for AppModel {
     ) -> (Self, Task<cosmic::Action<Self::Message>>) {
         // Load configuration
         let config_context = cosmic_config::Config::new(Self::APP_ID, Config::VERSION).ok();
-        let config = config_context
+        let mut config = config_context
             .as_ref()
             .map(|ctx| Config::get_entry(ctx).unwrap_or_else(|(_e, c)| c))
             .unwrap_or_default();
-
-        let enabled_uids = config.enabled_calendar_uids.clone();
-        let upcoming_count = config.upcoming_events_count as usize;
-        let additional_emails = config.additional_emails.clone();
+        let migrated = config.migrate_url_patterns();
 
         // Construct the app model with the runtime's core.
-        let app = AppModel {
+        let mut app = AppModel {
             core,
+            sources: SourceSet::from_config(&config),
             config,
             config_context,
             ..Default::default()
         };
+        if migrated {
+            app.save_config();
+        }
 
-        // Fetch initial calendar list and meeting data
-        let calendars_task = Task::perform(
-            async { crate::calendar::get_available_calendars().await },
-            |calendars| Message::CalendarsLoaded(calendars).into(),
-        );
+        // Show the meetings from the last run until the sources answer
+        if let Some(snapshot) = crate::cache::load() {
+            app.upcoming_meetings = snapshot.meetings;
+            app.available_calendars = snapshot.calendars;
+            app.fetched_at = Some(snapshot.fetched_at);
+            app.has_loaded_meetings = true;
+            app.is_stale = true;
+        }
 
-        let meetings_task = Task::perform(
-            async move {
-                crate::calendar::get_upcoming_meetings(
-                    &enabled_uids,
-                    upcoming_count + 1,
-                    &additional_emails,
-                )
-                .await
-            },
-            |meetings| Message::MeetingsUpdated(meetings).into(),
-        );
+        // Fetch initial calendar list and meeting data
+        let calendars_task = app.fetch_calendars_task();
+        let meetings_task = app.fetch_meetings_task(app.config.enabled_calendar_uids.clone());
 
         let accounts_task = Task::perform(
             async { crate::calendar::check_accounts_needing_attention().await },
@@ -1981,7 +2929,7 @@ impl cosmic::Application for AppModel {
             let is_same_day = meeting.start.date_naive() == now.date_naive();
 
             let physical_location = if self.config.panel_show_location {
-                get_physical_location(meeting, &self.config.meeting_url_patterns)
+                get_physical_location(meeting, &self.config.url_patterns())
             } else {
                 None
             };
@@ -2044,21 +2992,19 @@ impl cosmic::Application for AppModel {
                     ..cosmic::iced::font::Font::DEFAULT
                 }))
                 .push(self.core.applet.text(info_str).class(secondary_text));
-            let join_url = match self.config.panel_join_button {
-                JoinButtonVisibility::Show => {
-                    extract_meeting_url(meeting, &self.config.meeting_url_patterns)
-                }
+            let join_links = match self.config.panel_join_button {
+                JoinButtonVisibility::Show => Some(self.config.meeting_links(meeting)),
                 JoinButtonVisibility::ShowIfSameDay if is_same_day => {
-                    extract_meeting_url(meeting, &self.config.meeting_url_patterns)
+                    Some(self.config.meeting_links(meeting))
                 }
                 JoinButtonVisibility::ShowIf30m if minutes_until <= 30 => {
-                    extract_meeting_url(meeting, &self.config.meeting_url_patterns)
+                    Some(self.config.meeting_links(meeting))
                 }
                 JoinButtonVisibility::ShowIf15m if minutes_until <= 15 => {
-                    extract_meeting_url(meeting, &self.config.meeting_url_patterns)
+                    Some(self.config.meeting_links(meeting))
                 }
                 JoinButtonVisibility::ShowIf5m if minutes_until <= 5 => {
-                    extract_meeting_url(meeting, &self.config.meeting_url_patterns)
+                    Some(self.config.meeting_links(meeting))
                 }
                 JoinButtonVisibility::Hide
                 | JoinButtonVisibility::ShowIfSameDay
@@ -2066,8 +3012,18 @@ impl cosmic::Application for AppModel {
                 | JoinButtonVisibility::ShowIf15m
                 | JoinButtonVisibility::ShowIf5m => None,
             };
+            // Icon and action for the Join button, if the meeting has a link
+            let font_size = space.space_xxs + space.space_xxxs;
+            let join = join_links.filter(|links| !links.is_empty()).map(|links| {
+                self.join_action(
+                    meeting,
+                    &links,
+                    font_size,
+                    Message::ChooseMeetingLink(meeting.uid.clone()),
+                )
+            });
 
-            (content, join_url)
+            (content, join)
         } else if !self.has_loaded_meetings {
             // Still loading initial data
             let content =
@@ -2087,6 +3043,33 @@ impl cosmic::Application for AppModel {
             (content, None)
         };
 
+        // Badge counting the invitations still waiting for a reply
+        let pending = filtered
+            .iter()
+            .filter(|m| m.attendance_status == AttendanceStatus::NeedsAction)
+            .count();
+        let panel_content = if pending > 0 {
+            panel_content.push(
+                widget::container(self.core.applet.text(pending.to_string()))
+                    .padding([0, space.space_xxs])
+                    .class(cosmic::theme::Container::custom(|theme| {
+                        cosmic::iced_widget::container::Style {
+                            text_color: Some(theme.cosmic().on_accent_color().into()),
+                            background: Some(cosmic::iced::Background::Color(
+                                theme.cosmic().accent_color().into(),
+                            )),
+                            border: cosmic::iced::Border {
+                                radius: 8.0.into(),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        }
+                    })),
+            )
+        } else {
+            panel_content
+        };
+
         // Main panel button with meeting text
         let constrained_content =
             widget::container(panel_content).padding([space.space_none, space.space_xs]);
@@ -2100,24 +3083,30 @@ impl cosmic::Application for AppModel {
             .spacing(space.space_xxs);
 
         // Add join button next to panel button if we should show it
-        if let Some(url) = show_panel_join {
+        if let Some((icon, on_join)) = show_panel_join {
             // Use space_xxs (8) + space_xxxs (4) = 12px for compact panel text
             let font_size = space.space_xxs + space.space_xxxs;
             row = row.push(
                 widget::button::custom(
-                    widget::text(fl!("join"))
-                        .size(font_size)
-                        .font(cosmic::iced::font::Font {
-                            weight: cosmic::iced::font::Weight::Bold,
-                            ..cosmic::iced::font::Font::DEFAULT
-                        })
-                        .line_height(cosmic::iced::widget::text::LineHeight::Absolute(
-                            font_size.into(),
-                        )),
+                    widget::row::with_capacity(2)
+                        .push(icon)
+                        .push(
+                            widget::text(fl!("join"))
+                                .size(font_size)
+                                .font(cosmic::iced::font::Font {
+                                    weight: cosmic::iced::font::Weight::Bold,
+                                    ..cosmic::iced::font::Font::DEFAULT
+                                })
+                                .line_height(cosmic::iced::widget::text::LineHeight::Absolute(
+                                    font_size.into(),
+                                )),
+                        )
+                        .spacing(space.space_xxxs)
+                        .align_y(cosmic::iced::Alignment::Center),
                 )
                 .padding([space.space_xxxs, space.space_xxs])
                 .class(cosmic::theme::Button::Suggested)
-                .on_press(Message::OpenUrl(url)),
+                .on_press(on_join),
             );
         }
 
@@ -2128,7 +3117,7 @@ impl cosmic::Application for AppModel {
     /// multiple poups, you may match the id parameter to determine which popup to
     /// create a view for.
     fn view_window(&self, _id: Id) -> Element<'_, Self::Message> {
-        let content: Element<'_, Self::Message> = match self.current_page {
+        let content: Element<'_, Self::Message> = match &self.current_page {
             PopupPage::Main => self.view_main_page(),
             PopupPage::Settings => self.view_settings_page(),
             PopupPage::Calendars => self.view_calendars_page(),
@@ -2140,8 +3129,12 @@ impl cosmic::Application for AppModel {
             PopupPage::PopupDisplaySettings => self.view_popup_display_settings_page(),
             PopupPage::PanelJoinButtonSettings => self.view_panel_join_button_settings_page(),
             PopupPage::PopupJoinButtonSettings => self.view_popup_join_button_settings_page(),
+            PopupPage::MeetingProviders => self.view_meeting_providers_page(),
+            PopupPage::MeetingProvider(idx) => self.view_meeting_provider_page(*idx),
             PopupPage::KeyboardShortcut => self.view_keyboard_shortcut_page(),
+            PopupPage::Diagnostics => self.view_diagnostics_page(),
             PopupPage::About => self.view_about_page(),
+            PopupPage::MeetingDetails(uid) => self.view_meeting_details_page(uid),
         };
 
         // Popup size limits
@@ -2172,6 +3165,7 @@ impl cosmic::Application for AppModel {
         let additional_emails = self.config.additional_emails.clone();
         let auto_refresh_enabled = self.config.auto_refresh_enabled;
         let auto_refresh_interval = self.config.auto_refresh_interval_minutes;
+        let window = QueryWindow::from_config(&self.config);
 
         // Create a unique subscription ID based on config values that affect filtering.
         // When these change, the subscription will be recreated with the new values.
@@ -2179,6 +3173,9 @@ impl cosmic::Application for AppModel {
         enabled_uids.hash(&mut hasher);
         upcoming_count.hash(&mut hasher);
         additional_emails.hash(&mut hasher);
+        window.hash(&mut hasher);
+        // The sources themselves, rather than their settings, which change on every keystroke
+        self.sources_generation.hash(&mut hasher);
         let config_hash = hasher.finish();
 
         // Create a separate hash for auto-refresh subscription
@@ -2188,6 +3185,13 @@ impl cosmic::Application for AppModel {
         enabled_uids.hash(&mut refresh_hasher);
         let refresh_hash = refresh_hasher.finish();
 
+        let sources = self.sources.clone();
+        let query = MeetingQuery {
+            enabled_uids,
+            additional_emails,
+            window,
+        };
+
         let mut subscriptions = vec![
             // Periodically read cached calendar and meeting data (every 60 seconds)
             Subscription::run_with_id(
@@ -2196,15 +3200,10 @@ impl cosmic::Application for AppModel {
                     let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
                     loop {
                         interval.tick().await;
-                        // Read cached calendars and meetings
-                        let calendars = crate::calendar::get_available_calendars().await;
+                        // Read cached calendars and meetings (EDS events come from live views)
+                        let calendars = sources.available_calendars().await;
                         let _ = channel.send(Message::CalendarsLoaded(calendars)).await;
-                        let meetings = crate::calendar::get_upcoming_meetings(
-                            &enabled_uids,
-                            upcoming_count + 1,
-                            &additional_emails,
-                        )
-                        .await;
+                        let meetings = sources.upcoming_meetings(&query, upcoming_count + 1).await;
                         let _ = channel.send(Message::MeetingsUpdated(meetings)).await;
                         // Check for GOA accounts needing re-authentication
                         let accounts = crate::calendar::check_accounts_needing_attention().await;
@@ -2221,6 +3220,7 @@ impl cosmic::Application for AppModel {
         // Add auto-refresh subscription if enabled
         if auto_refresh_enabled {
             let refresh_uids = self.config.enabled_calendar_uids.clone();
+            let sources = self.sources.clone();
             subscriptions.push(Subscription::run_with_id(
                 refresh_hash,
                 cosmic::iced::stream::channel(2, move |mut channel| async move {
@@ -2232,7 +3232,7 @@ impl cosmic::Application for AppModel {
                     loop {
                         interval.tick().await;
                         // Trigger a refresh from remote servers
-                        crate::calendar::refresh_calendars(&refresh_uids).await;
+                        sources.refresh(&refresh_uids).await;
                         // Signal that refresh started (the 60-second subscription will pick up new data)
                         let _ = channel.send(Message::RefreshCalendars).await;
                     }
@@ -2240,17 +3240,21 @@ impl cosmic::Application for AppModel {
             ));
         }
 
-        // Watch for D-Bus PropertiesChanged signals from EDS calendars
+        // Watch for calendar content changes (e.g. EDS live calendar view signals)
         // This detects when calendars are updated after a sync (by us or external apps)
         let watch_uids = self.config.enabled_calendar_uids.clone();
+        let watch_sources = self.sources.clone();
         subscriptions.push(Subscription::run_with_id(
             ("calendar-changes", config_hash),
             cosmic::iced::stream::channel(4, move |mut channel| async move {
                 let (sender, mut receiver) = tokio::sync::mpsc::channel::<()>(4);
 
                 // Spawn the watcher in a separate task
-                let watch_task =
-                    tokio::spawn(crate::calendar::watch_calendar_changes(watch_uids, sender));
+                let watch_task = tokio::spawn(async move {
+                    watch_sources
+                        .watch_changes(watch_uids, window, sender)
+                        .await;
+                });
 
                 // Forward messages from the watcher to the iced channel
                 while receiver.recv().await.is_some() {
@@ -2262,14 +3266,17 @@ impl cosmic::Application for AppModel {
             }),
         ));
 
-        // Watch for new/removed calendar sources via ObjectManager signals
+        // Watch for new/removed calendars (e.g. EDS ObjectManager signals)
         // Detects when the user adds or removes a calendar account
+        let list_sources = self.sources.clone();
         subscriptions.push(Subscription::run_with_id(
-            "source-changes",
+            ("source-changes", config_hash),
             cosmic::iced::stream::channel(2, move |mut channel| async move {
                 let (sender, mut receiver) = tokio::sync::mpsc::channel::<()>(2);
 
-                let watch_task = tokio::spawn(crate::calendar::watch_source_changes(sender));
+                let watch_task = tokio::spawn(async move {
+                    list_sources.watch_calendar_list(sender).await;
+                });
 
                 while receiver.recv().await.is_some() {
                     let _ = channel.send(Message::SourcesChanged).await;
@@ -2311,13 +3318,47 @@ impl cosmic::Application for AppModel {
     fn update(&mut self, message: Self::Message) -> Task<cosmic::Action<Self::Message>> {
         match message {
             Message::UpdateConfig(config) => {
+                let sources_changed = config.ics_calendar_paths != self.config.ics_calendar_paths
+                    || config.vdir_paths != self.config.vdir_paths
+                    || config.caldav_accounts != self.config.caldav_accounts
+                    || config.webcal_urls != self.config.webcal_urls
+                    || config.auto_refresh_interval_minutes
+                        != self.config.auto_refresh_interval_minutes;
                 self.config = config;
+                if sources_changed {
+                    return self.reload_sources();
+                }
             }
-            Message::MeetingsUpdated(meetings) => {
-                self.upcoming_meetings = meetings;
+            Message::MeetingsUpdated(fetch) => {
+                self.sources_error = fetch.all_failed.then(|| {
+                    fetch
+                        .source_errors
+                        .first()
+                        .or_else(|| fetch.errors.values().next())
+                        .cloned()
+                        .unwrap_or(CalendarError::Unreachable)
+                });
+                self.calendar_errors = fetch.errors;
                 self.has_loaded_meetings = true;
+
+                // Nothing could be read, which says nothing about the
+                // meetings, so keep the earlier ones and mark them out of date
+                if fetch.all_failed {
+                    self.is_stale = true;
+                    return Task::none();
+                }
+                self.is_stale = false;
+                self.upcoming_meetings = fetch.meetings;
+                self.fetched_at = Some(chrono::Local::now());
+                return self.save_snapshot_task();
             }
             Message::CalendarsLoaded(calendars) => {
+                // EDS always has some calendars, so an empty list means no
+                // source answered. Keep the ones we have.
+                if calendars.is_empty() && !self.available_calendars.is_empty() {
+                    return Task::none();
+                }
+
                 // Auto-enable newly discovered meeting-source calendars
                 // (only when the user has explicitly selected calendars; if the
                 // list is empty, all calendars are already implicitly enabled)
@@ -2340,6 +3381,7 @@ impl cosmic::Application for AppModel {
                     }
                 }
                 self.available_calendars = calendars;
+                return self.save_snapshot_task();
             }
             Message::ToggleCalendar(uid) => {
                 // If the list is empty (all enabled), populate it with all meeting-source calendars
@@ -2363,20 +3405,7 @@ impl cosmic::Application for AppModel {
                 self.save_config();
 
                 // Refresh meetings with new filter
-                let enabled_uids = self.enabled_meeting_source_uids();
-                let upcoming_count = self.config.upcoming_events_count as usize;
-                let additional_emails = self.config.additional_emails.clone();
-                return Task::perform(
-                    async move {
-                        crate::calendar::get_upcoming_meetings(
-                            &enabled_uids,
-                            upcoming_count + 1,
-                            &additional_emails,
-                        )
-                        .await
-                    },
-                    |meetings| Message::MeetingsUpdated(meetings).into(),
-                );
+                return self.fetch_meetings_task(self.enabled_meeting_source_uids());
             }
             Message::SelectDisplayFormat(idx) => {
                 self.config.display_format = match idx {
@@ -2392,22 +3421,12 @@ impl cosmic::Application for AppModel {
                 }
                 self.save_config();
                 // Refresh meetings with new count
-                let enabled_uids = self.config.enabled_calendar_uids.clone();
-                let upcoming_count = self.config.upcoming_events_count as usize;
-                let additional_emails = self.config.additional_emails.clone();
-                return Task::perform(
-                    async move {
-                        crate::calendar::get_upcoming_meetings(
-                            &enabled_uids,
-                            upcoming_count + 1,
-                            &additional_emails,
-                        )
-                        .await
-                    },
-                    |meetings| Message::MeetingsUpdated(meetings).into(),
-                );
+                return self.fetch_meetings_task(self.config.enabled_calendar_uids.clone());
             }
             Message::Navigate(page) => {
+                if page != self.current_page {
+                    self.response_error = None;
+                }
                 // Save config when leaving display settings pages (for slider values)
                 if matches!(
                     self.current_page,
@@ -2415,7 +3434,55 @@ impl cosmic::Application for AppModel {
                 ) {
                     self.save_config();
                 }
+                // Read edited paths, accounts and subscriptions when leaving the Calendars page
+                if self.current_page == PopupPage::Calendars
+                    && page != PopupPage::Calendars
+                    && self.sources_edited
+                {
+                    self.current_page = page;
+                    return self.reload_sources();
+                }
+                let run_diagnostics =
+                    page == PopupPage::Diagnostics && self.current_page != PopupPage::Diagnostics;
                 self.current_page = page;
+                if run_diagnostics {
+                    return self.run_diagnostics_task();
+                }
+            }
+            Message::RunDiagnostics => {
+                return self.run_diagnostics_task();
+            }
+            Message::DiagnosticsLoaded(diagnostics) => {
+                self.diagnostics = Some(diagnostics);
+            }
+            Message::RespondToInvitation(uid, response) => {
+                let Some(meeting) = self
+                    .upcoming_meetings
+                    .iter()
+                    .find(|m| m.uid == uid)
+                    .cloned()
+                else {
+                    return Task::none();
+                };
+                self.responding_to = Some(uid);
+                self.response_error = None;
+                let sources = self.sources.clone();
+                let additional_emails = self.config.additional_emails.clone();
+                return Task::perform(
+                    async move {
+                        sources
+                            .respond(&meeting, response, &additional_emails)
+                            .await
+                    },
+                    |result| Message::InvitationResponded(result).into(),
+                );
+            }
+            Message::InvitationResponded(result) => {
+                self.responding_to = None;
+                match result {
+                    Ok(()) => return self.fetch_meetings_task(self.enabled_meeting_source_uids()),
+                    Err(error) => self.response_error = Some(error),
+                }
             }
             Message::OpenCalendar => {
                 use crate::config::CalendarAppAction;
@@ -2492,19 +3559,92 @@ impl cosmic::Application for AppModel {
                 self.config.popup_calendar_indicator = enabled;
                 self.save_config();
             }
-            Message::UpdatePattern(idx, pattern) => {
-                if idx < self.config.meeting_url_patterns.len() {
-                    self.config.meeting_url_patterns[idx] = pattern;
-                    self.save_config();
+            Message::JoinMeeting(url) => {
+                join_meeting(&self.config, &url);
+            }
+            Message::ToggleJoinMenu(uid) => {
+                self.join_menu = if self.join_menu.as_ref() == Some(&uid) {
+                    None
+                } else {
+                    Some(uid)
+                };
+            }
+            Message::ChooseMeetingLink(uid) => {
+                // The details page always lists every link, whatever the popup shows
+                self.current_page = PopupPage::MeetingDetails(uid);
+                if self.popup.is_none() {
+                    return self.open_popup();
                 }
             }
-            Message::AddPattern => {
-                self.config.meeting_url_patterns.push(String::new());
+            Message::JoinMeetingLink(event_uid, url) => {
+                self.config
+                    .preferred_meeting_links
+                    .insert(event_uid, url.clone());
+                self.save_config();
+                self.join_menu = None;
+                join_meeting(&self.config, &url);
+            }
+            Message::AddProvider => {
+                self.config
+                    .meeting_providers
+                    .push(MeetingProvider::custom(vec![String::new()]));
                 self.save_config();
+                self.current_page =
+                    PopupPage::MeetingProvider(self.config.meeting_providers.len() - 1);
+            }
+            Message::RemoveProvider(idx) => {
+                if idx < self.config.meeting_providers.len() {
+                    self.config.meeting_providers.remove(idx);
+                    self.save_config();
+                }
+                self.current_page = PopupPage::MeetingProviders;
             }
-            Message::RemovePattern(idx) => {
-                if idx < self.config.meeting_url_patterns.len() {
-                    self.config.meeting_url_patterns.remove(idx);
+            Message::SetProviderName(idx, name) => {
+                if let Some(provider) = self.config.meeting_providers.get_mut(idx) {
+                    provider.name = name;
+                    self.save_config();
+                }
+            }
+            Message::SetProviderIcon(idx, icon) => {
+                if let Some(provider) = self.config.meeting_providers.get_mut(idx) {
+                    provider.icon = icon;
+                    self.save_config();
+                }
+            }
+            Message::SetProviderOpenInApp(idx, enabled) => {
+                if let Some(provider) = self.config.meeting_providers.get_mut(idx) {
+                    provider.open_in_app = enabled;
+                    self.save_config();
+                }
+            }
+            Message::SetProviderCommand(idx, command) => {
+                if let Some(provider) = self.config.meeting_providers.get_mut(idx) {
+                    provider.launch_command = command;
+                    self.save_config();
+                }
+            }
+            Message::UpdatePattern(idx, pattern_idx, pattern) => {
+                if let Some(slot) = self
+                    .config
+                    .meeting_providers
+                    .get_mut(idx)
+                    .and_then(|p| p.patterns.get_mut(pattern_idx))
+                {
+                    *slot = pattern;
+                    self.save_config();
+                }
+            }
+            Message::AddPattern(idx) => {
+                if let Some(provider) = self.config.meeting_providers.get_mut(idx) {
+                    provider.patterns.push(String::new());
+                    self.save_config();
+                }
+            }
+            Message::RemovePattern(idx, pattern_idx) => {
+                if let Some(provider) = self.config.meeting_providers.get_mut(idx)
+                    && pattern_idx < provider.patterns.len()
+                {
+                    provider.patterns.remove(pattern_idx);
                     self.save_config();
                 }
             }
@@ -2513,13 +3653,12 @@ impl cosmic::Application for AppModel {
                 self.save_config();
             }
             Message::SetInProgressMeeting(idx) => {
-                self.config.show_in_progress = match idx {
-                    1 => InProgressMeeting::Within5m,
-                    2 => InProgressMeeting::Within10m,
-                    3 => InProgressMeeting::Within15m,
-                    4 => InProgressMeeting::Within30m,
-                    _ => InProgressMeeting::Off, // 0 or any other value
-                };
+                self.config.show_in_progress = self
+                    .config
+                    .in_progress_options()
+                    .get(idx)
+                    .copied()
+                    .unwrap_or(InProgressMeeting::Off);
                 self.save_config();
             }
             Message::SetEventStatusFilter(idx) => {
@@ -2531,18 +3670,35 @@ impl cosmic::Application for AppModel {
                 };
                 self.save_config();
             }
+            Message::SetHideFreeEvents(enabled) => {
+                self.config.hide_free_events = enabled;
+                self.save_config();
+            }
             Message::SetTimeUntilFilter(idx) => {
-                use crate::config::TimeUntilFilter;
-                self.config.time_until_filter = match idx {
-                    1 => TimeUntilFilter::Within3Hours,
-                    2 => TimeUntilFilter::Within6Hours,
-                    3 => TimeUntilFilter::SameDay,
-                    4 => TimeUntilFilter::Within1Day,
-                    5 => TimeUntilFilter::Within2Days,
-                    _ => TimeUntilFilter::All, // 0 or any other value
-                };
+                self.config.time_until_filter = self
+                    .config
+                    .time_until_options()
+                    .get(idx)
+                    .copied()
+                    .unwrap_or_default();
                 self.save_config();
             }
+            Message::SetLookBehind(idx) => {
+                if let Some(minutes) = LOOK_BEHIND_OPTIONS.get(idx) {
+                    self.config.look_behind_minutes = *minutes;
+                    self.config.clamp_filters_to_window();
+                    self.save_config();
+                    return self.fetch_meetings_task(self.enabled_meeting_source_uids());
+                }
+            }
+            Message::SetLookAhead(idx) => {
+                if let Some(days) = LOOK_AHEAD_OPTIONS.get(idx) {
+                    self.config.look_ahead_days = *days;
+                    self.config.clamp_filters_to_window();
+                    self.save_config();
+                    return self.fetch_meetings_task(self.enabled_meeting_source_uids());
+                }
+            }
             Message::UpdateEmail(idx, email) => {
                 if let Some(e) = self.config.additional_emails.get_mut(idx) {
                     *e = email;
@@ -2562,27 +3718,122 @@ impl cosmic::Application for AppModel {
                 }
                 self.save_config();
             }
+            Message::UpdateIcsPath(idx, path) => {
+                if let Some(p) = self.config.ics_calendar_paths.get_mut(idx) {
+                    *p = path;
+                }
+                self.save_config();
+                // Read the file once editing is done rather than on every keystroke
+                self.sources_edited = true;
+            }
+            Message::AddIcsPath => {
+                let new_idx = self.config.ics_calendar_paths.len();
+                self.config.ics_calendar_paths.push(String::new());
+                self.save_config();
+                // Focus the new path input field
+                return cosmic::widget::text_input::focus(ics_path_input_id(new_idx));
+            }
+            Message::RemoveIcsPath(idx) => {
+                if idx < self.config.ics_calendar_paths.len() {
+                    self.config.ics_calendar_paths.remove(idx);
+                }
+                self.save_config();
+                return self.reload_sources();
+            }
+            Message::UpdateVdirPath(idx, path) => {
+                if let Some(p) = self.config.vdir_paths.get_mut(idx) {
+                    *p = path;
+                }
+                self.save_config();
+                self.sources_edited = true;
+            }
+            Message::AddVdirPath => {
+                let new_idx = self.config.vdir_paths.len();
+                self.config.vdir_paths.push(String::new());
+                self.save_config();
+                // Focus the new path input field
+                return cosmic::widget::text_input::focus(vdir_path_input_id(new_idx));
+            }
+            Message::RemoveVdirPath(idx) => {
+                if idx < self.config.vdir_paths.len() {
+                    self.config.vdir_paths.remove(idx);
+                }
+                self.save_config();
+                return self.reload_sources();
+            }
+            Message::UpdateWebcalUrl(idx, url) => {
+                if let Some(u) = self.config.webcal_urls.get_mut(idx) {
+                    *u = url;
+                }
+                self.save_config();
+                // Download once editing is done rather than on every keystroke
+                self.sources_edited = true;
+            }
+            Message::AddWebcalUrl => {
+                let new_idx = self.config.webcal_urls.len();
+                self.config.webcal_urls.push(String::new());
+                self.save_config();
+                // Focus the new URL input field
+                return cosmic::widget::text_input::focus(webcal_url_input_id(new_idx));
+            }
+            Message::RemoveWebcalUrl(idx) => {
+                if idx < self.config.webcal_urls.len() {
+                    self.config.webcal_urls.remove(idx);
+                }
+                self.save_config();
+                return self.reload_sources();
+            }
+            Message::UpdateCalDavUrl(idx, url) => {
+                if let Some(account) = self.config.caldav_accounts.get_mut(idx) {
+                    account.url = url;
+                }
+                self.save_config();
+                // Connect once editing is done rather than on every keystroke
+                self.sources_edited = true;
+            }
+            Message::UpdateCalDavUsername(idx, username) => {
+                if let Some(account) = self.config.caldav_accounts.get_mut(idx) {
+                    account.username = username;
+                }
+                self.save_config();
+                self.sources_edited = true;
+            }
+            Message::AddCalDavAccount => {
+                let new_idx = self.config.caldav_accounts.len();
+                self.config
+                    .caldav_accounts
+                    .push(crate::config::CalDavAccount::default());
+                self.save_config();
+                // Focus the new URL input field
+                return cosmic::widget::text_input::focus(caldav_url_input_id(new_idx));
+            }
+            Message::RemoveCalDavAccount(idx) => {
+                if idx < self.config.caldav_accounts.len() {
+                    self.config.caldav_accounts.remove(idx);
+                }
+                self.save_config();
+                return self.reload_sources();
+            }
             Message::RefreshCalendars => {
                 if !self.is_refreshing {
                     self.is_refreshing = true;
-                    let enabled_uids = self.enabled_meeting_source_uids();
-                    let upcoming_count = self.config.upcoming_events_count as usize;
-                    let additional_emails = self.config.additional_emails.clone();
+                    let sources = self.sources.clone();
+                    let query = MeetingQuery {
+                        enabled_uids: self.enabled_meeting_source_uids(),
+                        additional_emails: self.config.additional_emails.clone(),
+                        window: QueryWindow::from_config(&self.config),
+                    };
+                    let limit = self.config.upcoming_events_count as usize + 1;
                     return Task::perform(
                         async move {
-                            // Ask EDS to re-discover calendars from accounts
-                            crate::calendar::refresh_source_backends().await;
+                            // Ask sources to re-discover calendars from accounts
+                            sources.discover().await;
                             // Refresh existing calendars from remote servers
-                            crate::calendar::refresh_calendars(&enabled_uids).await;
-                            // Wait a moment for EDS to process
+                            sources.refresh(&query.enabled_uids).await;
+                            // Wait a moment for the sources to process
                             tokio::time::sleep(std::time::Duration::from_millis(500)).await;
                             // Then fetch updated meetings
-                            crate::calendar::get_upcoming_meetings(
-                                &enabled_uids,
-                                upcoming_count + 1,
-                                &additional_emails,
-                            )
-                            .await
+                            sources.upcoming_meetings(&query, limit).await
                         },
                         |meetings| Message::MeetingsUpdated(meetings).into(),
                     )
@@ -2604,6 +3855,10 @@ impl cosmic::Application for AppModel {
                     _ => 10, // 1 or any other value
                 };
                 self.save_config();
+                // Subscriptions are polled on this interval
+                if !self.config.webcal_urls.is_empty() {
+                    return self.reload_sources();
+                }
             }
             Message::SetShowCalendarButton(enabled) => {
                 self.config.show_calendar_button = enabled;
@@ -2646,52 +3901,16 @@ impl cosmic::Application for AppModel {
             Message::SourcesChanged => {
                 // EDS sources were added or removed — refresh calendar list and meetings.
                 // The CalendarsLoaded handler will auto-enable any new calendars.
-                let enabled_uids = self.enabled_meeting_source_uids();
-                let upcoming_count = self.config.upcoming_events_count as usize;
-                let additional_emails = self.config.additional_emails.clone();
-
-                let calendars_task = Task::perform(
-                    async { crate::calendar::get_available_calendars().await },
-                    |calendars| Message::CalendarsLoaded(calendars).into(),
-                );
-
-                let meetings_task = Task::perform(
-                    async move {
-                        crate::calendar::get_upcoming_meetings(
-                            &enabled_uids,
-                            upcoming_count + 1,
-                            &additional_emails,
-                        )
-                        .await
-                    },
-                    |meetings| Message::MeetingsUpdated(meetings).into(),
-                );
+                let calendars_task = self.fetch_calendars_task();
+                let meetings_task = self.fetch_meetings_task(self.enabled_meeting_source_uids());
 
                 return Task::batch([calendars_task, meetings_task]);
             }
             Message::CalendarChanged => {
                 // A calendar was updated via D-Bus signal (sync completed)
                 // Refresh both calendars list (for updated sync timestamps) and meetings
-                let enabled_uids = self.enabled_meeting_source_uids();
-                let upcoming_count = self.config.upcoming_events_count as usize;
-                let additional_emails = self.config.additional_emails.clone();
-
-                let calendars_task = Task::perform(
-                    async { crate::calendar::get_available_calendars().await },
-                    |calendars| Message::CalendarsLoaded(calendars).into(),
-                );
-
-                let meetings_task = Task::perform(
-                    async move {
-                        crate::calendar::get_upcoming_meetings(
-                            &enabled_uids,
-                            upcoming_count + 1,
-                            &additional_emails,
-                        )
-                        .await
-                    },
-                    |meetings| Message::MeetingsUpdated(meetings).into(),
-                );
+                let calendars_task = self.fetch_calendars_task();
+                let meetings_task = self.fetch_meetings_task(self.enabled_meeting_source_uids());
 
                 return Task::batch([calendars_task, meetings_task]);
             }
@@ -2699,40 +3918,25 @@ impl cosmic::Application for AppModel {
                 // System woke from sleep or session was unlocked
                 // Refresh immediately to show current data, and optionally trigger EDS sync
                 let enabled_uids = self.enabled_meeting_source_uids();
-                let upcoming_count = self.config.upcoming_events_count as usize;
-                let additional_emails = self.config.additional_emails.clone();
 
                 let mut tasks = vec![];
 
-                // If auto-refresh is enabled, tell EDS to fetch fresh data from remote servers
+                // If auto-refresh is enabled, tell the sources to fetch fresh data from remote servers
                 // The CalendarChanged handler will fire again when EDS finishes syncing
                 if self.config.auto_refresh_enabled {
                     let refresh_uids = enabled_uids.clone();
+                    let sources = self.sources.clone();
                     tasks.push(Task::perform(
                         async move {
-                            crate::calendar::refresh_calendars(&refresh_uids).await;
+                            sources.refresh(&refresh_uids).await;
                         },
                         |()| Message::Noop.into(),
                     ));
                 }
 
                 // Also immediately refresh local data so we show what's cached
-                tasks.push(Task::perform(
-                    async { crate::calendar::get_available_calendars().await },
-                    |calendars| Message::CalendarsLoaded(calendars).into(),
-                ));
-
-                tasks.push(Task::perform(
-                    async move {
-                        crate::calendar::get_upcoming_meetings(
-                            &enabled_uids,
-                            upcoming_count + 1,
-                            &additional_emails,
-                        )
-                        .await
-                    },
-                    |meetings| Message::MeetingsUpdated(meetings).into(),
-                ));
+                tasks.push(self.fetch_calendars_task());
+                tasks.push(self.fetch_meetings_task(enabled_uids));
 
                 return Task::batch(tasks);
             }
@@ -2740,17 +3944,7 @@ impl cosmic::Application for AppModel {
                 return if let Some(p) = self.popup.take() {
                     destroy_popup(p)
                 } else {
-                    let new_id = Id::unique();
-                    self.popup.replace(new_id);
-                    let mut popup_settings = self.core.applet.get_popup_settings(
-                        self.core.main_window_id().unwrap(),
-                        new_id,
-                        None,
-                        None,
-                        None,
-                    );
-                    popup_settings.positioner.size_limits = Limits::NONE;
-                    get_popup(popup_settings)
+                    self.open_popup()
                 };
             }
             Message::PopupClosed(id) => {
@@ -2760,6 +3954,11 @@ impl cosmic::Application for AppModel {
                     self.save_config();
                     // Reset to main page for next open
                     self.current_page = PopupPage::Main;
+                    self.join_menu = None;
+                    // Apply source edits made just before the popup closed
+                    if self.sources_edited {
+                        return self.reload_sources();
+                    }
                 }
             }
         }
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//
+// The last meetings and calendars fetched, kept on disk so the applet has
+// something to show before the calendar sources answer.
+
+use crate::calendar::{CalendarInfo, Meeting};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+/// Prefix for the cache directory, shared with the calendar sources' caches.
+const CACHE_PREFIX: &str = "com.dangrover.next-meeting-app";
+
+const SNAPSHOT_FILE: &str = "meetings.json";
+
+/// Meetings and calendars as of the last successful fetch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub fetched_at: DateTime<Local>,
+    pub meetings: Vec<Meeting>,
+    pub calendars: Vec<CalendarInfo>,
+}
+
+/// Read the snapshot saved by the last run, if there is a readable one.
+pub fn load() -> Option<Snapshot> {
+    let path = xdg::BaseDirectories::with_prefix(CACHE_PREFIX).find_cache_file(SNAPSHOT_FILE)?;
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Save a snapshot, replacing the previous one.
+pub async fn save(snapshot: Snapshot) {
+    let Ok(path) = xdg::BaseDirectories::with_prefix(CACHE_PREFIX).place_cache_file(SNAPSHOT_FILE)
+    else {
+        return;
+    };
+    let Ok(contents) = serde_json::to_string(&snapshot) else {
+        return;
+    };
+
+    // Write a temporary file and rename it, so a crash can't leave half a snapshot
+    let temp_path = path.with_extension("json.tmp");
+    if tokio::fs::write(&temp_path, contents).await.is_ok() {
+        let _ = tokio::fs::rename(&temp_path, &path).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::{AttendanceStatus, EventStatus};
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let start = Local::now();
+        let snapshot = Snapshot {
+            fetched_at: start,
+            meetings: vec![Meeting {
+                uid: "standup@example.com".to_string(),
+                title: "Standup".to_string(),
+                start,
+                end: start + chrono::Duration::minutes(15),
+                location: Some("https://meet.google.com/abc-defg-hij".to_string()),
+                description: None,
+                calendar_uid: "work".to_string(),
+                is_all_day: false,
+                attendance_status: AttendanceStatus::Tentative,
+                status: EventStatus::Confirmed,
+                is_free: false,
+                organizer: None,
+                attendees: Vec::new(),
+                conference_urls: Vec::new(),
+                dial_ins: Vec::new(),
+                description_links: Vec::new(),
+            }],
+            calendars: vec![CalendarInfo {
+                uid: "work".to_string(),
+                display_name: "Work".to_string(),
+                color: Some("#62a0ea".to_string()),
+                last_synced: None,
+                backend: Some("caldav".to_string()),
+            }],
+        };
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let loaded: Snapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.fetched_at, snapshot.fetched_at);
+        assert_eq!(loaded.meetings[0].title, "Standup");
+        assert_eq!(loaded.meetings[0].end, snapshot.meetings[0].end);
+        assert_eq!(
+            loaded.meetings[0].attendance_status,
+            AttendanceStatus::Tentative
+        );
+        assert_eq!(loaded.calendars[0].display_name, "Work");
+    }
+}
@@ -1,18 +1,42 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::config::Config;
+use calcard::common::PartialDateTime;
 use calcard::icalendar::{
-    ICalendar, ICalendarComponentType, ICalendarEntry, ICalendarParameterName,
-    ICalendarParameterValue, ICalendarParticipationStatus, ICalendarProperty, ICalendarValue,
-    ICalendarValueType, dates::TimeOrDelta,
+    ICalendar, ICalendarComponentType, ICalendarEntry, ICalendarFeatureType, ICalendarFrequency,
+    ICalendarParameterName, ICalendarParameterValue, ICalendarParticipationRole,
+    ICalendarParticipationStatus, ICalendarProperty, ICalendarStatus, ICalendarTransparency,
+    ICalendarUserTypes, ICalendarValue, ICalendarValueType, dates::TimeOrDelta,
 };
-use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone};
 use chrono_tz::Tz;
 use futures_util::StreamExt;
+use futures_util::future::{BoxFuture, join_all};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
 use zbus::{Connection, zvariant};
 
+mod caldav;
+mod eds;
+mod html;
+mod http;
+mod ics;
+mod secrets;
+mod vdir;
+mod webcal;
+
+pub use caldav::CalDavSource;
+pub use eds::EdsSource;
+pub use ics::IcsFileSource;
+pub use vdir::VdirSource;
+pub use webcal::WebcalSource;
+
 /// User's attendance status for a meeting
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum AttendanceStatus {
     /// User has accepted the meeting
     Accepted,
@@ -27,9 +51,99 @@ pub enum AttendanceStatus {
     None,
 }
 
-#[derive(Debug, Clone)]
+/// The organizer's status for an event (`STATUS`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EventStatus {
+    /// Confirmed, or no status given
+    #[default]
+    Confirmed,
+    /// Not yet confirmed by the organizer
+    Tentative,
+    /// Called off, but still in the calendar
+    Cancelled,
+}
+
+/// What kind of calendar user a participant is (`CUTYPE`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ParticipantKind {
+    #[default]
+    Individual,
+    Group,
+    Resource,
+    Room,
+    Unknown,
+}
+
+/// A participant's role in a meeting (`ROLE`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ParticipantRole {
+    Chair,
+    #[default]
+    Required,
+    Optional,
+    NonParticipant,
+}
+
+/// The organizer or an attendee of a meeting
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Participant {
+    /// Display name (`CN`)
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub role: ParticipantRole,
+    /// Their reply to the invitation (`PARTSTAT`)
+    pub status: AttendanceStatus,
+    pub kind: ParticipantKind,
+}
+
+/// A phone number for joining a meeting by phone
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DialIn {
+    /// The number as written in the invitation
+    pub number: String,
+    /// Conference ID or PIN to enter once connected
+    pub pin: Option<String>,
+    /// Country or city the number is for, e.g. "US (New York)"
+    pub label: Option<String>,
+}
+
+impl DialIn {
+    /// The number followed by pauses and the PIN, the way phones dial it.
+    #[must_use]
+    pub fn dial_string(&self) -> String {
+        let digits: String = self
+            .number
+            .chars()
+            .filter(|c| c.is_ascii_digit() || *c == '+')
+            .collect();
+        match &self.pin {
+            Some(pin) => format!("{digits},,{pin}#"),
+            None => digits,
+        }
+    }
+
+    /// A `tel:` link that dials the number and enters the PIN.
+    #[must_use]
+    pub fn tel_uri(&self) -> String {
+        format!("tel:{}", self.dial_string())
+    }
+}
+
+impl Participant {
+    /// Name to show for the participant: their name, else their email.
+    #[must_use]
+    pub fn display_name(&self) -> &str {
+        self.name
+            .as_deref()
+            .filter(|n| !n.trim().is_empty())
+            .or(self.email.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Meeting {
-    #[allow(dead_code)]
+    /// The event's `UID`, followed by `@` and the instance's original start.
     pub uid: String,
     pub title: String,
     pub start: DateTime<Local>,
@@ -40,9 +154,46 @@ pub struct Meeting {
     pub calendar_uid: String,
     pub is_all_day: bool,
     pub attendance_status: AttendanceStatus,
+    #[serde(default)]
+    pub status: EventStatus,
+    /// Whether the event is marked as free time (`TRANSP:TRANSPARENT`).
+    #[serde(default)]
+    pub is_free: bool,
+    #[serde(default)]
+    pub organizer: Option<Participant>,
+    #[serde(default)]
+    pub attendees: Vec<Participant>,
+    /// Video call links from the event's conference properties, best first.
+    #[serde(default)]
+    pub conference_urls: Vec<String>,
+    /// Phone numbers for joining by phone.
+    #[serde(default)]
+    pub dial_ins: Vec<DialIn>,
+    /// Links from the HTML description, which often only appear behind link text.
+    #[serde(default)]
+    pub description_links: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+impl Meeting {
+    /// The event's own `UID`, shared by every instance of a recurring event.
+    #[must_use]
+    pub fn event_uid(&self) -> &str {
+        self.uid
+            .rsplit_once('@')
+            .map_or(self.uid.as_str(), |(uid, _)| uid)
+    }
+
+    /// The instance's original start, which tells it apart from the other
+    /// instances of a recurring event.
+    #[must_use]
+    pub fn series_start(&self) -> Option<DateTime<Local>> {
+        let (_, start) = self.uid.rsplit_once('@')?;
+        let naive = NaiveDateTime::parse_from_str(start, "%Y%m%dT%H%M%S").ok()?;
+        Local.from_local_datetime(&naive).earliest()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalendarInfo {
     pub uid: String,
     pub display_name: String,
@@ -65,391 +216,635 @@ impl CalendarInfo {
     }
 }
 
-/// An online account that needs the user's attention (e.g. re-authentication).
-#[derive(Debug, Clone)]
-pub struct AccountNeedingAttention {
-    /// Display-friendly identity (e.g. "dangrover@fastmail.com")
-    pub identity: String,
+/// Why meetings couldn't be read from a calendar, or from a whole source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalendarError {
+    /// The service behind the source (session bus, EDS, server) couldn't be reached.
+    Unreachable,
+    /// The calendar couldn't be opened. Holds the service's error message.
+    OpenFailed(String),
+    /// Asking the calendar for its events failed. Holds the service's error message.
+    QueryFailed(String),
+    /// The calendar didn't answer in time.
+    TimedOut,
+    /// The server rejected the credentials, or none are stored.
+    AuthFailed,
+    /// The server answered with an unexpected HTTP status.
+    HttpStatus(u16),
+    /// The configured URL isn't valid.
+    InvalidUrl,
+    /// A file or folder couldn't be read. Holds the I/O error message.
+    Unreadable(String),
+    /// The server's answer wasn't a calendar.
+    InvalidData,
+    /// This many events couldn't be parsed; the others are still shown.
+    InvalidEvents(usize),
+    /// The calendar can't be written to.
+    ReadOnly,
+    /// The user isn't among the meeting's attendees.
+    NotInvited,
+    /// Saving a change to the calendar failed. Holds the service's error message.
+    WriteFailed(String),
 }
 
-/// Check GNOME Online Accounts for any calendar-enabled accounts that need
-/// attention (expired credentials, etc.).
-pub async fn check_accounts_needing_attention() -> Vec<AccountNeedingAttention> {
-    use std::collections::HashMap;
-    use zvariant::{OwnedObjectPath, OwnedValue, Value};
+/// Meetings fetched from one source, along with anything that went wrong.
+#[derive(Debug, Default)]
+pub struct SourceFetch {
+    /// Meetings in the query window, each tagged with whether it came from a
+    /// RECURRENCE-ID override, which takes priority when deduplicating.
+    pub meetings: Vec<(bool, Meeting)>,
+    /// Number of calendars whose events were read.
+    pub calendars_read: usize,
+    /// Problems with individual calendars, keyed by calendar UID.
+    pub errors: HashMap<String, CalendarError>,
+    /// Set when the source couldn't be read at all, e.g. EDS isn't running.
+    pub source_error: Option<CalendarError>,
+}
 
-    let Ok(conn) = Connection::session().await else {
-        return Vec::new();
-    };
+impl SourceFetch {
+    /// A fetch where the whole source failed.
+    #[must_use]
+    pub fn failed(error: CalendarError) -> Self {
+        Self {
+            source_error: Some(error),
+            ..Self::default()
+        }
+    }
 
-    let Ok(proxy) = zbus::Proxy::new(
-        &conn,
-        "org.gnome.OnlineAccounts",
-        "/org/gnome/OnlineAccounts",
-        "org.freedesktop.DBus.ObjectManager",
-    )
-    .await
-    else {
-        return Vec::new();
-    };
+    /// Whether nothing could be read from this source.
+    #[must_use]
+    pub fn is_failure(&self) -> bool {
+        self.source_error.is_some() || (self.calendars_read == 0 && !self.errors.is_empty())
+    }
 
-    let Ok(reply) = proxy.call_method("GetManagedObjects", &()).await else {
-        return Vec::new();
-    };
-    let Ok(objects) =
-        reply.body::<HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>>()
-    else {
-        return Vec::new();
-    };
+    /// Parse one calendar's objects into this fetch, noting any events that
+    /// couldn't be parsed unless the calendar already has a problem recorded.
+    fn add_calendar(
+        &mut self,
+        ics_objects: &[String],
+        calendar_uid: &str,
+        window: &FetchWindow,
+        user_emails: &[String],
+    ) {
+        let invalid = parse_ics_objects(
+            ics_objects,
+            calendar_uid,
+            window,
+            user_emails,
+            &mut self.meetings,
+        )
+        .invalid;
+        self.calendars_read += 1;
+        if invalid > 0 {
+            self.errors
+                .entry(calendar_uid.to_string())
+                .or_insert(CalendarError::InvalidEvents(invalid));
+        }
+    }
+}
 
-    let mut results = Vec::new();
+/// The result of fetching upcoming meetings from every source.
+#[derive(Debug, Clone, Default)]
+pub struct MeetingsFetch {
+    /// Deduplicated meetings, sorted by start time.
+    pub meetings: Vec<Meeting>,
+    /// Problems with individual calendars, keyed by calendar UID.
+    pub errors: HashMap<String, CalendarError>,
+    /// Errors of sources that couldn't be read at all.
+    pub source_errors: Vec<CalendarError>,
+    /// Whether every source failed, in which case an empty list of meetings
+    /// says nothing about the user's calendar.
+    pub all_failed: bool,
+}
 
-    for (_path, interfaces) in objects {
-        let Some(account) = interfaces.get("org.gnome.OnlineAccounts.Account") else {
-            continue;
-        };
+/// Why an event instance isn't shown in the popup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FilterReason {
+    /// The object is a task or journal entry rather than an event.
+    NotAnEvent,
+    /// The event ends before it starts.
+    InvalidTimes,
+    /// The event is over, or started before the query window.
+    Past,
+    /// The event starts after the query window.
+    AfterWindow,
+    /// Another copy of the same instance was kept instead.
+    Duplicate,
+    /// All-day events are hidden in settings.
+    AllDay,
+    /// Meetings in progress are hidden in settings.
+    InProgress,
+    /// The event starts later than the "time until" setting allows.
+    TimeUntil,
+    /// The user's attendance status is filtered out in settings.
+    AttendanceStatus,
+    /// The organizer cancelled the event.
+    Cancelled,
+    /// Events marked as free are hidden in settings.
+    Free,
+}
 
-        // Only care about accounts that have calendar support
-        if !interfaces.contains_key("org.gnome.OnlineAccounts.Calendar") {
-            continue;
-        }
+/// What one calendar returned when queried for the diagnostics page.
+#[derive(Debug, Clone)]
+pub struct CalendarDiagnostics {
+    pub uid: String,
+    pub display_name: String,
+    /// Backend type (e.g., "local", "caldav", "google")
+    pub backend: Option<String>,
+    /// The calendar's `Revision` as reported by the source.
+    pub revision: Option<String>,
+    /// Whether the calendar's events could be read.
+    pub result: Result<(), CalendarError>,
+    /// How long reading the calendar's events took.
+    pub duration: Option<std::time::Duration>,
+    /// Number of objects the calendar returned.
+    pub raw_objects: usize,
+    /// Number of objects that couldn't be parsed.
+    pub invalid_objects: usize,
+    /// Number of event instances after recurrence expansion.
+    pub instances: usize,
+    /// Instances left out while fetching, by reason.
+    pub filtered: BTreeMap<FilterReason, usize>,
+    /// Meetings in the query window, before the display settings apply.
+    pub meetings: Vec<Meeting>,
+}
 
-        // Check if CalendarDisabled
-        let calendar_disabled = account.get("CalendarDisabled").is_some_and(|v| {
-            v.downcast_ref::<Value>()
-                .is_some_and(|val| matches!(val, Value::Bool(true)))
-        });
-        if calendar_disabled {
-            continue;
+/// Parameters for a meeting fetch, shared by every calendar source.
+#[derive(Debug, Clone, Default)]
+pub struct MeetingQuery {
+    /// Calendar UIDs to query. Empty means all calendars.
+    pub enabled_uids: Vec<String>,
+    /// Extra email addresses to identify the user in ATTENDEE fields.
+    pub additional_emails: Vec<String>,
+    /// How far around now to fetch meetings.
+    pub window: QueryWindow,
+}
+
+/// How far back and ahead of now meetings are fetched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QueryWindow {
+    /// Meetings that started this long ago are still fetched while in progress.
+    pub look_behind: chrono::Duration,
+    /// Meetings starting up to this far ahead are fetched.
+    pub look_ahead: chrono::Duration,
+}
+
+impl Default for QueryWindow {
+    fn default() -> Self {
+        Self {
+            look_behind: chrono::Duration::minutes(30),
+            look_ahead: chrono::Duration::days(30),
         }
+    }
+}
 
-        // Check AttentionNeeded
-        let attention_needed = account.get("AttentionNeeded").is_some_and(|v| {
-            v.downcast_ref::<Value>()
-                .is_some_and(|val| matches!(val, Value::Bool(true)))
-        });
-        if !attention_needed {
-            continue;
+impl QueryWindow {
+    /// The window configured in the applet's settings.
+    #[must_use]
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            look_behind: chrono::Duration::minutes(i64::from(config.look_behind_minutes)),
+            look_ahead: chrono::Duration::days(i64::from(config.look_ahead_days)),
         }
+    }
 
-        let identity = account
-            .get("PresentationIdentity")
-            .and_then(|v| {
-                if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
-                    Some(s.to_string())
-                } else {
-                    None
-                }
-            })
-            .unwrap_or_default();
+    /// The time range meetings are fetched for, relative to `now`.
+    fn around(self, now: DateTime<Local>) -> (DateTime<Local>, DateTime<Local>) {
+        (now - self.look_behind, now + self.look_ahead)
+    }
+}
 
-        results.push(AccountNeedingAttention { identity });
+/// A backend that can supply calendars and meetings to the applet.
+///
+/// Implementations return boxed futures so that sources can be stored as
+/// trait objects in a [`SourceSet`]. Every operation fails soft: a source that
+/// is unavailable returns empty results, and fetches report what went wrong
+/// alongside whatever could still be read.
+pub trait CalendarSource: Send + Sync {
+    /// List the calendars this source provides.
+    fn list_calendars(&self) -> BoxFuture<'_, Vec<CalendarInfo>>;
+
+    /// Fetch meetings in the query window, before deduplication.
+    fn fetch_meetings<'a>(&'a self, query: &'a MeetingQuery) -> BoxFuture<'a, SourceFetch>;
+
+    /// Ask the source to sync the given calendars with their upstream servers.
+    /// If `enabled_uids` is empty, all calendars are refreshed.
+    fn refresh<'a>(&'a self, enabled_uids: &'a [String]) -> BoxFuture<'a, ()>;
+
+    /// Ask the source to look for calendars that were added upstream.
+    fn discover(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async {})
     }
 
-    results
-}
+    /// Whether the source can reply to invitations in the given calendar.
+    fn can_respond(&self, _calendar_uid: &str) -> bool {
+        false
+    }
 
-/// Fetch available calendars from Evolution Data Server via D-Bus
-pub async fn get_available_calendars() -> Vec<CalendarInfo> {
-    // Debug: simulate no calendars for testing
-    if std::env::var("DEBUG_NO_CALENDARS").is_ok() {
-        return Vec::new();
+    /// Reply to a meeting invitation, setting the user's `PARTSTAT` on the event.
+    fn respond<'a>(
+        &'a self,
+        _meeting: &'a Meeting,
+        _response: AttendanceStatus,
+        _additional_emails: &'a [String],
+    ) -> BoxFuture<'a, Result<(), CalendarError>> {
+        Box::pin(async { Err(CalendarError::ReadOnly) })
     }
 
-    let Ok(conn) = Connection::session().await else {
-        return Vec::new();
-    };
+    /// Read every calendar afresh and report what each returned, for the
+    /// diagnostics page.
+    fn diagnose<'a>(&'a self, _query: &'a MeetingQuery) -> BoxFuture<'a, Vec<CalendarDiagnostics>> {
+        Box::pin(async { Vec::new() })
+    }
 
-    get_calendars_from_dbus(&conn).await.unwrap_or_default()
+    /// Watch for changes to calendar contents, sending to the channel on each change.
+    /// Runs until cancelled; returns immediately if the source can't be watched.
+    fn watch_changes(
+        &self,
+        enabled_uids: Vec<String>,
+        window: QueryWindow,
+        sender: tokio::sync::mpsc::Sender<()>,
+    ) -> BoxFuture<'static, ()>;
+
+    /// Watch for calendars being added or removed, sending to the channel on each change.
+    fn watch_calendar_list(
+        &self,
+        _sender: tokio::sync::mpsc::Sender<()>,
+    ) -> BoxFuture<'static, ()> {
+        Box::pin(async {})
+    }
 }
 
-/// Ask EDS to re-discover calendars from all collection/account backends.
+/// The calendar sources the applet reads from.
 ///
-/// Calls `RefreshBackend` on the `SourceManager` for every source that has a
-/// `[Collection]` section (e.g. `CalDAV` or GOA accounts). This triggers
-/// server-side discovery of new calendars that were added to the account.
-pub async fn refresh_source_backends() {
-    use std::collections::HashMap;
-    use zvariant::{OwnedObjectPath, OwnedValue, Value};
-
-    let Ok(conn) = Connection::session().await else {
-        return;
-    };
+/// Cheap to clone, so it can be moved into tasks and subscriptions.
+#[derive(Clone)]
+pub struct SourceSet {
+    sources: Vec<Arc<dyn CalendarSource>>,
+}
 
-    let Ok(proxy) = zbus::Proxy::new(
-        &conn,
-        "org.gnome.evolution.dataserver.Sources5",
-        "/org/gnome/evolution/dataserver/SourceManager",
-        "org.gnome.evolution.dataserver.SourceManager",
-    )
-    .await
-    else {
-        return;
-    };
+impl Default for SourceSet {
+    fn default() -> Self {
+        Self::from_config(&Config::default())
+    }
+}
 
-    // Also get managed objects to find collection UIDs
-    let Ok(om_proxy) = zbus::Proxy::new(
-        &conn,
-        "org.gnome.evolution.dataserver.Sources5",
-        "/org/gnome/evolution/dataserver/SourceManager",
-        "org.freedesktop.DBus.ObjectManager",
-    )
-    .await
-    else {
-        return;
-    };
+impl SourceSet {
+    #[must_use]
+    pub fn new(sources: Vec<Arc<dyn CalendarSource>>) -> Self {
+        Self { sources }
+    }
 
-    let Ok(reply) = om_proxy.call_method("GetManagedObjects", &()).await else {
-        return;
-    };
-    let Ok(objects) =
-        reply.body::<HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>>()
-    else {
-        return;
-    };
+    /// Build the sources described by the config. EDS is always included.
+    #[must_use]
+    pub fn from_config(config: &Config) -> Self {
+        let mut sources: Vec<Arc<dyn CalendarSource>> = vec![Arc::new(EdsSource::default())];
+        if !config.ics_calendar_paths.is_empty() {
+            sources.push(Arc::new(IcsFileSource::new(&config.ics_calendar_paths)));
+        }
+        if !config.vdir_paths.is_empty() {
+            sources.push(Arc::new(VdirSource::new(&config.vdir_paths)));
+        }
+        if !config.webcal_urls.is_empty() {
+            sources.push(Arc::new(WebcalSource::new(
+                &config.webcal_urls,
+                config.auto_refresh_interval_minutes,
+            )));
+        }
+        for account in &config.caldav_accounts {
+            if !account.url.trim().is_empty() {
+                sources.push(Arc::new(CalDavSource::new(account.clone())));
+            }
+        }
+        Self::new(sources)
+    }
 
-    for (_path, interfaces) in objects {
-        let Some(source_props) = interfaces.get("org.gnome.evolution.dataserver.Source") else {
-            continue;
-        };
+    /// Fetch available calendars from all sources, sorted by display name.
+    pub async fn available_calendars(&self) -> Vec<CalendarInfo> {
+        // Debug: simulate no calendars for testing
+        if std::env::var("DEBUG_NO_CALENDARS").is_ok() {
+            return Vec::new();
+        }
 
-        // Check for [Collection] section in the Data property
-        let is_collection = source_props.get("Data").is_some_and(|v| {
-            if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
-                s.contains("[Collection]")
-            } else {
-                false
-            }
-        });
+        let lists = join_all(self.sources.iter().map(|s| s.list_calendars())).await;
+        let mut calendars: Vec<CalendarInfo> = lists.into_iter().flatten().collect();
+        calendars.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+        calendars
+    }
 
-        if !is_collection {
-            continue;
+    /// Fetch upcoming meetings from all sources.
+    /// Returns up to `limit` meetings (at least one), deduplicated and sorted by
+    /// start time, along with the calendars and sources that couldn't be read.
+    pub async fn upcoming_meetings(&self, query: &MeetingQuery, limit: usize) -> MeetingsFetch {
+        // Debug: simulate no calendars for testing
+        if std::env::var("DEBUG_NO_CALENDARS").is_ok() {
+            return MeetingsFetch::default();
         }
 
-        let Some(uid) = source_props.get("UID").and_then(|v| {
-            if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
-                Some(s.to_string())
-            } else {
-                None
-            }
-        }) else {
-            continue;
-        };
+        let results = join_all(self.sources.iter().map(|s| s.fetch_meetings(query))).await;
+        let all_failed = !results.is_empty() && results.iter().all(SourceFetch::is_failure);
 
-        let _ = proxy.call_method("RefreshBackend", &(uid.as_str(),)).await;
+        let mut fetch = MeetingsFetch {
+            all_failed,
+            ..MeetingsFetch::default()
+        };
+        let mut all_meetings = Vec::new();
+        for result in results {
+            all_meetings.extend(result.meetings);
+            fetch.errors.extend(result.errors);
+            fetch.source_errors.extend(result.source_error);
+        }
+        // Cancelled instances still replace the instance they cancel, then drop out
+        let mut meetings = dedup_and_sort_meetings(all_meetings, usize::MAX);
+        meetings.retain(|m| m.status != EventStatus::Cancelled);
+        meetings.truncate(limit.max(1));
+        fetch.meetings = meetings;
+        fetch
     }
-}
 
-/// Refresh all calendars by triggering an upstream sync with remote servers.
-/// This calls the Refresh D-Bus method on each calendar, which forces EDS to
-/// fetch the latest data from CalDAV/Google/etc servers.
-/// If `enabled_uids` is empty, all calendars are refreshed.
-pub async fn refresh_calendars(enabled_uids: &[String]) {
-    let Ok(conn) = Connection::session().await else {
-        return;
-    };
-
-    // Get calendar source UIDs
-    let Some(mut source_uids) = get_calendar_source_uids(&conn).await else {
-        return;
-    };
+    /// Trigger an upstream sync on every source.
+    pub async fn refresh(&self, enabled_uids: &[String]) {
+        join_all(self.sources.iter().map(|s| s.refresh(enabled_uids))).await;
+    }
 
-    // Filter to only enabled calendars if specified
-    if !enabled_uids.is_empty() {
-        source_uids.retain(|uid| enabled_uids.contains(uid));
+    /// Ask every source to re-discover its calendars.
+    pub async fn discover(&self) {
+        join_all(self.sources.iter().map(|s| s.discover())).await;
     }
 
-    // Open calendar factory
-    let Ok(calendar_factory_proxy) = zbus::Proxy::new(
-        &conn,
-        "org.gnome.evolution.dataserver.Calendar8",
-        "/org/gnome/evolution/dataserver/CalendarFactory",
-        "org.gnome.evolution.dataserver.CalendarFactory",
-    )
-    .await
-    else {
-        return;
-    };
+    /// Whether invitations in the given calendar can be replied to.
+    #[must_use]
+    pub fn can_respond(&self, calendar_uid: &str) -> bool {
+        self.sources.iter().any(|s| s.can_respond(calendar_uid))
+    }
 
-    // Refresh each calendar
-    for source_uid in &source_uids {
-        // Open the calendar
-        let (calendar_path, bus_name): (String, String) = match calendar_factory_proxy
-            .call_method("OpenCalendar", &(source_uid.as_str(),))
-            .await
+    /// Reply to a meeting invitation through the source its calendar belongs to.
+    pub async fn respond(
+        &self,
+        meeting: &Meeting,
+        response: AttendanceStatus,
+        additional_emails: &[String],
+    ) -> Result<(), CalendarError> {
+        match self
+            .sources
+            .iter()
+            .find(|s| s.can_respond(&meeting.calendar_uid))
         {
-            Ok(reply) => match reply.body::<(String, String)>() {
-                Ok((path, bus)) => (path, bus),
-                Err(_) => continue,
-            },
-            Err(_) => continue,
-        };
+            Some(source) => source.respond(meeting, response, additional_emails).await,
+            None => Err(CalendarError::ReadOnly),
+        }
+    }
 
-        // Get a proxy to the calendar
-        let Ok(calendar_proxy) = zbus::Proxy::new(
-            &conn,
-            bus_name.as_str(),
-            calendar_path.as_str(),
-            "org.gnome.evolution.dataserver.Calendar",
-        )
-        .await
-        else {
-            continue;
-        };
+    /// Collect diagnostics from every source.
+    pub async fn diagnose(&self, query: &MeetingQuery) -> Vec<CalendarDiagnostics> {
+        join_all(self.sources.iter().map(|s| s.diagnose(query)))
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
 
-        // Initialize the backend (required before any calendar operations)
-        let _ = calendar_proxy.call_method("Open", &()).await;
+    /// Watch all sources for calendar content changes.
+    pub async fn watch_changes(
+        &self,
+        enabled_uids: Vec<String>,
+        window: QueryWindow,
+        sender: tokio::sync::mpsc::Sender<()>,
+    ) {
+        // Polled together rather than spawned, so cancelling this future stops every watcher
+        let watchers: Vec<_> = self
+            .sources
+            .iter()
+            .map(|s| s.watch_changes(enabled_uids.clone(), window, sender.clone()))
+            .collect();
+        drop(sender);
+        join_all(watchers).await;
+    }
 
-        // Call Refresh method (fire and forget - don't wait for completion)
-        let _ = calendar_proxy.call_method("Refresh", &()).await;
+    /// Watch all sources for calendars being added or removed.
+    pub async fn watch_calendar_list(&self, sender: tokio::sync::mpsc::Sender<()>) {
+        let watchers: Vec<_> = self
+            .sources
+            .iter()
+            .map(|s| s.watch_calendar_list(sender.clone()))
+            .collect();
+        drop(sender);
+        join_all(watchers).await;
     }
 }
 
-/// Watch for calendar changes via D-Bus `PropertiesChanged` signals.
-/// Returns a stream that yields () whenever any calendar's properties change.
-/// This allows detecting when EDS has updated calendar data after a sync.
-pub async fn watch_calendar_changes(
-    enabled_uids: Vec<String>,
-    sender: tokio::sync::mpsc::Sender<()>,
-) {
-    let Ok(conn) = Connection::session().await else {
-        return;
-    };
-
-    // Get calendar source UIDs
-    let Some(mut source_uids) = get_calendar_source_uids(&conn).await else {
-        return;
-    };
+/// Phone numbers in international format, optionally followed by a one-tap
+/// PIN (`+16465588656,,85512345678#`)
+static PHONE_NUMBER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\+\d[\d ().\-]{6,}\d(?:,+(\d+)#?)?").expect("phone number pattern is valid")
+});
+
+/// Conference IDs and PINs announced in invitation text
+static CONFERENCE_ID: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:meeting id|conference id|access code|meeting number|pin)\b[^\d\n]{0,12}(\d[\d ]{2,}\d)")
+        .expect("conference ID pattern is valid")
+});
+
+/// Redirect wrappers that carry the real link in a query parameter: Outlook
+/// SafeLinks (`url=`) and Google's click tracker (`q=`)
+static REDIRECT_WRAPPER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?i)https?://(?:[a-z0-9-]+\.)*safelinks\.protection\.outlook\.com/[^\s<>"]*|https?://(?:www\.)?google\.com/url\?[^\s<>"]*"#,
+    )
+    .expect("redirect wrapper pattern is valid")
+});
+
+/// Colors handed out to calendars that don't define their own.
+const FALLBACK_COLORS: [&str; 8] = [
+    "#62a0ea", "#57e389", "#f8e45c", "#ffa348", "#ed333b", "#c061cb", "#b5835a", "#9a9996",
+];
+
+/// Pick a stable color for a calendar that doesn't define one, keyed by its UID.
+fn fallback_color(key: &str) -> String {
+    let sum: usize = key.bytes().map(usize::from).sum();
+    FALLBACK_COLORS[sum % FALLBACK_COLORS.len()].to_string()
+}
 
-    // Filter to enabled calendars if specified
-    if !enabled_uids.is_empty() {
-        source_uids.retain(|uid| enabled_uids.contains(uid));
+/// Normalize a `#RRGGBB` or `#RRGGBBAA` color to lowercase `#rrggbb`.
+fn normalize_hex_color(color: &str) -> Option<String> {
+    let hex = color.trim().trim_start_matches('#');
+    if (hex.len() == 6 || hex.len() == 8) && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(format!("#{}", hex[..6].to_ascii_lowercase()))
+    } else {
+        None
     }
+}
 
-    // Open calendar factory
-    let Ok(calendar_factory_proxy) = zbus::Proxy::new(
-        &conn,
-        "org.gnome.evolution.dataserver.Calendar8",
-        "/org/gnome/evolution/dataserver/CalendarFactory",
-        "org.gnome.evolution.dataserver.CalendarFactory",
-    )
-    .await
-    else {
-        return;
-    };
+/// Calendar-level properties from the top of an `.ics` file.
+#[derive(Debug, Default, PartialEq)]
+struct CalendarHeader {
+    name: Option<String>,
+    color: Option<String>,
+}
 
-    // First collect all calendar (path, bus) pairs
-    let mut calendar_info: Vec<(String, String)> = Vec::new();
-    for source_uid in &source_uids {
-        let (calendar_path, bus_name): (String, String) = match calendar_factory_proxy
-            .call_method("OpenCalendar", &(source_uid.as_str(),))
-            .await
+/// Parse the calendar name (`X-WR-CALNAME`) and color (`X-APPLE-CALENDAR-COLOR`)
+/// from an `.ics` file. Only lines before the first component are considered.
+fn parse_calendar_header(contents: &str) -> CalendarHeader {
+    let mut header = CalendarHeader::default();
+    for line in contents.lines() {
+        let line = line.trim_end();
+        if line.starts_with("BEGIN:") && line != "BEGIN:VCALENDAR" {
+            break;
+        }
+        if let Some(name) = line.strip_prefix("X-WR-CALNAME:")
+            && !name.trim().is_empty()
         {
-            Ok(reply) => match reply.body::<(String, String)>() {
-                Ok((path, bus)) => (path, bus),
-                Err(_) => continue,
-            },
-            Err(_) => continue,
-        };
-        calendar_info.push((calendar_path, bus_name));
-    }
-
-    if calendar_info.is_empty() {
-        return;
+            header.name = Some(name.trim().to_string());
+        } else if let Some(color) = line.strip_prefix("X-APPLE-CALENDAR-COLOR:") {
+            header.color = normalize_hex_color(color);
+        }
     }
+    header
+}
 
-    // Spawn a watcher task for each calendar
-    // Each task watches for PropertiesChanged and sends to the shared channel
-    let mut handles = Vec::new();
-    for (calendar_path, bus_name) in calendar_info {
-        let sender_clone = sender.clone();
-        let conn_clone = conn.clone();
-        handles.push(tokio::spawn(async move {
-            watch_single_calendar(conn_clone, bus_name, calendar_path, sender_clone).await;
-        }));
+/// Expand a leading `~/` to the user's home directory.
+fn expand_home(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/")
+        && let Some(home) = std::env::var_os("HOME")
+    {
+        return PathBuf::from(home).join(rest);
     }
+    PathBuf::from(path)
+}
 
-    // Wait for all watcher tasks (they run indefinitely until cancelled)
-    for handle in handles {
-        let _ = handle.await;
-    }
+/// Whether a path looks like an iCalendar file.
+fn is_ics_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ics"))
 }
 
-/// Watch a single calendar for `PropertiesChanged` signals
-async fn watch_single_calendar(
-    conn: Connection,
-    bus_name: String,
-    calendar_path: String,
-    sender: tokio::sync::mpsc::Sender<()>,
-) {
-    // Create a proxy for the Properties interface on this calendar
-    let Ok(props_proxy) = zbus::Proxy::new(
-        &conn,
-        bus_name.as_str(),
-        calendar_path.as_str(),
-        "org.freedesktop.DBus.Properties",
-    )
-    .await
-    else {
-        return;
+/// List the `.ics` files directly inside a folder, sorted by name.
+async fn list_ics_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return Vec::new();
     };
 
-    // Subscribe to PropertiesChanged signals
-    let Ok(mut stream) = props_proxy.receive_signal("PropertiesChanged").await else {
-        return;
-    };
+    let mut files = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if is_ics_file(&path) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+}
 
-    // Listen for signals and notify the channel
-    while stream.next().await.is_some() {
-        let _ = sender.try_send(());
+/// The span and zone a fetch parses events in.
+#[derive(Debug, Clone, Copy)]
+struct FetchWindow {
+    now: DateTime<Local>,
+    /// Meetings must start after this, or still be in progress.
+    start: DateTime<Local>,
+    /// Recurring events are expanded up to here.
+    end: DateTime<Local>,
+    /// The local time zone, resolved once per fetch.
+    local_tz: Tz,
+}
+
+impl FetchWindow {
+    /// The query window around `now`, in the current local time zone.
+    fn new(now: DateTime<Local>, window: QueryWindow) -> Self {
+        let (start, end) = window.around(now);
+        let local_tz = localzone::get_local_zone()
+            .and_then(|name| Tz::from_str_insensitive(&name).ok())
+            .unwrap_or(Tz::UTC);
+        Self {
+            now,
+            start,
+            end,
+            local_tz,
+        }
     }
 }
 
-/// Watch for new or removed calendar sources via the `ObjectManager`'s
-/// `InterfacesAdded` / `InterfacesRemoved` signals on the EDS `SourceManager`.
-///
-/// Sends to the channel whenever the set of sources changes, so the app can
-/// immediately refresh its calendar list and meetings.
-pub async fn watch_source_changes(sender: tokio::sync::mpsc::Sender<()>) {
+/// An online account that needs the user's attention (e.g. re-authentication).
+#[derive(Debug, Clone)]
+pub struct AccountNeedingAttention {
+    /// Display-friendly identity (e.g. "dangrover@fastmail.com")
+    pub identity: String,
+}
+
+/// Check GNOME Online Accounts for any calendar-enabled accounts that need
+/// attention (expired credentials, etc.).
+pub async fn check_accounts_needing_attention() -> Vec<AccountNeedingAttention> {
+    use std::collections::HashMap;
+    use zvariant::{OwnedObjectPath, OwnedValue, Value};
+
     let Ok(conn) = Connection::session().await else {
-        return;
+        return Vec::new();
     };
 
     let Ok(proxy) = zbus::Proxy::new(
         &conn,
-        "org.gnome.evolution.dataserver.Sources5",
-        "/org/gnome/evolution/dataserver/SourceManager",
+        "org.gnome.OnlineAccounts",
+        "/org/gnome/OnlineAccounts",
         "org.freedesktop.DBus.ObjectManager",
     )
     .await
     else {
-        return;
+        return Vec::new();
+    };
+
+    let Ok(reply) = proxy.call_method("GetManagedObjects", &()).await else {
+        return Vec::new();
+    };
+    let Ok(objects) =
+        reply.body::<HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>>()
+    else {
+        return Vec::new();
     };
 
-    // Listen for InterfacesAdded (new source) and InterfacesRemoved (deleted source)
-    let added = proxy.receive_signal("InterfacesAdded").await;
-    let removed = proxy.receive_signal("InterfacesRemoved").await;
+    let mut results = Vec::new();
 
-    // Merge whichever streams we were able to subscribe to
-    match (added, removed) {
-        (Ok(a), Ok(r)) => {
-            let mut merged = futures_util::stream::select(a, r);
-            while merged.next().await.is_some() {
-                let _ = sender.try_send(());
-            }
+    for (_path, interfaces) in objects {
+        let Some(account) = interfaces.get("org.gnome.OnlineAccounts.Account") else {
+            continue;
+        };
+
+        // Only care about accounts that have calendar support
+        if !interfaces.contains_key("org.gnome.OnlineAccounts.Calendar") {
+            continue;
         }
-        (Ok(mut a), Err(_)) => {
-            while a.next().await.is_some() {
-                let _ = sender.try_send(());
-            }
+
+        // Check if CalendarDisabled
+        let calendar_disabled = account.get("CalendarDisabled").is_some_and(|v| {
+            v.downcast_ref::<Value>()
+                .is_some_and(|val| matches!(val, Value::Bool(true)))
+        });
+        if calendar_disabled {
+            continue;
         }
-        (Err(_), Ok(mut r)) => {
-            while r.next().await.is_some() {
-                let _ = sender.try_send(());
-            }
+
+        // Check AttentionNeeded
+        let attention_needed = account.get("AttentionNeeded").is_some_and(|v| {
+            v.downcast_ref::<Value>()
+                .is_some_and(|val| matches!(val, Value::Bool(true)))
+        });
+        if !attention_needed {
+            continue;
         }
-        (Err(_), Err(_)) => {}
+
+        let identity = account
+            .get("PresentationIdentity")
+            .and_then(|v| {
+                if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
+                    Some(s.to_string())
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        results.push(AccountNeedingAttention { identity });
     }
+
+    results
 }
 
 /// Watch for system resume (from sleep) and session unlock events via D-Bus.
@@ -564,167 +959,261 @@ async fn watch_session_unlock(conn: Connection, sender: tokio::sync::mpsc::Sende
     }
 }
 
-/// Fetch upcoming meetings from Evolution Data Server via D-Bus
-/// If `enabled_uids` is empty, all calendars are queried.
-/// Otherwise, only calendars with UIDs in the list are queried.
-/// Returns up to `limit` meetings (use limit=0 for just the next meeting info).
-/// `additional_emails` are extra email addresses to identify the user in ATTENDEE fields.
-pub async fn get_upcoming_meetings(
-    enabled_uids: &[String],
-    limit: usize,
-    additional_emails: &[String],
-) -> Vec<Meeting> {
-    // Debug: simulate no calendars for testing
-    if std::env::var("DEBUG_NO_CALENDARS").is_ok() {
-        return Vec::new();
-    }
-
-    let Ok(conn) = Connection::session().await else {
-        return Vec::new();
-    };
-
-    get_meetings_from_dbus(&conn, enabled_uids, limit.max(1), additional_emails).await
+/// Counts from parsing a calendar's objects.
+#[derive(Debug, Default)]
+struct ParseStats {
+    /// Objects that couldn't be parsed.
+    invalid: usize,
+    /// Instances after recurrence expansion, including filtered ones.
+    instances: usize,
+    /// Instances left out, by reason.
+    filtered: BTreeMap<FilterReason, usize>,
 }
 
-#[allow(clippy::too_many_lines)]
-async fn get_meetings_from_dbus(
-    conn: &Connection,
-    enabled_uids: &[String],
-    limit: usize,
-    additional_emails: &[String],
-) -> Vec<Meeting> {
-    // Evolution Data Server workflow:
-    // 1. Get calendar source UIDs from D-Bus SourceManager
-    // 2. For each source, use CalendarFactory.OpenCalendar to get a calendar object
-    // 3. Query the calendar object for events using GetObjectList
-    // 4. Parse the iCalendar objects
-
-    // Step 1: Get calendar source UIDs from D-Bus SourceManager
-    let Some(mut source_uids) = get_calendar_source_uids(conn).await else {
-        return Vec::new();
-    };
-
-    // Filter to only enabled calendars if a filter is specified
-    if !enabled_uids.is_empty() {
-        source_uids.retain(|uid| enabled_uids.contains(uid));
-    }
-
-    // Step 2: Open calendars and get events
-    let Ok(calendar_factory_proxy) = zbus::Proxy::new(
-        conn,
-        "org.gnome.evolution.dataserver.Calendar8",
-        "/org/gnome/evolution/dataserver/CalendarFactory",
-        "org.gnome.evolution.dataserver.CalendarFactory",
-    )
-    .await
-    else {
-        return Vec::new();
-    };
-
-    let mut all_meetings: Vec<(bool, Meeting)> = Vec::new();
-
-    for source_uid in source_uids {
-        // Open the calendar for this source
-        let (calendar_path, bus_name): (String, String) = match calendar_factory_proxy
-            .call_method("OpenCalendar", &(source_uid.as_str(),))
-            .await
+/// Limit the recurring events in a calendar to the fetch window, so that
+/// expansion doesn't walk every instance of a series that started years ago.
+///
+/// Open-ended series have their DTSTART moved forward by whole recurrence
+/// periods to shortly before the window, which leaves every later instance
+/// where it was. Every series also gets an UNTIL shortly after the window.
+fn bound_recurrences(calendar: &mut ICalendar, window: &FetchWindow) {
+    // DTSTART and UNTIL are compared as wall-clock times, so leave room for
+    // the difference between the event's time zone and the local one
+    let margin = chrono::Duration::days(2);
+    let earliest = (window.start - margin).naive_local();
+    let latest = (window.end + margin).naive_local();
+
+    for comp in &mut calendar.components {
+        if !matches!(comp.component_type, ICalendarComponentType::VEvent)
+            || comp.property(&ICalendarProperty::RecurrenceId).is_some()
         {
-            Ok(reply) => match reply.body::<(String, String)>() {
-                Ok((path, bus)) => (path, bus),
-                Err(_) => continue,
-            },
-            Err(_) => continue,
-        };
-
-        // Step 3: Query the calendar for events using GetObjectList
-        let Ok(calendar_proxy) = zbus::Proxy::new(
-            conn,
-            bus_name.as_str(),
-            calendar_path.as_str(),
-            "org.gnome.evolution.dataserver.Calendar",
-        )
-        .await
+            continue;
+        }
+        let Some(ICalendarValue::RecurrenceRule(rule)) = comp
+            .property(&ICalendarProperty::Rrule)
+            .and_then(|entry| entry.values.first())
         else {
             continue;
         };
+        let date_time = |prop: &ICalendarProperty| {
+            comp.property(prop)
+                .and_then(|entry| entry.values.first())
+                .and_then(|value| match value {
+                    ICalendarValue::PartialDateTime(dt) => dt.to_date_time(),
+                    _ => None,
+                })
+                .map(|dt| dt.date_time)
+        };
+        let Some(dtstart) = date_time(&ICalendarProperty::Dtstart) else {
+            continue;
+        };
+        let dtend = date_time(&ICalendarProperty::Dtend);
+
+        // Skipping instances would change what COUNT counts
+        let shift = if rule.count.is_none() {
+            series_shift(rule, dtstart, earliest).filter(|shift| match (shift, dtend) {
+                // Months differ in length, so an event spanning two of them
+                // would change duration
+                (SeriesShift::Months(_), Some(dtend)) => {
+                    (dtend.year(), dtend.month()) == (dtstart.year(), dtstart.month())
+                }
+                _ => true,
+            })
+        } else {
+            None
+        };
+        let until = rule
+            .until
+            .as_ref()
+            .and_then(PartialDateTime::to_date_time)
+            .map(|until| until.date_time);
+        let bound_until = dtstart <= latest && until.is_none_or(|until| until > latest);
+
+        for entry in &mut comp.entries {
+            match (&entry.name, entry.values.first_mut()) {
+                (
+                    ICalendarProperty::Dtstart | ICalendarProperty::Dtend,
+                    Some(ICalendarValue::PartialDateTime(dt)),
+                ) => {
+                    if let Some(shift) = shift {
+                        shift_partial_date(dt, shift);
+                    }
+                }
+                (ICalendarProperty::Rrule, Some(ICalendarValue::RecurrenceRule(rule)))
+                    if bound_until =>
+                {
+                    rule.until = Some(PartialDateTime {
+                        year: u16::try_from(latest.year()).ok(),
+                        month: u8::try_from(latest.month()).ok(),
+                        day: u8::try_from(latest.day()).ok(),
+                        hour: Some(0),
+                        minute: Some(0),
+                        second: Some(0),
+                        ..PartialDateTime::default()
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
 
-        // Initialize the backend (required before any calendar operations)
-        let _ = calendar_proxy.call_method("Open", &()).await;
-
-        // Get the CalEmailAddress property for this calendar
-        // This is used to identify the user in ATTENDEE fields
-        let cal_email: Option<String> = calendar_proxy
-            .get_property::<String>("CalEmailAddress")
-            .await
-            .ok();
+/// How far to move a series' start, in whole recurrence periods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeriesShift {
+    Days(u64),
+    Months(u32),
+}
 
-        // Combine CalEmailAddress with additional_emails for user identification
-        // Filter out empty strings from additional_emails
-        let mut user_emails: Vec<String> = additional_emails
-            .iter()
-            .filter(|e| !e.trim().is_empty())
-            .cloned()
-            .collect();
-        if let Some(email) = cal_email
-            && !email.is_empty()
-            && !user_emails.iter().any(|e| e.eq_ignore_ascii_case(&email))
-        {
-            user_emails.push(email);
+/// The largest shift by whole periods of `rule` that keeps `dtstart` at or
+/// before `earliest`, or `None` if it can't move.
+fn series_shift(
+    rule: &calcard::icalendar::ICalendarRecurrenceRule,
+    dtstart: NaiveDateTime,
+    earliest: NaiveDateTime,
+) -> Option<SeriesShift> {
+    if dtstart >= earliest {
+        return None;
+    }
+    let interval = i64::from(rule.interval.unwrap_or(1).max(1));
+    let shift = match rule.freq {
+        ICalendarFrequency::Daily | ICalendarFrequency::Weekly => {
+            let period = if rule.freq == ICalendarFrequency::Daily {
+                interval
+            } else {
+                interval * 7
+            };
+            let days = (earliest - dtstart).num_days() / period * period;
+            SeriesShift::Days(u64::try_from(days).ok().filter(|d| *d > 0)?)
         }
+        ICalendarFrequency::Monthly | ICalendarFrequency::Yearly => {
+            let period = if rule.freq == ICalendarFrequency::Monthly {
+                interval
+            } else {
+                interval * 12
+            };
+            // One month short, so the day of the month can't pass `earliest`
+            let months = i64::from(earliest.year() - dtstart.year()) * 12
+                + i64::from(earliest.month())
+                - i64::from(dtstart.month())
+                - 1;
+            let months = months / period * period;
+            let months = u32::try_from(months).ok().filter(|m| *m > 0)?;
+            // A start on the 31st can't move to a shorter month without
+            // changing which day the series falls on
+            let moved = dtstart.checked_add_months(chrono::Months::new(months))?;
+            if moved.day() != dtstart.day() {
+                return None;
+            }
+            SeriesShift::Months(months)
+        }
+        _ => return None,
+    };
+    Some(shift)
+}
 
-        // GetObjectList takes an S-expression query string
-        // Use occur-in-time-range? to expand recurring events into instances
-        // Query from 30 minutes ago (to include in-progress meetings) to 30 days in the future
-        let now = Local::now();
-        let query_start = now - chrono::Duration::minutes(30);
-        let query_end = now + chrono::Duration::days(30);
-        // Convert to UTC for the query (EDS expects UTC timestamps)
-        let query_start_utc = query_start.with_timezone(&chrono::Utc);
-        let query_end_utc = query_end.with_timezone(&chrono::Utc);
-        let query = format!(
-            "(occur-in-time-range? (make-time \"{}\") (make-time \"{}\"))",
-            query_start_utc.format("%Y%m%dT%H%M%SZ"),
-            query_end_utc.format("%Y%m%dT%H%M%SZ")
-        );
-
-        let ics_objects: Vec<String> = match calendar_proxy
-            .call_method("GetObjectList", &(query.as_str(),))
-            .await
-        {
-            Ok(reply) => match reply.body::<Vec<String>>() {
-                Ok(objects) => objects,
-                Err(_) => continue,
-            },
-            Err(_) => continue,
-        };
-
-        // Step 4: Parse iCalendar objects and extract meetings using calcard
-        parse_ics_objects(
-            &ics_objects,
-            &source_uid,
-            now,
-            query_start,
-            &user_emails,
-            &mut all_meetings,
-        );
+/// Move the date of a DTSTART or DTEND value, keeping its time and zone.
+fn shift_partial_date(value: &mut PartialDateTime, shift: SeriesShift) {
+    let Some(date) = value.to_date_time().map(|dt| dt.date_time.date()) else {
+        return;
+    };
+    let moved = match shift {
+        SeriesShift::Days(days) => date.checked_add_days(chrono::Days::new(days)),
+        SeriesShift::Months(months) => date.checked_add_months(chrono::Months::new(months)),
+    };
+    if let Some(moved) = moved {
+        value.year = u16::try_from(moved.year()).ok();
+        value.month = u8::try_from(moved.month()).ok();
+        value.day = u8::try_from(moved.day()).ok();
     }
+}
+
+/// A RECURRENCE-ID override's place in its series.
+#[derive(Debug, Clone, Copy)]
+struct Override {
+    /// The original start of the instance it replaces.
+    recurrence_id: DateTime<Local>,
+    /// How far the override moves the instance.
+    offset: chrono::TimeDelta,
+    /// Whether it also applies to every later instance (RANGE=THISANDFUTURE).
+    this_and_future: bool,
+}
+
+/// A RANGE=THISANDFUTURE override that applies to later instances of a
+/// series stored as a separate object from the master.
+struct FutureOverride {
+    recurrence_id: DateTime<Local>,
+    offset: chrono::TimeDelta,
+    duration: chrono::TimeDelta,
+    /// Index of the calendar and component that hold the new details.
+    calendar: usize,
+    component: usize,
+}
+
+/// Find the RECURRENCE-ID override details of each component in a calendar,
+/// resolving times the same way calcard's expansion does.
+fn find_overrides(calendar: &ICalendar, custom_zones: &CustomZones) -> Vec<Option<Override>> {
+    let resolver = calendar
+        .build_tz_resolver()
+        .with_default(custom_zones.local_tz);
+    let resolve = |comp: &calcard::icalendar::ICalendarComponent, prop: &ICalendarProperty| {
+        let entry = comp.property(prop)?;
+        let ICalendarValue::PartialDateTime(value) = entry.values.first()? else {
+            return None;
+        };
+        let tz = resolver.resolve_or_default(entry.tz_id());
+        let time = value.to_date_time_with_tz(tz)?.with_timezone(&Local);
+        Some(custom_zones.correct(comp, prop, time))
+    };
 
-    // Deduplicate and sort
-    dedup_and_sort_meetings(all_meetings, limit)
+    calendar
+        .components
+        .iter()
+        .map(|comp| {
+            let recurrence_id = resolve(comp, &ICalendarProperty::RecurrenceId)?;
+            let offset = resolve(comp, &ICalendarProperty::Dtstart)
+                .map_or_else(chrono::TimeDelta::zero, |start| start - recurrence_id);
+            let this_and_future =
+                comp.property(&ICalendarProperty::RecurrenceId)
+                    .is_some_and(|entry| {
+                        entry.params.iter().any(|p| match (&p.name, &p.value) {
+                            (ICalendarParameterName::Range, ICalendarParameterValue::Bool(b)) => *b,
+                            (ICalendarParameterName::Range, ICalendarParameterValue::Text(t)) => {
+                                t.eq_ignore_ascii_case("THISANDFUTURE")
+                            }
+                            _ => false,
+                        })
+                    });
+            Some(Override {
+                recurrence_id,
+                offset,
+                this_and_future,
+            })
+        })
+        .collect()
 }
 
 /// Parse ICS objects into meetings, collecting into the provided vector.
 ///
 /// Each ICS object is parsed with calcard, recurring events are expanded,
 /// and results are tagged with whether they're RECURRENCE-ID overrides.
+/// Instances are keyed by the start they had in the series, so an override
+/// that moves an instance still replaces the master's copy of it. Overrides
+/// with RANGE=THISANDFUTURE also move and update every later instance.
+#[allow(clippy::too_many_lines)]
 fn parse_ics_objects(
     ics_objects: &[String],
     source_uid: &str,
-    now: DateTime<Local>,
-    query_start: DateTime<Local>,
+    window: &FetchWindow,
     user_emails: &[String],
     all_meetings: &mut Vec<(bool, Meeting)>,
-) {
+) -> ParseStats {
+    let mut stats = ParseStats::default();
+    let mut zone_matches = HashMap::new();
+
+    // Parse and expand every object first, so THISANDFUTURE overrides stored
+    // separately from their master can be applied to its later instances
+    let mut calendars = Vec::with_capacity(ics_objects.len());
     for ics_object in ics_objects {
         // EDS returns raw VEVENT objects without VCALENDAR wrapper
         let wrapped = if ics_object.trim().starts_with("BEGIN:VEVENT") {
@@ -734,51 +1223,155 @@ fn parse_ics_objects(
         };
 
         // Parse with calcard (handles line unfolding and text unescaping)
-        let Ok(calendar) = ICalendar::parse(&wrapped) else {
+        let Ok(mut calendar) = ICalendar::parse(&wrapped) else {
+            stats.invalid += 1;
             continue;
         };
 
-        // Get the local timezone for expansion
-        let local_tz = localzone::get_local_zone()
-            .and_then(|name| chrono_tz::Tz::from_str_insensitive(&name).ok())
-            .unwrap_or(chrono_tz::Tz::UTC);
+        // Expand recurring events (handles RRULE, EXDATE, RDATE). Series are
+        // bounded to the window first, so the limit is only a safety net.
+        let custom_zones = resolve_custom_timezones(
+            &mut calendar,
+            window.now.year(),
+            window.local_tz,
+            &mut zone_matches,
+        );
+        bound_recurrences(&mut calendar, window);
+        let expanded = calendar.expand_dates(window.local_tz, 10_000);
+        let overrides = find_overrides(&calendar, &custom_zones);
+        calendars.push((calendar, expanded, overrides, custom_zones));
+    }
 
-        // Expand recurring events (handles RRULE, EXDATE, RDATE)
-        // Use a large limit since EDS returns master events with RRULEs that
-        // may have started years ago; we need enough instances to reach today.
-        let expanded = calendar.expand_dates(local_tz, 10_000);
+    // Instances replaced by an override, by UID and series start. The master's
+    // copy is dropped even if the override is filtered out below (say, moved
+    // into the past), since the instance no longer happens at its old time.
+    let mut overridden: HashSet<(String, DateTime<Local>)> = HashSet::new();
+    let mut future_overrides: HashMap<String, Vec<FutureOverride>> = HashMap::new();
+    for (calendar_index, (calendar, expanded, overrides, custom_zones)) in
+        calendars.iter().enumerate()
+    {
+        for event in &expanded.events {
+            let comp_id = event.comp_id as usize;
+            let (Some(comp), Some(Some(ov))) =
+                (calendar.components.get(comp_id), overrides.get(comp_id))
+            else {
+                continue;
+            };
+            let start = custom_zones.correct(
+                comp,
+                &ICalendarProperty::Dtstart,
+                event.start.with_timezone(&Local),
+            );
+            let uid = extract_text_property(comp, &ICalendarProperty::Uid).unwrap_or_default();
+            overridden.insert((uid.clone(), start - ov.offset));
+            if ov.this_and_future {
+                let duration = match event.end {
+                    TimeOrDelta::Time(t) => {
+                        custom_zones.correct(
+                            comp,
+                            &ICalendarProperty::Dtend,
+                            t.with_timezone(&Local),
+                        ) - start
+                    }
+                    TimeOrDelta::Delta(d) => d,
+                };
+                future_overrides
+                    .entry(uid)
+                    .or_default()
+                    .push(FutureOverride {
+                        recurrence_id: ov.recurrence_id,
+                        offset: ov.offset,
+                        duration,
+                        calendar: calendar_index,
+                        component: comp_id,
+                    });
+            }
+        }
+    }
 
-        for event in expanded.events {
+    for (calendar, expanded, overrides, custom_zones) in &calendars {
+        for event in &expanded.events {
             // Get the component for this event
-            let Some(comp) = calendar.components.get(event.comp_id as usize) else {
+            let Some(mut comp) = calendar.components.get(event.comp_id as usize) else {
                 continue;
             };
+            stats.instances += 1;
 
             // Skip non-events
             if !matches!(comp.component_type, ICalendarComponentType::VEvent) {
+                *stats.filtered.entry(FilterReason::NotAnEvent).or_default() += 1;
                 continue;
             }
 
             // Convert start time to local
-            let start: DateTime<Local> = event.start.with_timezone(&Local);
+            let mut start: DateTime<Local> = custom_zones.correct(
+                comp,
+                &ICalendarProperty::Dtstart,
+                event.start.with_timezone(&Local),
+            );
 
             // Convert end time to local (handle both Time and Delta variants)
-            let end: DateTime<Local> = match event.end {
-                TimeOrDelta::Time(t) => t.with_timezone(&Local),
+            let mut end: DateTime<Local> = match event.end {
+                TimeOrDelta::Time(t) => {
+                    custom_zones.correct(comp, &ICalendarProperty::Dtend, t.with_timezone(&Local))
+                }
                 TimeOrDelta::Delta(d) => start + d,
             };
 
+            let uid = extract_text_property(comp, &ICalendarProperty::Uid).unwrap_or_default();
+
+            // Work out the start this instance had in its series. Overrides are
+            // keyed by their RECURRENCE-ID, and master instances after a
+            // separately stored THISANDFUTURE override take on its changes.
+            let override_info = overrides.get(event.comp_id as usize).copied().flatten();
+            let is_override = override_info.is_some();
+            let series_start = if let Some(ov) = override_info {
+                start - ov.offset
+            } else {
+                let future = future_overrides.get(&uid).and_then(|list| {
+                    list.iter()
+                        .filter(|f| f.recurrence_id < start)
+                        .max_by_key(|f| f.recurrence_id)
+                });
+                let series_start = start;
+                if let Some(future) = future
+                    && let Some(override_comp) = calendars
+                        .get(future.calendar)
+                        .and_then(|(c, _, _, _)| c.components.get(future.component))
+                {
+                    comp = override_comp;
+                    start += future.offset;
+                    end = start + future.duration;
+                }
+                series_start
+            };
+            if !is_override && overridden.contains(&(uid.clone(), series_start)) {
+                continue;
+            }
+
             // Filter by time range
-            if !should_include_meeting(start, end, now, query_start) {
+            if !should_include_meeting(start, end, window.now, window.start) {
+                let reason = if start < end {
+                    FilterReason::Past
+                } else {
+                    FilterReason::InvalidTimes
+                };
+                *stats.filtered.entry(reason).or_default() += 1;
                 continue;
             }
 
             // Extract properties from the component
-            let uid = extract_text_property(comp, &ICalendarProperty::Uid).unwrap_or_default();
             let title = extract_text_property(comp, &ICalendarProperty::Summary)
                 .unwrap_or_else(|| "Untitled Event".to_string());
             let location = extract_text_property(comp, &ICalendarProperty::Location);
-            let description = extract_text_property(comp, &ICalendarProperty::Description);
+            // The HTML description is usually more complete than the plain one
+            let html_description = parse_alt_description(comp);
+            let description = html_description
+                .as_ref()
+                .map(|html| html.text.clone())
+                .filter(|text| !text.is_empty())
+                .or_else(|| extract_text_property(comp, &ICalendarProperty::Description));
+            let description_links = html_description.map(|html| html.links).unwrap_or_default();
 
             // Check if this is an all-day event (DTSTART has VALUE=DATE or no time part)
             let is_all_day = comp
@@ -799,20 +1392,33 @@ fn parse_ics_objects(
                     has_date_param || no_time
                 });
 
-            // Parse attendance status from ATTENDEE entries
-            let attendance_status = parse_attendance_status_calcard(&comp.entries, user_emails);
+            // Parse the organizer and attendees, and find the user among them
+            let organizer = comp
+                .property(&ICalendarProperty::Organizer)
+                .map(parse_participant);
+            let attendees: Vec<Participant> = comp
+                .properties(&ICalendarProperty::Attendee)
+                .map(parse_participant)
+                .collect();
+            let attendance_status = attendance_status_for(&attendees, user_emails);
+            let conference_urls = parse_conference_urls(comp);
+            let dial_ins = parse_dial_ins(comp, description.as_deref());
+            let status = parse_event_status(comp);
+            let is_free = comp
+                .property(&ICalendarProperty::Transp)
+                .and_then(|entry| entry.values.first())
+                .is_some_and(|value| {
+                    matches!(
+                        value,
+                        ICalendarValue::Transparency(ICalendarTransparency::Transparent)
+                    )
+                });
 
-            // Generate unique ID using uid@timestamp so that recurring
+            // Generate unique ID using uid@series-start so that recurring
             // master expansions and RECURRENCE-ID overrides for the same
-            // date/time produce the same key (enabling dedup below).
-            let meeting_uid = format!("{}@{}", uid, start.format("%Y%m%dT%H%M%S"));
-
-            // Track whether this is a RECURRENCE-ID override (more specific
-            // than an expanded master instance, so preferred during dedup)
-            let is_override = comp
-                .entries
-                .iter()
-                .any(|e| matches!(e.name, ICalendarProperty::RecurrenceId));
+            // instance produce the same key (enabling dedup below), even
+            // when the override moves it to another time.
+            let meeting_uid = format!("{}@{}", uid, series_start.format("%Y%m%dT%H%M%S"));
 
             all_meetings.push((
                 is_override,
@@ -826,10 +1432,18 @@ fn parse_ics_objects(
                     calendar_uid: source_uid.to_string(),
                     is_all_day,
                     attendance_status,
+                    status,
+                    is_free,
+                    organizer,
+                    attendees,
+                    conference_urls,
+                    dial_ins,
+                    description_links,
                 },
             ));
         }
     }
+    stats
 }
 
 /// Deduplicate meetings and sort by start time.
@@ -858,232 +1472,208 @@ fn dedup_and_sort_meetings(all_meetings: Vec<(bool, Meeting)>, limit: usize) ->
     meetings.into_iter().take(limit).collect()
 }
 
-/// Get calendar source UIDs from Evolution Data Server via D-Bus
+/// Determine if a meeting should be included based on its timing.
+/// Returns true if the meeting is either:
+/// - Future (starts after now)
+/// - In-progress (started within the query window and hasn't ended yet)
 ///
-/// This queries the `SourceManager`'s `ObjectManager` interface to discover
-/// all sources, including those from GNOME Online Accounts which are
-/// not stored as files in ~/.config/evolution/sources/
-async fn get_calendar_source_uids(conn: &Connection) -> Option<Vec<String>> {
-    use std::collections::HashMap;
-    use zvariant::{OwnedObjectPath, OwnedValue, Value};
-
-    let source_manager_proxy = zbus::Proxy::new(
-        conn,
-        "org.gnome.evolution.dataserver.Sources5",
-        "/org/gnome/evolution/dataserver/SourceManager",
-        "org.freedesktop.DBus.ObjectManager",
-    )
-    .await
-    .ok()?;
-
-    // GetManagedObjects returns a{oa{sa{sv}}} - dict of object paths to interface properties
-    let reply = source_manager_proxy
-        .call_method("GetManagedObjects", &())
-        .await
-        .ok()?;
-
-    let objects: HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>> =
-        reply.body().ok()?;
-
-    let mut source_uids = Vec::new();
-
-    for (_path, interfaces) in objects {
-        // Look for the Source interface
-        if let Some(source_props) = interfaces.get("org.gnome.evolution.dataserver.Source") {
-            // Get the UID
-            let uid = source_props.get("UID").and_then(|v| {
-                if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
-                    Some(s.to_string())
-                } else {
-                    None
-                }
-            });
-
-            // Get the Data (source configuration) and check for [Calendar] section
-            let has_calendar = source_props.get("Data").is_some_and(|v| {
-                if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
-                    s.contains("[Calendar]")
-                } else {
-                    false
-                }
-            });
+/// Also validates that start < end (invalid meetings are excluded).
+fn should_include_meeting(
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+    now: DateTime<Local>,
+    query_start: DateTime<Local>,
+) -> bool {
+    let is_future = start > now;
+    let is_in_progress = start <= now && start > query_start && end > now;
+    (is_future || is_in_progress) && start < end
+}
 
-            if let Some(uid) = uid
-                && has_calendar
-            {
-                source_uids.push(uid);
-            }
-        }
+/// Read an event's `STATUS`. Statuses meant for tasks and journal entries
+/// are treated as confirmed.
+fn parse_event_status(comp: &calcard::icalendar::ICalendarComponent) -> EventStatus {
+    match comp
+        .property(&ICalendarProperty::Status)
+        .and_then(|entry| entry.values.first())
+    {
+        Some(ICalendarValue::Status(ICalendarStatus::Cancelled)) => EventStatus::Cancelled,
+        Some(ICalendarValue::Status(ICalendarStatus::Tentative)) => EventStatus::Tentative,
+        _ => EventStatus::Confirmed,
     }
-
-    Some(source_uids)
 }
 
-/// Get calendar info (UID and display name) from Evolution Data Server via D-Bus
-async fn get_calendars_from_dbus(conn: &Connection) -> Option<Vec<CalendarInfo>> {
-    use std::collections::HashMap;
-    use zvariant::{OwnedObjectPath, OwnedValue, Value};
-
-    let source_manager_proxy = zbus::Proxy::new(
-        conn,
-        "org.gnome.evolution.dataserver.Sources5",
-        "/org/gnome/evolution/dataserver/SourceManager",
-        "org.freedesktop.DBus.ObjectManager",
-    )
-    .await
-    .ok()?;
-
-    let reply = source_manager_proxy
-        .call_method("GetManagedObjects", &())
-        .await
-        .ok()?;
-
-    let objects: HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>> =
-        reply.body().ok()?;
-
-    let mut calendars = Vec::new();
-
-    for (_path, interfaces) in objects {
-        if let Some(source_props) = interfaces.get("org.gnome.evolution.dataserver.Source") {
-            // Get the UID
-            let uid = source_props.get("UID").and_then(|v| {
-                if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
-                    Some(s.to_string())
-                } else {
-                    None
-                }
-            });
-
-            // Get the Data field and extract DisplayName and check for [Calendar]
-            let data = source_props.get("Data").and_then(|v| {
-                if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
-                    Some(s.to_string())
-                } else {
-                    None
-                }
-            });
+/// Read the video call links from an event's structured conference data:
+/// RFC 7986 `CONFERENCE` entries, then Google's `X-GOOGLE-CONFERENCE` and
+/// Teams' `X-MICROSOFT-SKYPETEAMSMEETINGURL`.
+fn parse_conference_urls(comp: &calcard::icalendar::ICalendarComponent) -> Vec<String> {
+    let is_web_link = |url: &str| url.starts_with("https://") || url.starts_with("http://");
+    let mut urls: Vec<String> = Vec::new();
 
-            if let (Some(uid), Some(data)) = (uid, data)
-                && data.contains("[Calendar]")
-            {
-                let display_name = parse_display_name(&data).unwrap_or_else(|| uid.clone());
-                let color = parse_color(&data);
-                let backend = parse_backend_name(&data);
-                calendars.push(CalendarInfo {
-                    uid,
-                    display_name,
-                    color,
-                    last_synced: None, // Will be filled in below
-                    backend,
-                });
-            }
+    // Entries that only offer audio or phone access aren't video calls
+    for entry in comp.properties(&ICalendarProperty::Conference) {
+        let features: Vec<&ICalendarFeatureType> = entry
+            .params
+            .iter()
+            .filter_map(|p| match &p.value {
+                ICalendarParameterValue::Feature(feature) => Some(feature),
+                _ => None,
+            })
+            .collect();
+        let audio_only = !features.is_empty()
+            && features
+                .iter()
+                .all(|f| matches!(f, ICalendarFeatureType::Audio | ICalendarFeatureType::Phone));
+        let url = entry.values.iter().find_map(|v| match v {
+            ICalendarValue::Uri(calcard::icalendar::Uri::Location(uri)) => Some(uri.trim()),
+            ICalendarValue::Text(text) => Some(text.trim()),
+            _ => None,
+        });
+        if let Some(url) = url.filter(|url| is_web_link(url))
+            && !audio_only
+            && !urls.iter().any(|u| u == url)
+        {
+            urls.push(url.to_string());
         }
     }
 
-    // Fetch last_synced (Revision) for each calendar
-    if let Ok(factory_proxy) = zbus::Proxy::new(
-        conn,
-        "org.gnome.evolution.dataserver.Calendar8",
-        "/org/gnome/evolution/dataserver/CalendarFactory",
-        "org.gnome.evolution.dataserver.CalendarFactory",
-    )
-    .await
-    {
-        for cal in &mut calendars {
-            if let Ok(reply) = factory_proxy
-                .call_method("OpenCalendar", &(cal.uid.as_str(),))
-                .await
-                && let Ok((calendar_path, bus_name)) = reply.body::<(String, String)>()
-                && let Ok(cal_proxy) = zbus::Proxy::new(
-                    conn,
-                    bus_name.as_str(),
-                    calendar_path.as_str(),
-                    "org.gnome.evolution.dataserver.Calendar",
-                )
-                .await
-            {
-                // Initialize the backend (required before reading properties)
-                let _ = cal_proxy.call_method("Open", &()).await;
-                // Get the Revision property (format: "2026-01-08T04:19:20Z(0)")
-                if let Ok(revision) = cal_proxy.get_property::<String>("Revision").await {
-                    // Extract just the timestamp part before the parentheses
-                    let timestamp = revision.split('(').next().unwrap_or(&revision);
-                    cal.last_synced = Some(timestamp.to_string());
-                }
+    for name in ["X-GOOGLE-CONFERENCE", "X-MICROSOFT-SKYPETEAMSMEETINGURL"] {
+        for entry in &comp.entries {
+            if !matches!(&entry.name, ICalendarProperty::Other(n) if n.eq_ignore_ascii_case(name)) {
+                continue;
+            }
+            // Unknown properties are split at semicolons, so put the URL back together
+            let url = entry
+                .values
+                .iter()
+                .filter_map(|v| match v {
+                    ICalendarValue::Text(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join(";");
+            let url = url.trim();
+            if is_web_link(url) && !urls.iter().any(|u| u == url) {
+                urls.push(url.to_string());
             }
         }
     }
 
-    // Sort by display name for consistent ordering
-    calendars.sort_by(|a, b| a.display_name.cmp(&b.display_name));
-    Some(calendars)
+    urls
+}
+
+/// Read the HTML description from `X-ALT-DESC;FMTTYPE=text/html`, as sent by
+/// Outlook and Teams.
+fn parse_alt_description(
+    comp: &calcard::icalendar::ICalendarComponent,
+) -> Option<html::HtmlDescription> {
+    let entry = comp.entries.iter().find(|entry| {
+        matches!(&entry.name, ICalendarProperty::Other(n) if n.eq_ignore_ascii_case("X-ALT-DESC"))
+            && entry.params.iter().any(|p| {
+                matches!(
+                    (&p.name, &p.value),
+                    (ICalendarParameterName::Fmttype, ICalendarParameterValue::Text(t))
+                        if t.eq_ignore_ascii_case("text/html")
+                )
+            })
+    })?;
+    // Unknown properties are split at semicolons, which HTML entities contain
+    let html = entry
+        .values
+        .iter()
+        .filter_map(|v| match v {
+            ICalendarValue::Text(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join(";");
+    Some(html::parse_html_description(&html))
 }
 
-/// Parse `DisplayName` from INI-format source data
-fn parse_display_name(data: &str) -> Option<String> {
-    // Look for DisplayName= line (without locale suffix like DisplayName[en])
-    for line in data.lines() {
-        let line = line.trim();
-        if line.starts_with("DisplayName=") {
-            return Some(line.strip_prefix("DisplayName=")?.to_string());
+/// Read the phone numbers for joining a meeting: `tel:` entries of
+/// `CONFERENCE`, then numbers listed in the description. Numbers without
+/// their own PIN get the conference ID mentioned in the description.
+fn parse_dial_ins(
+    comp: &calcard::icalendar::ICalendarComponent,
+    description: Option<&str>,
+) -> Vec<DialIn> {
+    let mut dial_ins: Vec<DialIn> = Vec::new();
+    let mut add = |dial_in: DialIn| {
+        let key =
+            |d: &DialIn| -> String { d.number.chars().filter(char::is_ascii_digit).collect() };
+        match dial_ins.iter_mut().find(|d| key(d) == key(&dial_in)) {
+            Some(existing) => {
+                existing.pin = existing.pin.take().or(dial_in.pin);
+                existing.label = existing.label.take().or(dial_in.label);
+            }
+            None => dial_ins.push(dial_in),
+        }
+    };
+
+    for entry in comp.properties(&ICalendarProperty::Conference) {
+        let Some(uri) = entry.values.iter().find_map(|v| match v {
+            ICalendarValue::Uri(calcard::icalendar::Uri::Location(uri)) => Some(uri.trim()),
+            _ => None,
+        }) else {
+            continue;
+        };
+        let Some((scheme, number)) = uri.split_once(':') else {
+            continue;
+        };
+        if !scheme.eq_ignore_ascii_case("tel") {
+            continue;
         }
+        let (number, pin) = match number.split_once(',') {
+            Some((number, pin)) => (number, pin.trim_matches([',', '#'])),
+            None => (number, ""),
+        };
+        let label = entry.params.iter().find_map(|p| match (&p.name, &p.value) {
+            (ICalendarParameterName::Label, ICalendarParameterValue::Text(label)) => {
+                Some(label.trim().to_string())
+            }
+            _ => None,
+        });
+        add(DialIn {
+            number: number.to_string(),
+            pin: (!pin.is_empty()).then(|| pin.to_string()),
+            label: label.filter(|l| !l.is_empty()),
+        });
     }
-    None
-}
 
-/// Parse Color from INI-format source data (in [Calendar] section)
-fn parse_color(data: &str) -> Option<String> {
-    // Look for Color= specifically in the [Calendar] section, since other sections
-    // (like [WebDAV Backend]) may have an empty Color= field
-    let mut in_calendar_section = false;
-    for line in data.lines() {
-        let line = line.trim();
-        if line == "[Calendar]" {
-            in_calendar_section = true;
-        } else if line.starts_with('[') {
-            in_calendar_section = false;
-        } else if in_calendar_section && line.starts_with("Color=") {
-            let color = line.strip_prefix("Color=")?.to_string();
-            if !color.is_empty() {
-                return Some(color);
-            }
+    let description = description.unwrap_or_default();
+    for line in description.lines() {
+        for caps in PHONE_NUMBER.captures_iter(line) {
+            let Some(whole) = caps.get(0) else {
+                continue;
+            };
+            let number = whole.as_str().split(',').next().unwrap_or_default();
+            // Whatever follows the number on its line usually says where it's for
+            let rest = &line[whole.end()..];
+            let label = rest
+                .split(['<', '+'])
+                .next()
+                .unwrap_or_default()
+                .trim_start_matches(|c: char| c.is_ascii_digit() || " ,*#-–:>".contains(c))
+                .trim();
+            add(DialIn {
+                number: number.trim().to_string(),
+                pin: caps.get(1).map(|pin| pin.as_str().to_string()),
+                label: (!label.is_empty() && label.chars().count() <= 40 && !label.contains("://"))
+                    .then(|| label.to_string()),
+            });
         }
     }
-    None
-}
 
-/// Parse `BackendName` from INI-format source data (in [Calendar] section)
-fn parse_backend_name(data: &str) -> Option<String> {
-    // Look for BackendName= in the [Calendar] section
-    let mut in_calendar_section = false;
-    for line in data.lines() {
-        let line = line.trim();
-        if line == "[Calendar]" {
-            in_calendar_section = true;
-        } else if line.starts_with('[') {
-            in_calendar_section = false;
-        } else if in_calendar_section && line.starts_with("BackendName=") {
-            return Some(line.strip_prefix("BackendName=")?.to_string());
+    let conference_id = CONFERENCE_ID
+        .captures(description)
+        .and_then(|caps| caps.get(1))
+        .map(|id| id.as_str().replace(' ', ""));
+    if let Some(id) = conference_id {
+        for dial_in in &mut dial_ins {
+            dial_in.pin.get_or_insert_with(|| id.clone());
         }
     }
-    None
-}
 
-/// Determine if a meeting should be included based on its timing.
-/// Returns true if the meeting is either:
-/// - Future (starts after now)
-/// - In-progress (started within the query window and hasn't ended yet)
-///
-/// Also validates that start < end (invalid meetings are excluded).
-fn should_include_meeting(
-    start: DateTime<Local>,
-    end: DateTime<Local>,
-    now: DateTime<Local>,
-    query_start: DateTime<Local>,
-) -> bool {
-    let is_future = start > now;
-    let is_in_progress = start <= now && start > query_start && end > now;
-    (is_future || is_in_progress) && start < end
+    dial_ins
 }
 
 /// Extract text value from a calcard component property
@@ -1099,74 +1689,81 @@ fn extract_text_property(
     })
 }
 
-/// Parse attendance status from calcard ATTENDEE entries
-fn parse_attendance_status_calcard(
-    entries: &[ICalendarEntry],
-    user_emails: &[String],
-) -> AttendanceStatus {
-    if user_emails.is_empty() {
-        return AttendanceStatus::None;
-    }
-
-    let user_emails_lower: Vec<String> = user_emails.iter().map(|e| e.to_lowercase()).collect();
-
-    // Find ATTENDEE entries
-    for entry in entries
-        .iter()
-        .filter(|e| matches!(e.name, ICalendarProperty::Attendee))
-    {
-        // Extract email from parameters or value
-        let attendee_email = entry
-            .params
-            .iter()
-            .find_map(|p| {
-                if matches!(p.name, ICalendarParameterName::Email)
-                    && let ICalendarParameterValue::Text(email) = &p.value
-                {
-                    return Some(email.to_lowercase());
-                }
-                None
-            })
-            .or_else(|| {
-                // Fall back to extracting from mailto: in the value
-                entry.values.iter().find_map(|v| {
-                    if let ICalendarValue::Uri(calcard::icalendar::Uri::Location(uri_str)) = v {
-                        let uri = uri_str.to_lowercase();
-                        if uri.starts_with("mailto:") {
-                            return Some(uri.trim_start_matches("mailto:").to_string());
-                        }
+/// Read an `ORGANIZER` or `ATTENDEE` entry.
+fn parse_participant(entry: &ICalendarEntry) -> Participant {
+    let mut participant = Participant::default();
+    for param in &entry.params {
+        match (&param.name, &param.value) {
+            (ICalendarParameterName::Cn, ICalendarParameterValue::Text(name)) => {
+                participant.name = Some(name.clone());
+            }
+            (ICalendarParameterName::Email, ICalendarParameterValue::Text(email)) => {
+                participant.email = Some(email.clone());
+            }
+            (ICalendarParameterName::Role, ICalendarParameterValue::Role(role)) => {
+                participant.role = match role {
+                    ICalendarParticipationRole::Chair | ICalendarParticipationRole::Owner => {
+                        ParticipantRole::Chair
                     }
-                    None
-                })
-            });
-
-        // Check if this attendee matches any of the user's emails
-        let is_user = attendee_email
-            .as_ref()
-            .is_some_and(|email| user_emails_lower.iter().any(|ue| ue == email));
+                    ICalendarParticipationRole::ReqParticipant => ParticipantRole::Required,
+                    ICalendarParticipationRole::OptParticipant => ParticipantRole::Optional,
+                    ICalendarParticipationRole::NonParticipant => ParticipantRole::NonParticipant,
+                };
+            }
+            (ICalendarParameterName::Partstat, ICalendarParameterValue::Partstat(status)) => {
+                participant.status = match status {
+                    ICalendarParticipationStatus::Accepted => AttendanceStatus::Accepted,
+                    ICalendarParticipationStatus::Tentative => AttendanceStatus::Tentative,
+                    ICalendarParticipationStatus::Declined => AttendanceStatus::Declined,
+                    ICalendarParticipationStatus::NeedsAction => AttendanceStatus::NeedsAction,
+                    _ => AttendanceStatus::None,
+                };
+            }
+            (ICalendarParameterName::Cutype, ICalendarParameterValue::Cutype(kind)) => {
+                participant.kind = match kind {
+                    ICalendarUserTypes::Individual => ParticipantKind::Individual,
+                    ICalendarUserTypes::Group => ParticipantKind::Group,
+                    ICalendarUserTypes::Resource => ParticipantKind::Resource,
+                    ICalendarUserTypes::Room => ParticipantKind::Room,
+                    ICalendarUserTypes::Unknown => ParticipantKind::Unknown,
+                };
+            }
+            _ => {}
+        }
+    }
 
-        if is_user {
-            // Extract PARTSTAT from parameters
-            for param in &entry.params {
-                if matches!(param.name, ICalendarParameterName::Partstat)
-                    && let ICalendarParameterValue::Partstat(status) = &param.value
-                {
-                    return match status {
-                        ICalendarParticipationStatus::Accepted => AttendanceStatus::Accepted,
-                        ICalendarParticipationStatus::Tentative => AttendanceStatus::Tentative,
-                        ICalendarParticipationStatus::Declined => AttendanceStatus::Declined,
-                        ICalendarParticipationStatus::NeedsAction => AttendanceStatus::NeedsAction,
-                        _ => AttendanceStatus::None,
-                    };
+    // Fall back to extracting the email from a mailto: value
+    if participant.email.is_none() {
+        participant.email = entry.values.iter().find_map(|v| {
+            if let ICalendarValue::Uri(calcard::icalendar::Uri::Location(uri)) = v {
+                let (scheme, address) = uri.split_once(':')?;
+                if scheme.eq_ignore_ascii_case("mailto") && !address.is_empty() {
+                    return Some(address.to_string());
                 }
             }
-        }
+            None
+        });
     }
 
-    AttendanceStatus::None
+    participant
+}
+
+/// Parse the user's attendance status from the attendees, matching on any of
+/// the user's emails.
+fn attendance_status_for(attendees: &[Participant], user_emails: &[String]) -> AttendanceStatus {
+    attendees
+        .iter()
+        .filter(|a| a.status != AttendanceStatus::None)
+        .find(|a| {
+            a.email.as_ref().is_some_and(|email| {
+                user_emails
+                    .iter()
+                    .any(|ue| ue.trim().eq_ignore_ascii_case(email))
+            })
+        })
+        .map_or(AttendanceStatus::None, |a| a.status)
 }
 
-#[allow(dead_code)] // Used by tests
 fn parse_ical_datetime(value: &str, tzid: Option<&str>) -> Option<DateTime<Local>> {
     // The value might be in formats like:
     // - "20240221T123000" (local time)
@@ -1232,7 +1829,6 @@ fn parse_ical_datetime(value: &str, tzid: Option<&str>) -> Option<DateTime<Local
 /// (e.g., "Eastern Standard Time") via the CLDR mapping from the localzone crate.
 ///
 /// Returns `None` and logs a warning if the timezone cannot be parsed.
-#[allow(dead_code)] // Used by tests
 fn parse_ical_timezone(tz_str: &str) -> Option<Tz> {
     // Helper to convert an IANA timezone string to chrono_tz::Tz
     let iana_to_tz = |iana: &str| -> Option<Tz> {
@@ -1287,41 +1883,458 @@ fn parse_ical_timezone(tz_str: &str) -> Option<Tz> {
     None
 }
 
-/// Extract a meeting URL from the meeting's location or description fields
-/// Checks location first (most common place for meeting links), then description
-pub fn extract_meeting_url(meeting: &Meeting, patterns: &[String]) -> Option<String> {
-    // Compile patterns, skipping any invalid ones
-    let compiled: Vec<Regex> = patterns.iter().filter_map(|p| Regex::new(p).ok()).collect();
+/// Point VTIMEZONE definitions with unrecognized TZIDs at the IANA zone that
+/// follows the same rules, so their events aren't read as local time.
+///
+/// Exchange and some older servers name zones freely (e.g. "Customized Time
+/// Zone") and rely on the embedded STANDARD/DAYLIGHT rules. The matched zone
+/// is added as an `X-LIC-LOCATION`, which calcard's resolver already honours.
+/// Definitions no IANA zone follows are returned, so their times can be
+/// corrected from the rules directly.
+///
+/// Matches are remembered in `cache` by the rules rather than the TZID, since
+/// every object from the same organiser tends to repeat the same definition
+/// while Exchange gives different ones the same name.
+fn resolve_custom_timezones(
+    calendar: &mut ICalendar,
+    year: i32,
+    local_tz: Tz,
+    cache: &mut HashMap<VTimezone, Option<Tz>>,
+) -> CustomZones {
+    let unresolved: Vec<(usize, String)> = calendar
+        .components
+        .iter()
+        .enumerate()
+        .filter(|(_, comp)| {
+            matches!(comp.component_type, ICalendarComponentType::VTimezone)
+                && comp.timezone().is_none()
+        })
+        .filter_map(|(index, comp)| {
+            let tzid = comp
+                .property(&ICalendarProperty::Tzid)?
+                .values
+                .first()?
+                .as_text()?;
+            Some((index, tzid.to_string()))
+        })
+        .collect();
 
-    if compiled.is_empty() {
-        return None;
+    let mut custom = CustomZones {
+        zones: HashMap::new(),
+        local_tz,
+    };
+    for (index, tzid) in unresolved {
+        let Some(vtz) = VTimezone::parse(calendar, index) else {
+            continue;
+        };
+        let tz = *cache
+            .entry(vtz.clone())
+            .or_insert_with(|| vtz.matching_zone(year));
+        match (tz, calendar.components.get_mut(index)) {
+            (Some(tz), Some(comp)) => comp.entries.push(ICalendarEntry {
+                name: ICalendarProperty::Other("X-LIC-LOCATION".to_string()),
+                params: Vec::new(),
+                values: vec![ICalendarValue::Text(tz.name().to_string())],
+            }),
+            (None, _) => {
+                custom.zones.insert(tzid, vtz);
+            }
+            (Some(_), None) => {}
+        }
+    }
+    custom
+}
+
+/// Time zone definitions that no IANA zone follows, by TZID.
+///
+/// calcard reads times in them as local time; [`CustomZones::correct`] puts
+/// them at the offset the definition's observances give for each instance.
+#[derive(Debug)]
+struct CustomZones {
+    zones: HashMap<String, VTimezone>,
+    /// The zone calcard falls back to for unknown TZIDs.
+    local_tz: Tz,
+}
+
+impl CustomZones {
+    /// Correct a time calcard worked out from a component's date-time
+    /// property, if that property's TZID is one of these zones. An end
+    /// computed without a DTEND is in the zone of the DTSTART.
+    fn correct(
+        &self,
+        comp: &calcard::icalendar::ICalendarComponent,
+        prop: &ICalendarProperty,
+        time: DateTime<Local>,
+    ) -> DateTime<Local> {
+        let Some(vtz) = comp
+            .property(prop)
+            .or_else(|| comp.property(&ICalendarProperty::Dtstart))
+            .and_then(|entry| entry.tz_id())
+            .and_then(|tzid| self.zones.get(tzid))
+        else {
+            return time;
+        };
+        let wall = time.with_timezone(&self.local_tz).naive_local();
+        vtz.offset_at_local(wall)
+            .and_then(chrono::FixedOffset::east_opt)
+            .and_then(|offset| offset.from_local_datetime(&wall).single())
+            .map_or(time, |corrected| corrected.with_timezone(&Local))
+    }
+}
+
+/// The STANDARD and DAYLIGHT observances of a VTIMEZONE.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct VTimezone {
+    observances: Vec<Observance>,
+}
+
+/// One STANDARD or DAYLIGHT observance: the offset it switches to, and when.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Observance {
+    /// First onset, in the local time before the switch.
+    start: NaiveDateTime,
+    /// UTC offsets before and after the switch, in seconds.
+    offset_from: i32,
+    offset_to: i32,
+    /// Yearly rule for later onsets, if it's one we understand.
+    rule: Option<YearlyRule>,
+    /// The rule's UNTIL, in UTC.
+    until: Option<NaiveDateTime>,
+    /// Extra onsets from RDATE, in local time.
+    rdates: Vec<NaiveDateTime>,
+}
+
+/// An observance's yearly rule, such as `FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct YearlyRule {
+    month: u32,
+    day: RuleDay,
+}
+
+/// Which day of the month a yearly rule falls on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RuleDay {
+    /// The nth weekday of the month, counted from the end if negative.
+    Weekday(i16, chrono::Weekday),
+    /// A fixed day of the month, counted from the end if negative.
+    MonthDay(i8),
+}
+
+impl VTimezone {
+    /// Read the observances of the VTIMEZONE at `index` in the calendar.
+    fn parse(calendar: &ICalendar, index: usize) -> Option<Self> {
+        let observances: Vec<Observance> = calendar
+            .components
+            .get(index)?
+            .component_ids
+            .iter()
+            .filter_map(|id| calendar.components.get(*id as usize))
+            .filter(|comp| {
+                matches!(
+                    comp.component_type,
+                    ICalendarComponentType::Standard | ICalendarComponentType::Daylight
+                )
+            })
+            .filter_map(Observance::parse)
+            .collect();
+        (!observances.is_empty()).then_some(Self { observances })
+    }
+
+    /// The UTC offset in effect at a UTC time, in seconds.
+    fn offset_at(&self, utc: NaiveDateTime) -> Option<i32> {
+        let latest = self
+            .observances
+            .iter()
+            .flat_map(|o| {
+                (utc.year() - 1..=utc.year())
+                    .flat_map(|year| o.onsets(year))
+                    .chain(std::iter::once(o.start_utc()))
+                    .filter(|onset| *onset <= utc)
+                    .map(|onset| (onset, o.offset_to))
+            })
+            .max_by_key(|(onset, _)| *onset);
+        match latest {
+            Some((_, offset)) => Some(offset),
+            // Before the first onset, the zone was on the first offset_from
+            None => self
+                .observances
+                .iter()
+                .min_by_key(|o| o.start)
+                .map(|o| o.offset_from),
+        }
+    }
+
+    /// The UTC offset in effect at a wall-clock time in this zone, in seconds.
+    /// A time repeated when the clocks go back gets the earlier offset, and
+    /// one skipped when they go forward is read as if the clocks hadn't moved.
+    fn offset_at_local(&self, local: NaiveDateTime) -> Option<i32> {
+        let mut offsets: Vec<i32> = self
+            .observances
+            .iter()
+            .flat_map(|o| [o.offset_from, o.offset_to])
+            .collect();
+        offsets.sort_unstable_by_key(|&offset| std::cmp::Reverse(offset));
+        offsets.dedup();
+        offsets
+            .into_iter()
+            .find(|&offset| {
+                self.offset_at(local - chrono::Duration::seconds(i64::from(offset))) == Some(offset)
+            })
+            .or_else(|| {
+                let earlier = local - chrono::Duration::days(1);
+                self.offset_at(earlier)
+            })
+    }
+
+    /// The first IANA zone that has the same offsets as this definition
+    /// throughout the years around `year`, including around each switch.
+    fn matching_zone(&self, year: i32) -> Option<Tz> {
+        use chrono::Offset;
+
+        let margin = chrono::Duration::minutes(30);
+        let mut probes = Vec::new();
+        for year in year - 1..=year + 1 {
+            probes.extend(
+                (1..=12)
+                    .filter_map(|month| NaiveDate::from_ymd_opt(year, month, 15))
+                    .filter_map(|date| date.and_hms_opt(12, 0, 0)),
+            );
+            for observance in &self.observances {
+                for onset in observance.onsets(year) {
+                    probes.extend([onset - margin, onset + margin]);
+                }
+            }
+        }
+        let expected: Vec<(NaiveDateTime, i32)> = probes
+            .into_iter()
+            .filter_map(|utc| Some((utc, self.offset_at(utc)?)))
+            .collect();
+        if expected.is_empty() {
+            return None;
+        }
+
+        chrono_tz::TZ_VARIANTS.iter().copied().find(|tz| {
+            expected.iter().all(|(utc, offset)| {
+                tz.offset_from_utc_datetime(utc).fix().local_minus_utc() == *offset
+            })
+        })
+    }
+}
+
+impl Observance {
+    fn parse(comp: &calcard::icalendar::ICalendarComponent) -> Option<Self> {
+        let first = |prop: &ICalendarProperty| comp.property(prop)?.values.first();
+        let offset = |prop: &ICalendarProperty| match first(prop)? {
+            ICalendarValue::PartialDateTime(dt) => {
+                let seconds =
+                    i32::from(dt.tz_hour?) * 3600 + i32::from(dt.tz_minute.unwrap_or(0)) * 60;
+                Some(if dt.tz_minus { -seconds } else { seconds })
+            }
+            _ => None,
+        };
+        let local = |value: &ICalendarValue| match value {
+            ICalendarValue::PartialDateTime(dt) => dt.to_date_time().map(|dt| dt.date_time),
+            _ => None,
+        };
+
+        let start = local(first(&ICalendarProperty::Dtstart)?)?;
+        let offset_to = offset(&ICalendarProperty::Tzoffsetto)?;
+        let offset_from = offset(&ICalendarProperty::Tzoffsetfrom).unwrap_or(offset_to);
+
+        let recurrence = match first(&ICalendarProperty::Rrule) {
+            Some(ICalendarValue::RecurrenceRule(rule)) => Some(rule),
+            _ => None,
+        };
+        let rule = recurrence.and_then(|rule| YearlyRule::parse(rule));
+        let until = recurrence
+            .and_then(|rule| rule.until.as_ref())
+            .and_then(PartialDateTime::to_date_time)
+            .map(|until| until.date_time);
+        let rdates = comp
+            .properties(&ICalendarProperty::Rdate)
+            .flat_map(|entry| entry.values.iter())
+            .filter_map(local)
+            .collect();
+
+        Some(Self {
+            start,
+            offset_from,
+            offset_to,
+            rule,
+            until,
+            rdates,
+        })
+    }
+
+    /// The first onset, in UTC.
+    fn start_utc(&self) -> NaiveDateTime {
+        self.start - chrono::Duration::seconds(i64::from(self.offset_from))
+    }
+
+    /// Onsets that fall in a given year, in UTC.
+    fn onsets(&self, year: i32) -> Vec<NaiveDateTime> {
+        let mut onsets: Vec<NaiveDateTime> = self
+            .rdates
+            .iter()
+            .copied()
+            .filter(|rdate| rdate.year() == year)
+            .collect();
+        if self.start.year() == year {
+            onsets.push(self.start);
+        } else if year > self.start.year()
+            && let Some(date) = self.rule.and_then(|rule| rule.date_in(year))
+        {
+            onsets.push(date.and_time(self.start.time()));
+        }
+
+        let from = chrono::Duration::seconds(i64::from(self.offset_from));
+        onsets
+            .into_iter()
+            .map(|onset| onset - from)
+            .filter(|onset| self.until.is_none_or(|until| *onset <= until))
+            .collect()
+    }
+}
+
+impl YearlyRule {
+    /// Understand the yearly rules time zone definitions use in practice.
+    fn parse(rule: &calcard::icalendar::ICalendarRecurrenceRule) -> Option<Self> {
+        if rule.freq != ICalendarFrequency::Yearly || rule.bymonth.len() != 1 {
+            return None;
+        }
+        let month = u32::from(rule.bymonth[0].month());
+        let day = match (rule.byday.as_slice(), rule.bymonthday.as_slice()) {
+            ([day], month_days) => {
+                // The ordinal is usually on the weekday, but older servers
+                // use BYSETPOS or a week's range of BYMONTHDAY instead
+                let ordinal = day
+                    .ordwk
+                    .or_else(|| rule.bysetpos.first().and_then(|p| i16::try_from(*p).ok()))
+                    .or_else(|| {
+                        let first = *month_days.iter().min()?;
+                        (first > 0).then(|| i16::from((first - 1) / 7 + 1))
+                    })
+                    .unwrap_or(1);
+                RuleDay::Weekday(ordinal, weekday(day.weekday))
+            }
+            ([], [day]) => RuleDay::MonthDay(*day),
+            _ => return None,
+        };
+        Some(Self { month, day })
     }
 
-    // Check location first
-    if let Some(ref location) = meeting.location {
-        for regex in &compiled {
-            if let Some(m) = regex.find(location) {
-                return Some(m.as_str().to_string());
+    /// The date the rule falls on in a given year.
+    fn date_in(self, year: i32) -> Option<NaiveDate> {
+        let first = NaiveDate::from_ymd_opt(year, self.month, 1)?;
+        let last = first
+            .checked_add_months(chrono::Months::new(1))?
+            .pred_opt()?;
+        match self.day {
+            RuleDay::MonthDay(day) if day > 0 => first.with_day(u32::from(day.unsigned_abs())),
+            RuleDay::MonthDay(day) => {
+                last.checked_sub_days(chrono::Days::new(u64::from(day.unsigned_abs() - 1)))
+            }
+            RuleDay::Weekday(ordinal, weekday) if ordinal > 0 => {
+                let ordinal = u8::try_from(ordinal).ok()?;
+                // A fifth weekday that doesn't exist means the last one
+                NaiveDate::from_weekday_of_month_opt(year, self.month, weekday, ordinal)
+                    .or_else(|| Self::last_weekday(last, weekday, 1))
             }
+            RuleDay::Weekday(ordinal, weekday) => {
+                Self::last_weekday(last, weekday, ordinal.unsigned_abs())
+            }
+        }
+    }
+
+    /// The nth-from-last `weekday` of the month ending on `last`.
+    fn last_weekday(last: NaiveDate, weekday: chrono::Weekday, nth: u16) -> Option<NaiveDate> {
+        let back = (7 + last.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+        let weeks_back = u64::from(nth.max(1) - 1) * 7;
+        last.checked_sub_days(chrono::Days::new(u64::from(back) + weeks_back))
+    }
+}
+
+/// Convert a calcard weekday to chrono's.
+fn weekday(day: calcard::icalendar::ICalendarWeekday) -> chrono::Weekday {
+    use calcard::icalendar::ICalendarWeekday;
+
+    match day {
+        ICalendarWeekday::Monday => chrono::Weekday::Mon,
+        ICalendarWeekday::Tuesday => chrono::Weekday::Tue,
+        ICalendarWeekday::Wednesday => chrono::Weekday::Wed,
+        ICalendarWeekday::Thursday => chrono::Weekday::Thu,
+        ICalendarWeekday::Friday => chrono::Weekday::Fri,
+        ICalendarWeekday::Saturday => chrono::Weekday::Sat,
+        ICalendarWeekday::Sunday => chrono::Weekday::Sun,
+    }
+}
+
+/// Replace redirect-wrapped links in some text with the links they point to.
+pub fn unwrap_redirects(text: &str) -> Cow<'_, str> {
+    REDIRECT_WRAPPER.replace_all(text, |caps: &regex::Captures| {
+        unwrap_redirect(&caps[0]).unwrap_or_else(|| caps[0].to_string())
+    })
+}
+
+/// The link a redirect wrapper points to, with any nested wrappers removed too.
+/// Returns `None` if the wrapper doesn't carry a web link.
+fn unwrap_redirect(wrapper: &str) -> Option<String> {
+    let parsed = url::Url::parse(wrapper).ok()?;
+    let (_, target) = parsed
+        .query_pairs()
+        .find(|(key, _)| key == "url" || key == "q")?;
+    if !target.starts_with("https://") && !target.starts_with("http://") {
+        return None;
+    }
+    Some(unwrap_redirects(&target).into_owned())
+}
+
+/// Extract every distinct meeting URL from the meeting, best first.
+/// Redirect wrappers such as Outlook SafeLinks are removed first, so the real
+/// links are matched and opened.
+/// Conference properties come first, then the location (most common place for
+/// meeting links), then the description, then the links in the HTML description
+pub fn extract_meeting_urls(meeting: &Meeting, patterns: &[String]) -> Vec<String> {
+    // Links from conference properties don't depend on the patterns
+    let mut urls: Vec<String> = Vec::new();
+    for url in &meeting.conference_urls {
+        let url = unwrap_redirects(url).into_owned();
+        if !urls.contains(&url) {
+            urls.push(url);
         }
     }
 
-    // Then check description
-    if let Some(ref description) = meeting.description {
-        for regex in &compiled {
-            if let Some(m) = regex.find(description) {
-                return Some(m.as_str().to_string());
+    // Compile patterns, skipping any invalid ones
+    let compiled: Vec<Regex> = patterns.iter().filter_map(|p| Regex::new(p).ok()).collect();
+
+    let texts = meeting
+        .location
+        .iter()
+        .chain(&meeting.description)
+        .chain(&meeting.description_links);
+    for text in texts {
+        let text = unwrap_redirects(text);
+        // Links in the order they appear, whichever pattern they match
+        let mut matches: Vec<regex::Match> = compiled
+            .iter()
+            .flat_map(|regex| regex.find_iter(&text))
+            .collect();
+        matches.sort_by_key(regex::Match::start);
+        for m in matches {
+            if !urls.iter().any(|u| u == m.as_str()) {
+                urls.push(m.as_str().to_string());
             }
         }
     }
 
-    None
+    urls
 }
 
 /// Get the physical location from a meeting (location that is not a URL)
-/// Returns None if the location is empty or appears to be just a URL
+/// Returns None if the location is empty or appears to be just a URL,
+/// including one hidden behind a redirect wrapper
 pub fn get_physical_location(meeting: &Meeting, url_patterns: &[String]) -> Option<String> {
-    let location = meeting.location.as_ref()?;
+    let location = unwrap_redirects(meeting.location.as_ref()?);
     let location = location.trim();
 
     if location.is_empty() {
@@ -1353,40 +2366,82 @@ pub fn get_physical_location(meeting: &Meeting, url_patterns: &[String]) -> Opti
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use chrono::{Datelike, Timelike};
 
-    // Tests for parse_display_name
-    #[test]
-    fn test_parse_display_name_simple() {
-        let data = "[Data Source]\nDisplayName=Work Calendar\nEnabled=true";
-        assert_eq!(parse_display_name(data), Some("Work Calendar".to_string()));
+    /// A calendar with a single hour-long event starting this time tomorrow.
+    pub(super) fn event_ics(uid: &str, summary: &str) -> String {
+        let start = Local::now() + chrono::Duration::days(1);
+        let end = start + chrono::Duration::hours(1);
+        format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:{uid}\r\n\
+             SUMMARY:{summary}\r\n\
+             DTSTART:{}\r\n\
+             DTEND:{}\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR",
+            start.format("%Y%m%dT%H%M%S"),
+            end.format("%Y%m%dT%H%M%S"),
+        )
+    }
+
+    /// An empty temporary directory that no other test, in this run or a
+    /// parallel one, will use.
+    pub(super) fn temp_dir(name: &str) -> PathBuf {
+        static NEXT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let n = NEXT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let dir =
+            std::env::temp_dir().join(format!("next-meeting-{name}-{}-{n}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
     }
 
+    // Tests for calendar color helpers
     #[test]
-    fn test_parse_display_name_with_sections() {
-        let data = "[Data Source]\nDisplayName=Personal\n[Calendar]\nBackendName=local";
-        assert_eq!(parse_display_name(data), Some("Personal".to_string()));
+    fn test_normalize_hex_color() {
+        assert_eq!(normalize_hex_color("#62A0EA"), Some("#62a0ea".to_string()));
+        assert_eq!(
+            normalize_hex_color("#FF2968FF"),
+            Some("#ff2968".to_string())
+        );
+        assert_eq!(normalize_hex_color("62a0ea\n"), Some("#62a0ea".to_string()));
+        assert_eq!(normalize_hex_color("red"), None);
+        assert_eq!(normalize_hex_color("#fff"), None);
     }
 
     #[test]
-    fn test_parse_display_name_missing() {
-        let data = "[Data Source]\nEnabled=true\n[Calendar]";
-        assert_eq!(parse_display_name(data), None);
+    fn test_fallback_color_is_stable() {
+        assert_eq!(fallback_color("ics:/a.ics"), fallback_color("ics:/a.ics"));
+        assert!(normalize_hex_color(&fallback_color("anything")).is_some());
     }
 
-    // Tests for parse_color
     #[test]
-    fn test_parse_color_hex() {
-        let data = "[Calendar]\nColor=#62a0ea\nBackendName=local";
-        assert_eq!(parse_color(data), Some("#62a0ea".to_string()));
+    fn test_parse_calendar_header() {
+        let contents = "BEGIN:VCALENDAR\r\n\
+             VERSION:2.0\r\n\
+             X-WR-CALNAME:On-call\r\n\
+             X-APPLE-CALENDAR-COLOR:#FF2968FF\r\n\
+             BEGIN:VEVENT\r\n\
+             X-WR-CALNAME:Not the calendar name\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR";
+        assert_eq!(
+            parse_calendar_header(contents),
+            CalendarHeader {
+                name: Some("On-call".to_string()),
+                color: Some("#ff2968".to_string()),
+            }
+        );
     }
 
     #[test]
-    fn test_parse_color_missing() {
-        let data = "[Calendar]\nBackendName=local";
-        assert_eq!(parse_color(data), None);
+    fn test_parse_calendar_header_missing() {
+        let contents = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nEND:VCALENDAR";
+        assert_eq!(parse_calendar_header(contents), CalendarHeader::default());
     }
 
     // Tests for parse_ical_datetime
@@ -1575,7 +2630,7 @@ mod tests {
     }
 
     // Helper to create a test meeting
-    fn make_test_meeting(location: Option<&str>, description: Option<&str>) -> Meeting {
+    pub(crate) fn make_test_meeting(location: Option<&str>, description: Option<&str>) -> Meeting {
         Meeting {
             uid: "test-uid".to_string(),
             title: "Test Meeting".to_string(),
@@ -1586,56 +2641,272 @@ mod tests {
             calendar_uid: "cal-uid".to_string(),
             is_all_day: false,
             attendance_status: AttendanceStatus::None,
+            status: EventStatus::Confirmed,
+            is_free: false,
+            organizer: None,
+            attendees: Vec::new(),
+            conference_urls: Vec::new(),
+            dial_ins: Vec::new(),
+            description_links: Vec::new(),
         }
     }
 
-    // Tests for extract_meeting_url
+    // Tests for extract_meeting_urls
+    #[test]
+    fn test_extract_meeting_url_from_location() {
+        let patterns = vec![r"https://meet\.google\.com/[a-z-]+".to_string()];
+        let meeting = make_test_meeting(Some("https://meet.google.com/abc-defg-hij"), None);
+        let url = extract_meeting_urls(&meeting, &patterns).first().cloned();
+        assert_eq!(
+            url,
+            Some("https://meet.google.com/abc-defg-hij".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_meeting_url_from_description() {
+        let patterns = vec![r"https://zoom\.us/j/\d+".to_string()];
+        let meeting = make_test_meeting(
+            Some("Conference Room A"),
+            Some("Join: https://zoom.us/j/123456789"),
+        );
+        let url = extract_meeting_urls(&meeting, &patterns).first().cloned();
+        assert_eq!(url, Some("https://zoom.us/j/123456789".to_string()));
+    }
+
+    #[test]
+    fn test_extract_meeting_url_location_priority() {
+        let patterns = vec![r"https://meet\.google\.com/[a-z-]+".to_string()];
+        let meeting = make_test_meeting(
+            Some("https://meet.google.com/loc-ation"),
+            Some("https://meet.google.com/desc-ription"),
+        );
+        let url = extract_meeting_urls(&meeting, &patterns).first().cloned();
+        // Location should be checked first
+        assert_eq!(url, Some("https://meet.google.com/loc-ation".to_string()));
+    }
+
+    #[test]
+    fn test_extract_meeting_url_no_match() {
+        let patterns = vec![r"https://meet\.google\.com/[a-z-]+".to_string()];
+        let meeting = make_test_meeting(Some("Conference Room B"), None);
+        assert!(
+            extract_meeting_urls(&meeting, &patterns)
+                .first()
+                .cloned()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_extract_meeting_url_empty_patterns() {
+        let patterns: Vec<String> = vec![];
+        let meeting = make_test_meeting(Some("https://meet.google.com/abc-def"), None);
+        assert!(
+            extract_meeting_urls(&meeting, &patterns)
+                .first()
+                .cloned()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_extract_meeting_url_conference_priority() {
+        let patterns = vec![r"https://meet\.google\.com/[a-z-]+".to_string()];
+        let mut meeting = make_test_meeting(Some("https://meet.google.com/loc-ation"), None);
+        meeting.conference_urls = vec!["https://teams.microsoft.com/l/meetup-join/abc".to_string()];
+        assert_eq!(
+            extract_meeting_urls(&meeting, &patterns).first().cloned(),
+            Some("https://teams.microsoft.com/l/meetup-join/abc".to_string())
+        );
+        // Conference links are used even without any patterns
+        assert_eq!(
+            extract_meeting_urls(&meeting, &[]).first().cloned(),
+            Some("https://teams.microsoft.com/l/meetup-join/abc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_meeting_urls_collects_all() {
+        let patterns = vec![
+            r"https://[a-z0-9]+\.zoom\.us/j/[0-9]+".to_string(),
+            r"https://meet\.google\.com/[a-z-]+".to_string(),
+        ];
+        let mut meeting = make_test_meeting(
+            Some("https://acme.zoom.us/j/123"),
+            Some(
+                "Zoom: https://acme.zoom.us/j/123\n\
+                 Backup: https://meet.google.com/abc-defg-hij\n\
+                 Breakout: https://acme.zoom.us/j/456",
+            ),
+        );
+        meeting.conference_urls = vec!["https://meet.google.com/abc-defg-hij".to_string()];
+        assert_eq!(
+            extract_meeting_urls(&meeting, &patterns),
+            vec![
+                "https://meet.google.com/abc-defg-hij".to_string(),
+                "https://acme.zoom.us/j/123".to_string(),
+                "https://acme.zoom.us/j/456".to_string(),
+            ]
+        );
+    }
+
     #[test]
-    fn test_extract_meeting_url_from_location() {
-        let patterns = vec![r"https://meet\.google\.com/[a-z-]+".to_string()];
-        let meeting = make_test_meeting(Some("https://meet.google.com/abc-defg-hij"), None);
-        let url = extract_meeting_url(&meeting, &patterns);
+    fn test_extract_meeting_urls_in_text_order() {
+        let patterns = vec![
+            r"https://meet\.google\.com/[a-z-]+".to_string(),
+            r"https://[a-z0-9]+\.zoom\.us/j/[0-9]+".to_string(),
+        ];
+        let meeting = make_test_meeting(
+            Some("Room 4"),
+            Some(
+                "Zoom: https://acme.zoom.us/j/123\n\
+                 Backup: https://meet.google.com/abc-defg-hij",
+            ),
+        );
+        // The Zoom link comes first in the text, though its pattern comes second
         assert_eq!(
-            url,
-            Some("https://meet.google.com/abc-defg-hij".to_string())
+            extract_meeting_urls(&meeting, &patterns),
+            vec![
+                "https://acme.zoom.us/j/123".to_string(),
+                "https://meet.google.com/abc-defg-hij".to_string(),
+            ]
         );
     }
 
     #[test]
-    fn test_extract_meeting_url_from_description() {
-        let patterns = vec![r"https://zoom\.us/j/\d+".to_string()];
+    fn test_extract_meeting_url_unwraps_safelinks() {
+        let patterns = vec![r"https://teams\.microsoft\.com/l/meetup-join/[^\s]+".to_string()];
         let meeting = make_test_meeting(
-            Some("Conference Room A"),
-            Some("Join: https://zoom.us/j/123456789"),
+            Some("Microsoft Teams Meeting"),
+            Some(
+                "Join the meeting now\n\
+                 https://nam12.safelinks.protection.outlook.com/?url=https%3A%2F%2Fteams.microsoft.com\
+                 %2Fl%2Fmeetup-join%2F19%253ameeting_abc%2540thread.v2%2F0&data=05%7C02%7C&sdata=xyz&reserved=0\n",
+            ),
+        );
+        assert_eq!(
+            extract_meeting_urls(&meeting, &patterns).first().cloned(),
+            Some(
+                "https://teams.microsoft.com/l/meetup-join/19%3ameeting_abc%40thread.v2/0"
+                    .to_string()
+            )
         );
-        let url = extract_meeting_url(&meeting, &patterns);
-        assert_eq!(url, Some("https://zoom.us/j/123456789".to_string()));
     }
 
     #[test]
-    fn test_extract_meeting_url_location_priority() {
+    fn test_extract_meeting_url_unwraps_google_redirect() {
         let patterns = vec![r"https://meet\.google\.com/[a-z-]+".to_string()];
         let meeting = make_test_meeting(
-            Some("https://meet.google.com/loc-ation"),
-            Some("https://meet.google.com/desc-ription"),
+            Some(
+                "https://www.google.com/url?q=https://meet.google.com/abc-defg-hij&sa=D&usg=AOvVaw",
+            ),
+            None,
+        );
+        assert_eq!(
+            extract_meeting_urls(&meeting, &patterns).first().cloned(),
+            Some("https://meet.google.com/abc-defg-hij".to_string())
+        );
+
+        // Wrappers nested in each other, including in conference properties
+        let mut meeting = make_test_meeting(None, None);
+        meeting.conference_urls = vec![
+            "https://eur01.safelinks.protection.outlook.com/?url=https%3A%2F%2Fwww.google.com\
+             %2Furl%3Fq%3Dhttps%253A%252F%252Facme.zoom.us%252Fj%252F123%26sa%3DD&data=05"
+                .to_string(),
+        ];
+        assert_eq!(
+            extract_meeting_urls(&meeting, &[]).first().cloned(),
+            Some("https://acme.zoom.us/j/123".to_string())
         );
-        let url = extract_meeting_url(&meeting, &patterns);
-        // Location should be checked first
-        assert_eq!(url, Some("https://meet.google.com/loc-ation".to_string()));
     }
 
     #[test]
-    fn test_extract_meeting_url_no_match() {
-        let patterns = vec![r"https://meet\.google\.com/[a-z-]+".to_string()];
-        let meeting = make_test_meeting(Some("Conference Room B"), None);
-        assert!(extract_meeting_url(&meeting, &patterns).is_none());
+    fn test_extract_meeting_url_from_html_description() {
+        let patterns = vec![r"https://teams\.microsoft\.com/l/meetup-join/[^\s]+".to_string()];
+        let ics = make_ics("teams", "Teams call", "20270601T100000", "20270601T110000").replace(
+            "SUMMARY",
+            "DESCRIPTION:Microsoft Teams meeting\\nJoin on your computer: Click here to j\r\n\
+             X-ALT-DESC;FMTTYPE=text/html:<html><head><style>p {margin:0;}</style></head>\r\n \
+             <body><p>Microsoft Teams meeting</p><p><b>Join on your computer</b>:&nbsp\r\n \
+             ;<a href=\"https://teams.microsoft.com/l/meetup-join/19%3ameeting_abc%40th\r\n \
+             read.v2/0\">Click here to join the meeting</a></p></body></html>\r\n\
+             SUMMARY",
+        );
+        let meetings = parse_and_dedup(&[&ics]);
+        let meeting = &meetings[0];
+
+        // The HTML text replaces the truncated plain description
+        assert_eq!(
+            meeting.description.as_deref(),
+            Some("Microsoft Teams meeting\nJoin on your computer: Click here to join the meeting")
+        );
+        assert_eq!(
+            extract_meeting_urls(meeting, &patterns).first().cloned(),
+            Some(
+                "https://teams.microsoft.com/l/meetup-join/19%3ameeting_abc%40thread.v2/0"
+                    .to_string()
+            )
+        );
     }
 
     #[test]
-    fn test_extract_meeting_url_empty_patterns() {
-        let patterns: Vec<String> = vec![];
-        let meeting = make_test_meeting(Some("https://meet.google.com/abc-def"), None);
-        assert!(extract_meeting_url(&meeting, &patterns).is_none());
+    fn test_unwrap_redirects_keeps_other_links() {
+        // Wrappers that don't carry a web link are left alone
+        let text = "See https://www.google.com/url?sa=D and https://example.com/page";
+        assert_eq!(unwrap_redirects(text), text);
+    }
+
+    #[test]
+    fn test_parse_conference_properties() {
+        let ics = make_ics("call", "Call", "20270601T100000", "20270601T110000").replace(
+            "SUMMARY",
+            "CONFERENCE;VALUE=URI;FEATURE=PHONE;LABEL=Dial in:tel:+1-555-0100\r\n\
+             CONFERENCE;VALUE=URI;FEATURE=AUDIO,PHONE:https://dial.example.com/123\r\n\
+             CONFERENCE;VALUE=URI;FEATURE=AUDIO,VIDEO:https://video.example.com/room\r\n\
+             X-GOOGLE-CONFERENCE:https://meet.google.com/abc-defg-hij\r\n\
+             X-MICROSOFT-SKYPETEAMSMEETINGURL:https://teams.microsoft.com/l/meetup-join/a;b\r\n\
+             SUMMARY",
+        );
+        let meetings = parse_and_dedup(&[&ics]);
+        assert_eq!(
+            meetings[0].conference_urls,
+            vec![
+                "https://video.example.com/room".to_string(),
+                "https://meet.google.com/abc-defg-hij".to_string(),
+                "https://teams.microsoft.com/l/meetup-join/a;b".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_dial_ins() {
+        let ics = make_ics("zoom", "Zoom call", "20270601T100000", "20270601T110000").replace(
+            "SUMMARY",
+            "CONFERENCE;VALUE=URI;FEATURE=PHONE;LABEL=UK:tel:+44-20-7946-0000,,555#\r\n\
+             DESCRIPTION:Join Zoom Meeting\\nhttps://zoom.us/j/85512345678\\n\\n\
+             Meeting ID: 855 1234 5678\\n\\nOne tap mobile\\n\
+             +16465588656,,85512345678#,,,,*123456# US (New York)\\n\\n\
+             Dial by your location\\n        +1 646 558 8656 US (New York)\\n\
+             \x20       +1 669 900 6833 US (San Jose)\\n\r\n\
+             SUMMARY",
+        );
+        let meetings = parse_and_dedup(&[&ics]);
+        let dial_ins = &meetings[0].dial_ins;
+        assert_eq!(dial_ins.len(), 3);
+
+        assert_eq!(dial_ins[0].number, "+44-20-7946-0000");
+        assert_eq!(dial_ins[0].pin.as_deref(), Some("555"));
+        assert_eq!(dial_ins[0].label.as_deref(), Some("UK"));
+
+        // The one-tap and listed copies of a number are merged
+        assert_eq!(dial_ins[1].number, "+16465588656");
+        assert_eq!(dial_ins[1].pin.as_deref(), Some("85512345678"));
+        assert_eq!(dial_ins[1].label.as_deref(), Some("US (New York)"));
+
+        // Numbers without their own PIN get the meeting ID
+        assert_eq!(dial_ins[2].label.as_deref(), Some("US (San Jose)"));
+        assert_eq!(dial_ins[2].tel_uri(), "tel:+16699006833,,85512345678#");
     }
 
     // Tests for get_physical_location
@@ -1656,6 +2927,16 @@ mod tests {
         assert!(get_physical_location(&meeting, &patterns).is_none());
     }
 
+    #[test]
+    fn test_get_physical_location_wrapped_url() {
+        let patterns = vec![r"https://meet\.google\.com/[a-z-]+".to_string()];
+        let meeting = make_test_meeting(
+            Some("https://www.google.com/url?q=https://meet.google.com/abc-defg-hij"),
+            None,
+        );
+        assert!(get_physical_location(&meeting, &patterns).is_none());
+    }
+
     #[test]
     fn test_get_physical_location_generic_url() {
         let patterns: Vec<String> = vec![];
@@ -2055,6 +3336,204 @@ mod tests {
         assert_eq!(eastern_day, Some(Tz::America__New_York)); // Both map to same tz
     }
 
+    /// Helper: an event in a custom zone, with its VTIMEZONE embedded the way
+    /// Exchange sends it.
+    fn make_vtimezone_ics(tzid: &str, observances: &str, dtstart: &str) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+             BEGIN:VTIMEZONE\r\n\
+             TZID:{tzid}\r\n\
+             {observances}\
+             END:VTIMEZONE\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:custom-tz\r\n\
+             SUMMARY:Custom zone meeting\r\n\
+             DTSTART;TZID=\"{tzid}\":{dtstart}\r\n\
+             DURATION:PT1H\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR"
+        )
+    }
+
+    /// Helper: the instant a UTC timestamp like `20270115T090000` denotes.
+    fn utc_instant(value: &str) -> DateTime<Local> {
+        chrono::Utc
+            .from_utc_datetime(&NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").unwrap())
+            .with_timezone(&Local)
+    }
+
+    const CUSTOM_CENTRAL_EUROPE: &str = "BEGIN:STANDARD\r\n\
+         DTSTART:16010101T030000\r\n\
+         TZOFFSETFROM:+0200\r\n\
+         TZOFFSETTO:+0100\r\n\
+         RRULE:FREQ=YEARLY;INTERVAL=1;BYDAY=-1SU;BYMONTH=10\r\n\
+         END:STANDARD\r\n\
+         BEGIN:DAYLIGHT\r\n\
+         DTSTART:16010101T020000\r\n\
+         TZOFFSETFROM:+0100\r\n\
+         TZOFFSETTO:+0200\r\n\
+         RRULE:FREQ=YEARLY;INTERVAL=1;BYDAY=-1SU;BYMONTH=3\r\n\
+         END:DAYLIGHT\r\n";
+
+    #[test]
+    fn test_tz_vtimezone_custom_european_rules() {
+        let winter = make_vtimezone_ics(
+            "Customized Time Zone",
+            CUSTOM_CENTRAL_EUROPE,
+            "20270115T100000",
+        );
+        let summer = make_vtimezone_ics(
+            "Customized Time Zone",
+            CUSTOM_CENTRAL_EUROPE,
+            "20270715T100000",
+        );
+        let meetings = parse_and_dedup(&[&winter]);
+        assert_eq!(meetings[0].start, utc_instant("20270115T090000"));
+        let meetings = parse_and_dedup(&[&summer]);
+        assert_eq!(meetings[0].start, utc_instant("20270715T080000"));
+    }
+
+    #[test]
+    fn test_tz_vtimezone_custom_us_rules() {
+        // Second Sunday of March to first Sunday of November, written with
+        // the BYMONTHDAY ranges some older servers use
+        let observances = "BEGIN:DAYLIGHT\r\n\
+             DTSTART:20070311T020000\r\n\
+             TZOFFSETFROM:-0500\r\n\
+             TZOFFSETTO:-0400\r\n\
+             RRULE:FREQ=YEARLY;BYMONTH=3;BYMONTHDAY=8,9,10,11,12,13,14;BYDAY=SU\r\n\
+             END:DAYLIGHT\r\n\
+             BEGIN:STANDARD\r\n\
+             DTSTART:20071104T020000\r\n\
+             TZOFFSETFROM:-0400\r\n\
+             TZOFFSETTO:-0500\r\n\
+             RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU\r\n\
+             END:STANDARD\r\n";
+
+        // March 13 is before the switch on the 14th in 2027, March 15 is after
+        let before = make_vtimezone_ics("Eastern (custom)", observances, "20270313T100000");
+        let after = make_vtimezone_ics("Eastern (custom)", observances, "20270315T100000");
+        let meetings = parse_and_dedup(&[&before]);
+        assert_eq!(meetings[0].start, utc_instant("20270313T150000"));
+        let meetings = parse_and_dedup(&[&after]);
+        assert_eq!(meetings[0].start, utc_instant("20270315T140000"));
+    }
+
+    #[test]
+    fn test_tz_vtimezone_custom_fixed_offset() {
+        let observances = "BEGIN:STANDARD\r\n\
+             DTSTART:16010101T000000\r\n\
+             TZOFFSETFROM:+0530\r\n\
+             TZOFFSETTO:+0530\r\n\
+             END:STANDARD\r\n";
+        let ics = make_vtimezone_ics("India custom", observances, "20270601T100000");
+        let meetings = parse_and_dedup(&[&ics]);
+        assert_eq!(meetings[0].start, utc_instant("20270601T043000"));
+    }
+
+    #[test]
+    fn test_tz_vtimezone_yearly_rule_dates() {
+        let rule = |month, day| YearlyRule { month, day };
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+
+        let last_sunday = rule(3, RuleDay::Weekday(-1, chrono::Weekday::Sun));
+        assert_eq!(last_sunday.date_in(2027), date(2027, 3, 28));
+        assert_eq!(last_sunday.date_in(2026), date(2026, 3, 29));
+
+        let second_sunday = rule(3, RuleDay::Weekday(2, chrono::Weekday::Sun));
+        assert_eq!(second_sunday.date_in(2027), date(2027, 3, 14));
+
+        // A fifth Friday that doesn't exist falls back to the last one
+        let fifth_friday = rule(2, RuleDay::Weekday(5, chrono::Weekday::Fri));
+        assert_eq!(fifth_friday.date_in(2027), date(2027, 2, 26));
+
+        assert_eq!(
+            rule(4, RuleDay::MonthDay(1)).date_in(2027),
+            date(2027, 4, 1)
+        );
+        assert_eq!(
+            rule(2, RuleDay::MonthDay(-1)).date_in(2028),
+            date(2028, 2, 29)
+        );
+    }
+
+    /// Switches on the first Monday of May and of September, which no IANA
+    /// zone does.
+    const MADE_UP_RULES: &str = "BEGIN:STANDARD\r\n\
+         DTSTART:16010101T030000\r\n\
+         TZOFFSETFROM:+0200\r\n\
+         TZOFFSETTO:+0100\r\n\
+         RRULE:FREQ=YEARLY;BYDAY=1MO;BYMONTH=9\r\n\
+         END:STANDARD\r\n\
+         BEGIN:DAYLIGHT\r\n\
+         DTSTART:16010101T020000\r\n\
+         TZOFFSETFROM:+0100\r\n\
+         TZOFFSETTO:+0200\r\n\
+         RRULE:FREQ=YEARLY;BYDAY=1MO;BYMONTH=5\r\n\
+         END:DAYLIGHT\r\n";
+
+    #[test]
+    fn test_tz_vtimezone_unknown_rules_keep_tzid_unresolved() {
+        // No IANA zone switches on these dates, so the zone isn't guessed
+        let ics = make_vtimezone_ics("Made-up Time", MADE_UP_RULES, "20270601T100000");
+        let mut calendar = ICalendar::parse(&ics).unwrap();
+        let mut cache = HashMap::new();
+        let custom = resolve_custom_timezones(&mut calendar, 2027, Tz::UTC, &mut cache);
+        assert!(custom.zones.contains_key("Made-up Time"));
+        assert_eq!(cache.values().collect::<Vec<_>>(), vec![&None]);
+        assert!(
+            calendar
+                .build_tz_resolver()
+                .resolve("Made-up Time")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_tz_vtimezone_unknown_rules_use_observance_offsets() {
+        // Before the May switch the zone is on +01:00, after it on +02:00
+        let april = make_vtimezone_ics("Made-up Time", MADE_UP_RULES, "20270402T100000");
+        let june = make_vtimezone_ics("Made-up Time", MADE_UP_RULES, "20270601T100000");
+        let meetings = parse_and_dedup(&[&april]);
+        assert_eq!(meetings[0].start, utc_instant("20270402T090000"));
+        assert_eq!(meetings[0].end, utc_instant("20270402T100000"));
+        let meetings = parse_and_dedup(&[&june]);
+        assert_eq!(meetings[0].start, utc_instant("20270601T080000"));
+    }
+
+    #[test]
+    fn test_tz_vtimezone_same_tzid_different_rules() {
+        // Exchange names every custom zone the same, so matches can't be
+        // shared by name
+        let us_rules = "BEGIN:DAYLIGHT\r\n\
+             DTSTART:20070311T020000\r\n\
+             TZOFFSETFROM:-0500\r\n\
+             TZOFFSETTO:-0400\r\n\
+             RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU\r\n\
+             END:DAYLIGHT\r\n\
+             BEGIN:STANDARD\r\n\
+             DTSTART:20071104T020000\r\n\
+             TZOFFSETFROM:-0400\r\n\
+             TZOFFSETTO:-0500\r\n\
+             RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU\r\n\
+             END:STANDARD\r\n";
+        let europe = make_vtimezone_ics(
+            "Customized Time Zone",
+            CUSTOM_CENTRAL_EUROPE,
+            "20270115T100000",
+        );
+        let us = make_vtimezone_ics("Customized Time Zone", us_rules, "20270115T100000");
+        let meetings = parse_and_dedup(&[&europe, &us]);
+        let starts: Vec<DateTime<Local>> = meetings.iter().map(|m| m.start).collect();
+        assert_eq!(
+            starts,
+            vec![
+                utc_instant("20270115T090000"),
+                utc_instant("20270115T150000")
+            ]
+        );
+    }
+
     // Integration tests: verify parse_ical_datetime works correctly with
     // various timezone formats, including edge cases.
     #[test]
@@ -2143,23 +3622,29 @@ mod tests {
 
     /// Helper: parse ICS objects and return deduplicated meetings with a wide time window.
     fn parse_and_dedup(ics_objects: &[&str]) -> Vec<Meeting> {
-        let now = Local::now();
-        // Wide window: from 1 year ago to catch all test events
-        let query_start = now - chrono::Duration::days(365);
         let user_emails: Vec<String> = vec![];
         let mut all_meetings = Vec::new();
         let ics_strings: Vec<String> = ics_objects.iter().map(|s| (*s).to_string()).collect();
         parse_ics_objects(
             &ics_strings,
             "test-calendar",
-            now,
-            query_start,
+            &wide_window(),
             &user_emails,
             &mut all_meetings,
         );
         dedup_and_sort_meetings(all_meetings, 1000)
     }
 
+    /// Helper: a window from 1 year ago to 10 years ahead, to catch all test events.
+    fn wide_window() -> FetchWindow {
+        let now = Local::now();
+        FetchWindow {
+            start: now - chrono::Duration::days(365),
+            end: now + chrono::Duration::days(3650),
+            ..FetchWindow::new(now, QueryWindow::default())
+        }
+    }
+
     // Test helpers use TZID=UTC to ensure consistent behavior across systems.
     // Real EDS data uses DTSTART;TZID=<timezone>:<time> format.
     // Using UTC avoids timezone conversion differences between calcard's
@@ -2369,6 +3854,260 @@ mod tests {
         assert!(titles.contains(&"Weekly")); // Week 2 keeps original
     }
 
+    #[test]
+    fn test_recurrence_id_override_moved_time_replaces_master_instance() {
+        // The override moves the first instance from 10:00 to 14:00. The
+        // master's 10:00 copy must not survive as a phantom meeting.
+        let master = make_recurring_ics(
+            "standup",
+            "Standup",
+            "20270601T100000",
+            "20270601T103000",
+            "FREQ=WEEKLY;COUNT=2",
+        );
+        let moved = make_override_ics(
+            "standup",
+            "Standup (Moved)",
+            "20270601T140000",
+            "20270601T143000",
+            "20270601T100000",
+        );
+
+        for objects in [[&master, &moved], [&moved, &master]] {
+            let meetings = parse_and_dedup(&[objects[0].as_str(), objects[1].as_str()]);
+            let starts: Vec<String> = meetings
+                .iter()
+                .map(|m| {
+                    m.start
+                        .with_timezone(&chrono::Utc)
+                        .format("%Y%m%dT%H%M")
+                        .to_string()
+                })
+                .collect();
+            assert_eq!(starts, vec!["20270601T1400", "20270608T1000"]);
+            assert_eq!(meetings[0].title, "Standup (Moved)");
+            assert_eq!(meetings[1].title, "Standup");
+        }
+    }
+
+    #[test]
+    fn test_recurrence_id_override_moved_into_the_past() {
+        // The override moves a future instance to a time that has already
+        // passed. It's filtered out, and the master's copy at the old time
+        // must not stand in for it.
+        let at = |days: i64, time: &str| {
+            let day = chrono::Utc::now() + chrono::Duration::days(days);
+            format!("{}T{time}", day.format("%Y%m%d"))
+        };
+        let master = make_recurring_ics(
+            "review",
+            "Review",
+            &at(-2, "100000"),
+            &at(-2, "103000"),
+            "FREQ=DAILY;COUNT=5",
+        );
+        let moved = make_override_ics(
+            "review",
+            "Review (Moved)",
+            &at(-2, "150000"),
+            &at(-2, "153000"),
+            &at(2, "100000"),
+        );
+
+        for objects in [[&master, &moved], [&moved, &master]] {
+            let meetings = parse_and_dedup(&[objects[0].as_str(), objects[1].as_str()]);
+            let starts: Vec<String> = meetings
+                .iter()
+                .map(|m| {
+                    m.start
+                        .with_timezone(&chrono::Utc)
+                        .format("%Y%m%dT%H%M%S")
+                        .to_string()
+                })
+                .collect();
+            assert!(starts.contains(&at(1, "100000")));
+            assert!(!starts.contains(&at(2, "100000")));
+            assert!(meetings.iter().all(|m| m.title == "Review"));
+        }
+    }
+
+    #[test]
+    fn test_recurrence_id_this_and_future_moves_later_instances() {
+        let master = make_recurring_ics(
+            "sync",
+            "Weekly Sync",
+            "20270601T100000",
+            "20270601T110000",
+            "FREQ=WEEKLY;COUNT=3",
+        );
+        let this_and_future = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:sync\r\n\
+             SUMMARY:Weekly Sync (Afternoon)\r\n\
+             DTSTART;TZID=UTC:20270608T150000\r\n\
+             DTEND;TZID=UTC:20270608T160000\r\n\
+             RECURRENCE-ID;RANGE=THISANDFUTURE;TZID=UTC:20270608T100000\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR";
+
+        let meetings = parse_and_dedup(&[&master, this_and_future]);
+        let instances: Vec<(String, &str)> = meetings
+            .iter()
+            .map(|m| {
+                (
+                    m.start
+                        .with_timezone(&chrono::Utc)
+                        .format("%Y%m%dT%H%M")
+                        .to_string(),
+                    m.title.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            instances,
+            vec![
+                ("20270601T1000".to_string(), "Weekly Sync"),
+                ("20270608T1500".to_string(), "Weekly Sync (Afternoon)"),
+                ("20270615T1500".to_string(), "Weekly Sync (Afternoon)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recurrence_id_other_range_overrides_one_instance() {
+        let master = make_recurring_ics(
+            "sync",
+            "Weekly Sync",
+            "20270601T100000",
+            "20270601T110000",
+            "FREQ=WEEKLY;COUNT=3",
+        );
+        // THISANDPRIOR was dropped from the spec, so only this instance moves
+        let this_and_prior = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:sync\r\n\
+             SUMMARY:Weekly Sync (Afternoon)\r\n\
+             DTSTART;TZID=UTC:20270608T150000\r\n\
+             DTEND;TZID=UTC:20270608T160000\r\n\
+             RECURRENCE-ID;RANGE=THISANDPRIOR;TZID=UTC:20270608T100000\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR";
+
+        let meetings = parse_and_dedup(&[&master, this_and_prior]);
+        let instances: Vec<(String, &str)> = meetings
+            .iter()
+            .map(|m| {
+                (
+                    m.start
+                        .with_timezone(&chrono::Utc)
+                        .format("%Y%m%dT%H%M")
+                        .to_string(),
+                    m.title.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            instances,
+            vec![
+                ("20270601T1000".to_string(), "Weekly Sync"),
+                ("20270608T1500".to_string(), "Weekly Sync (Afternoon)"),
+                ("20270615T1000".to_string(), "Weekly Sync"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_event_status_and_transparency() {
+        let cancelled = make_ics("gone", "Offsite", "20270601T100000", "20270601T110000")
+            .replace("SUMMARY", "STATUS:CANCELLED\r\nSUMMARY");
+        let free = make_ics("focus", "Focus time", "20270602T100000", "20270602T110000").replace(
+            "SUMMARY",
+            "TRANSP:TRANSPARENT\r\nSTATUS:TENTATIVE\r\nSUMMARY",
+        );
+        let busy = make_ics("sync", "Sync", "20270603T100000", "20270603T110000")
+            .replace("SUMMARY", "TRANSP:OPAQUE\r\nSUMMARY");
+
+        let meetings = parse_and_dedup(&[&cancelled, &free, &busy]);
+        assert_eq!(meetings.len(), 3);
+        assert_eq!(meetings[0].status, EventStatus::Cancelled);
+        assert!(!meetings[0].is_free);
+        assert_eq!(meetings[1].status, EventStatus::Tentative);
+        assert!(meetings[1].is_free);
+        assert_eq!(meetings[2].status, EventStatus::Confirmed);
+        assert!(!meetings[2].is_free);
+    }
+
+    #[test]
+    fn test_parse_organizer_and_attendees() {
+        let ics = make_ics(
+            "review",
+            "Design review",
+            "20270601T100000",
+            "20270601T110000",
+        )
+        .replace(
+            "SUMMARY",
+            "ORGANIZER;CN=Ana Lima:mailto:ana@example.com\r\n\
+                 ATTENDEE;CN=Ana Lima;ROLE=CHAIR;PARTSTAT=ACCEPTED:mailto:ana@example.com\r\n\
+                 ATTENDEE;ROLE=OPT-PARTICIPANT;PARTSTAT=DECLINED:MAILTO:Bo@Example.com\r\n\
+                 ATTENDEE;CN=Room 4;CUTYPE=ROOM:mailto:room4@example.com\r\n\
+                 SUMMARY",
+        );
+        let mut all_meetings = Vec::new();
+        parse_ics_objects(
+            &[ics],
+            "test-calendar",
+            &wide_window(),
+            &["bo@example.com".to_string()],
+            &mut all_meetings,
+        );
+        let meeting = &all_meetings[0].1;
+
+        let organizer = meeting.organizer.as_ref().unwrap();
+        assert_eq!(organizer.display_name(), "Ana Lima");
+        assert_eq!(organizer.email.as_deref(), Some("ana@example.com"));
+
+        assert_eq!(meeting.attendees.len(), 3);
+        assert_eq!(meeting.attendees[0].role, ParticipantRole::Chair);
+        assert_eq!(meeting.attendees[0].status, AttendanceStatus::Accepted);
+        assert_eq!(meeting.attendees[1].display_name(), "Bo@Example.com");
+        assert_eq!(meeting.attendees[1].role, ParticipantRole::Optional);
+        assert_eq!(meeting.attendees[2].kind, ParticipantKind::Room);
+        assert_eq!(meeting.attendees[2].status, AttendanceStatus::None);
+
+        // The user is found among the attendees regardless of case
+        assert_eq!(meeting.attendance_status, AttendanceStatus::Declined);
+    }
+
+    #[test]
+    fn test_cancelled_override_replaces_master_instance() {
+        let master = make_recurring_ics(
+            "weekly",
+            "Weekly",
+            "20270601T120000",
+            "20270601T130000",
+            "FREQ=WEEKLY;COUNT=2",
+        );
+        let cancelled = make_override_ics(
+            "weekly",
+            "Weekly",
+            "20270601T120000",
+            "20270601T130000",
+            "20270601T120000",
+        )
+        .replace("RECURRENCE-ID", "STATUS:CANCELLED\r\nRECURRENCE-ID");
+
+        // The cancelled override wins over the master's instance...
+        let mut meetings = parse_and_dedup(&[&master, &cancelled]);
+        assert_eq!(meetings.len(), 2);
+        assert_eq!(meetings[0].status, EventStatus::Cancelled);
+
+        // ...so dropping cancelled instances leaves only the second week
+        meetings.retain(|m| m.status != EventStatus::Cancelled);
+        assert_eq!(meetings.len(), 1);
+        assert_eq!(meetings[0].start.format("%Y%m%d").to_string(), "20270608");
+    }
+
     #[test]
     fn test_dedup_same_uid_same_time_non_recurring() {
         // Two non-recurring events with same UID and same time (shouldn't happen
@@ -2415,6 +4154,13 @@ mod tests {
                 calendar_uid: "cal".to_string(),
                 is_all_day: false,
                 attendance_status: AttendanceStatus::None,
+                status: EventStatus::Confirmed,
+                is_free: false,
+                organizer: None,
+                attendees: Vec::new(),
+                conference_urls: Vec::new(),
+                dial_ins: Vec::new(),
+                description_links: Vec::new(),
             },
         );
         let m2 = (
@@ -2429,6 +4175,13 @@ mod tests {
                 calendar_uid: "cal".to_string(),
                 is_all_day: false,
                 attendance_status: AttendanceStatus::None,
+                status: EventStatus::Confirmed,
+                is_free: false,
+                organizer: None,
+                attendees: Vec::new(),
+                conference_urls: Vec::new(),
+                dial_ins: Vec::new(),
+                description_links: Vec::new(),
             },
         );
         let m3 = (
@@ -2443,6 +4196,13 @@ mod tests {
                 calendar_uid: "cal".to_string(),
                 is_all_day: false,
                 attendance_status: AttendanceStatus::None,
+                status: EventStatus::Confirmed,
+                is_free: false,
+                organizer: None,
+                attendees: Vec::new(),
+                conference_urls: Vec::new(),
+                dial_ins: Vec::new(),
+                description_links: Vec::new(),
             },
         );
 
@@ -2470,6 +4230,13 @@ mod tests {
                 calendar_uid: "cal".to_string(),
                 is_all_day: false,
                 attendance_status: AttendanceStatus::None,
+                status: EventStatus::Confirmed,
+                is_free: false,
+                organizer: None,
+                attendees: Vec::new(),
+                conference_urls: Vec::new(),
+                dial_ins: Vec::new(),
+                description_links: Vec::new(),
             },
         );
         let override_m = (
@@ -2484,6 +4251,13 @@ mod tests {
                 calendar_uid: "cal".to_string(),
                 is_all_day: false,
                 attendance_status: AttendanceStatus::None,
+                status: EventStatus::Confirmed,
+                is_free: false,
+                organizer: None,
+                attendees: Vec::new(),
+                conference_urls: Vec::new(),
+                dial_ins: Vec::new(),
+                description_links: Vec::new(),
             },
         );
 
@@ -2517,6 +4291,13 @@ mod tests {
                 calendar_uid: "cal".to_string(),
                 is_all_day: false,
                 attendance_status: AttendanceStatus::None,
+                status: EventStatus::Confirmed,
+                is_free: false,
+                organizer: None,
+                attendees: Vec::new(),
+                conference_urls: Vec::new(),
+                dial_ins: Vec::new(),
+                description_links: Vec::new(),
             },
         );
         let non_override = (
@@ -2531,6 +4312,13 @@ mod tests {
                 calendar_uid: "cal".to_string(),
                 is_all_day: false,
                 attendance_status: AttendanceStatus::None,
+                status: EventStatus::Confirmed,
+                is_free: false,
+                organizer: None,
+                attendees: Vec::new(),
+                conference_urls: Vec::new(),
+                dial_ins: Vec::new(),
+                description_links: Vec::new(),
             },
         );
 
@@ -2641,4 +4429,176 @@ mod tests {
         assert_eq!(meetings.len(), 1);
         assert_eq!(meetings[0].title, "Untitled Event");
     }
+
+    #[test]
+    fn test_source_fetch_counts_invalid_events() {
+        let valid = make_ics("valid1", "Valid", "20270601T100000", "20270601T110000");
+        let objects = vec![valid, "not a calendar".to_string()];
+        let mut fetch = SourceFetch::default();
+        fetch.add_calendar(&objects, "cal", &wide_window(), &[]);
+        assert_eq!(fetch.meetings.len(), 1);
+        assert_eq!(
+            fetch.errors.get("cal"),
+            Some(&CalendarError::InvalidEvents(1))
+        );
+        assert!(!fetch.is_failure());
+
+        let mut failed = SourceFetch::default();
+        failed
+            .errors
+            .insert("cal".to_string(), CalendarError::TimedOut);
+        assert!(failed.is_failure());
+    }
+
+    #[test]
+    fn test_parse_stats_counts_filtered_instances() {
+        let past = make_ics("past1", "Past", "20200601T100000", "20200601T110000");
+        let inverted = make_ics("bad1", "Inverted", "20270601T110000", "20270601T100000");
+        let future = make_ics("future1", "Future", "20270601T100000", "20270601T110000");
+        let mut meetings = Vec::new();
+        let window = FetchWindow {
+            start: Local::now(),
+            ..wide_window()
+        };
+        let stats = parse_ics_objects(
+            &[past, inverted, future],
+            "cal",
+            &window,
+            &[],
+            &mut meetings,
+        );
+        assert_eq!(meetings.len(), 1);
+        assert_eq!(stats.instances, 3);
+        assert_eq!(stats.filtered.get(&FilterReason::Past), Some(&1));
+        assert_eq!(stats.filtered.get(&FilterReason::InvalidTimes), Some(&1));
+    }
+
+    #[test]
+    fn test_look_behind_window_keeps_long_running_meetings() {
+        let now = Local::now();
+        let utc = |t: DateTime<Local>| t.with_timezone(&chrono::Utc).format("%Y%m%dT%H%M%S");
+        let workshop = make_ics(
+            "workshop",
+            "Workshop",
+            &utc(now - chrono::Duration::minutes(90)).to_string(),
+            &utc(now + chrono::Duration::minutes(30)).to_string(),
+        );
+
+        let parse = |window: QueryWindow| {
+            let mut meetings = Vec::new();
+            parse_ics_objects(
+                std::slice::from_ref(&workshop),
+                "cal",
+                &FetchWindow::new(now, window),
+                &[],
+                &mut meetings,
+            );
+            meetings.len()
+        };
+
+        assert_eq!(parse(QueryWindow::default()), 0);
+        assert_eq!(
+            parse(QueryWindow {
+                look_behind: chrono::Duration::hours(2),
+                ..QueryWindow::default()
+            }),
+            1
+        );
+    }
+
+    /// Helper: recurring series that started in 2015 and never end, the
+    /// shape that made expansion slow.
+    fn long_running_series_fixture() -> Vec<String> {
+        [
+            "FREQ=DAILY",
+            "FREQ=DAILY;INTERVAL=3",
+            "FREQ=WEEKLY;BYDAY=MO,WE,FR",
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU",
+            "FREQ=MONTHLY;BYDAY=2TH",
+            "FREQ=MONTHLY;BYMONTHDAY=15",
+            "FREQ=YEARLY",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, rrule)| {
+            make_recurring_ics(
+                &format!("series{i}"),
+                &format!("Series {i}"),
+                "20150105T093000",
+                "20150105T100000",
+                rrule,
+            )
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_bounded_expansion_matches_full_expansion() {
+        let window = FetchWindow::new(Local::now(), QueryWindow::default());
+        for ics in long_running_series_fixture() {
+            let mut meetings = Vec::new();
+            let stats = parse_ics_objects(
+                std::slice::from_ref(&ics),
+                "cal",
+                &window,
+                &[],
+                &mut meetings,
+            );
+            let bounded: Vec<DateTime<Local>> = meetings
+                .iter()
+                .map(|(_, m)| m.start)
+                .filter(|start| *start < window.end)
+                .collect();
+
+            // Expand the whole series from 2015 and filter it the old way
+            let calendar = ICalendar::parse(&ics).unwrap();
+            let full: Vec<DateTime<Local>> = calendar
+                .expand_dates(window.local_tz, 10_000)
+                .events
+                .iter()
+                .map(|e| {
+                    let start = e.start.with_timezone(&Local);
+                    let end = match e.end {
+                        TimeOrDelta::Time(t) => t.with_timezone(&Local),
+                        TimeOrDelta::Delta(d) => start + d,
+                    };
+                    (start, end)
+                })
+                .filter(|(start, end)| {
+                    should_include_meeting(*start, *end, window.now, window.start)
+                        && *start < window.end
+                })
+                .map(|(start, _)| start)
+                .collect();
+
+            assert_eq!(bounded, full, "{ics}");
+            assert!(
+                stats.instances <= 40,
+                "{} instances for {ics}",
+                stats.instances
+            );
+        }
+    }
+
+    /// Times parsing of the long-running series fixture.
+    /// Run with `just bench`.
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_parse_long_running_series() {
+        let objects: Vec<String> = std::iter::repeat_n(long_running_series_fixture(), 30)
+            .flatten()
+            .collect();
+        let window = FetchWindow::new(Local::now(), QueryWindow::default());
+        let runs: u32 = 10;
+        let started = std::time::Instant::now();
+        for _ in 0..runs {
+            let mut meetings = Vec::new();
+            parse_ics_objects(&objects, "cal", &window, &[], &mut meetings);
+        }
+        println!(
+            "parsed {} objects in {:?} per fetch",
+            objects.len(),
+            started.elapsed() / runs
+        );
+    }
 }
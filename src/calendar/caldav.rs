@@ -0,0 +1,848 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//
+// Calendar source that talks CalDAV to a server directly, for systems without EDS.
+
+use super::http::{Credentials, Request, Response};
+use super::{
+    CalendarError, CalendarInfo, CalendarSource, FetchWindow, MeetingQuery, QueryWindow,
+    SourceFetch, fallback_color, normalize_hex_color, secrets,
+};
+use crate::config::CalDavAccount;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use futures_util::future::BoxFuture;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Prefix for calendar UIDs from this source, so they can't collide with EDS UIDs.
+const UID_PREFIX: &str = "caldav:";
+
+/// Secret Service `application` attribute that CalDAV passwords are stored under.
+const SECRET_APPLICATION: &str = "com.dangrover.next-meeting-app";
+
+/// Minimum time between syncs with the server, unless a refresh is requested.
+const SYNC_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+const DAV: &str = "DAV:";
+const CALDAV: &str = "urn:ietf:params:xml:ns:caldav";
+const APPLE_ICAL: &str = "http://apple.com/ns/ical/";
+
+/// Properties requested when listing calendars.
+const CALENDAR_PROPS: &str = "<D:resourcetype/><D:displayname/>\
+     <C:supported-calendar-component-set/><A:calendar-color/>";
+
+/// Calendar source for one CalDAV account.
+///
+/// Calendars are discovered with `PROPFIND` (principal, then calendar home),
+/// fetched once with a `calendar-query` over the applet's time window, and
+/// kept up to date with `sync-collection` reports. The password is looked up
+/// in the Secret Service under the account's URL and username.
+#[derive(Debug)]
+pub struct CalDavSource {
+    account: CalDavAccount,
+    /// Credentials from the Secret Service, looked up on first use.
+    credentials: Mutex<Option<Option<Credentials>>>,
+    state: Mutex<SyncState>,
+}
+
+/// What we know about the server, kept between fetches.
+#[derive(Debug, Default)]
+struct SyncState {
+    /// Discovered calendars, or `None` if discovery hasn't run (or must run again).
+    calendars: Option<Vec<RemoteCalendar>>,
+    /// Synced contents of each calendar, keyed by collection URL.
+    collections: HashMap<String, Collection>,
+}
+
+/// A calendar collection found on the server.
+#[derive(Debug, Clone)]
+struct RemoteCalendar {
+    url: String,
+    display_name: String,
+    color: Option<String>,
+}
+
+/// The synced contents of one calendar collection.
+#[derive(Debug)]
+struct Collection {
+    /// Token for the next incremental `sync-collection` report.
+    sync_token: Option<String>,
+    /// Calendar objects keyed by their href.
+    objects: HashMap<String, String>,
+    /// Local day the full time-window query ran on. The window moves with the
+    /// clock, so the full query is repeated daily to pick up events entering it.
+    window_day: NaiveDate,
+    /// The window the full query covered; a different one needs a new query.
+    window: QueryWindow,
+    /// When the collection was last synced, or `None` to sync on the next fetch.
+    synced_at: Option<Instant>,
+    /// Wall-clock time of the last successful sync, for the Calendars page.
+    last_synced: DateTime<Utc>,
+}
+
+impl CalDavSource {
+    #[must_use]
+    pub fn new(account: CalDavAccount) -> Self {
+        Self {
+            account,
+            credentials: Mutex::default(),
+            state: Mutex::default(),
+        }
+    }
+
+    /// Credentials for the account, looked up in the Secret Service once.
+    /// Requests are sent without authentication if no password is stored.
+    async fn credentials(&self) -> Option<Credentials> {
+        let mut cached = self.credentials.lock().await;
+        if cached.is_none() {
+            let username = self.account.username.trim();
+            let password = if username.is_empty() {
+                None
+            } else {
+                let attributes = HashMap::from([
+                    ("application", SECRET_APPLICATION),
+                    ("url", self.account.url.trim()),
+                    ("username", username),
+                ]);
+                secrets::lookup_password(&attributes).await
+            };
+            *cached = Some(password.map(|password| Credentials {
+                username: username.to_string(),
+                password,
+            }));
+        }
+        cached.clone().flatten()
+    }
+
+    /// Send a WebDAV request with an XML body and parse the multistatus reply.
+    async fn multistatus(
+        &self,
+        method: &'static str,
+        url: &str,
+        depth: &str,
+        body: String,
+    ) -> Result<(Multistatus, Response), CalendarError> {
+        let response = Request::new(method, url)
+            .header("Depth", depth)
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(body)
+            .credentials(self.credentials().await)
+            .send()
+            .await
+            .ok_or(CalendarError::Unreachable)?;
+        match response.status {
+            207 => {}
+            401 | 403 => return Err(CalendarError::AuthFailed),
+            status => return Err(CalendarError::HttpStatus(status)),
+        }
+        let multistatus = parse_multistatus(&response.body).ok_or(CalendarError::InvalidData)?;
+        Ok((multistatus, response))
+    }
+
+    /// `PROPFIND` the given properties, with hrefs resolved to absolute URLs.
+    async fn propfind(
+        &self,
+        url: &str,
+        depth: &str,
+        props: &str,
+    ) -> Result<Vec<DavResponse>, CalendarError> {
+        let body = format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+             <D:propfind xmlns:D=\"{DAV}\" xmlns:C=\"{CALDAV}\" xmlns:A=\"{APPLE_ICAL}\">\
+             <D:prop>{props}</D:prop></D:propfind>"
+        );
+        let (multistatus, response) = self.multistatus("PROPFIND", url, depth, body).await?;
+        Ok(multistatus
+            .responses
+            .into_iter()
+            .filter_map(|r| r.resolved(&response.url))
+            .collect())
+    }
+
+    /// Find the account's calendars: follow the principal to the calendar
+    /// home and list its event calendars. The configured URL may also point
+    /// straight at a calendar home or a single calendar.
+    async fn discover_calendars(&self) -> Result<Vec<RemoteCalendar>, CalendarError> {
+        let start = account_url(&self.account.url).ok_or(CalendarError::InvalidUrl)?;
+        let props = "<D:current-user-principal/><D:resourcetype/><C:calendar-home-set/>";
+
+        // Servers that only answer on the well-known path redirect from there.
+        // If that fails too, the first error says more about what's wrong.
+        let root = match self.propfind(&start, "0", props).await {
+            Ok(root) => root,
+            Err(error) => {
+                let well_known = url::Url::parse(&start)
+                    .and_then(|url| url.join("/.well-known/caldav"))
+                    .map_err(|_| CalendarError::InvalidUrl)?;
+                self.propfind(well_known.as_str(), "0", props)
+                    .await
+                    .map_err(|_| error)?
+            }
+        };
+        let root = root.into_iter().next().ok_or(CalendarError::InvalidData)?;
+
+        if root.is_calendar {
+            let calendar = self.propfind(&root.href, "0", CALENDAR_PROPS).await?;
+            return Ok(calendar.iter().filter_map(remote_calendar).collect());
+        }
+
+        let home = match (&root.calendar_home_set, &root.current_user_principal) {
+            (Some(home), _) => home.clone(),
+            (None, Some(principal)) => self
+                .propfind(principal, "0", "<C:calendar-home-set/>")
+                .await
+                .ok()
+                .and_then(|r| r.into_iter().next())
+                .and_then(|r| r.calendar_home_set)
+                .unwrap_or_else(|| root.href.clone()),
+            (None, None) => root.href.clone(),
+        };
+
+        let listing = self.propfind(&home, "1", CALENDAR_PROPS).await?;
+        Ok(listing.iter().filter_map(remote_calendar).collect())
+    }
+
+    /// The account's calendars, discovering them if needed.
+    async fn calendars(&self) -> Result<Vec<RemoteCalendar>, CalendarError> {
+        if let Some(calendars) = &self.state.lock().await.calendars {
+            return Ok(calendars.clone());
+        }
+
+        let discovered = self.discover_calendars().await;
+        let mut state = self.state.lock().await;
+        // Only remember successful discovery, so a flaky network is retried
+        if let Ok(calendars) = &discovered {
+            state.calendars = Some(calendars.clone());
+        }
+        discovered
+    }
+
+    /// Bring a calendar's objects up to date with the server.
+    ///
+    /// Uses an incremental `sync-collection` report when we hold a sync
+    /// token, and falls back to a full time-window query otherwise. On any
+    /// failure the previously synced objects are kept.
+    ///
+    /// The state is only locked to read and store results, never across a
+    /// request, so a slow server doesn't hold up the account's other calendars.
+    async fn sync_calendar(
+        &self,
+        url: &str,
+        now: DateTime<Local>,
+        window: QueryWindow,
+    ) -> Result<(), CalendarError> {
+        let today = now.date_naive();
+
+        let (fresh, token) = match self
+            .state
+            .lock()
+            .await
+            .collections
+            .get(url)
+            .filter(|c| c.window == window)
+        {
+            Some(c) => (
+                c.synced_at.is_some_and(|t| t.elapsed() < SYNC_INTERVAL),
+                c.sync_token.clone().filter(|_| c.window_day == today),
+            ),
+            None => (false, None),
+        };
+        if fresh {
+            return Ok(());
+        }
+
+        if let Some(token) = token
+            && let Ok((changes, new_token)) = self.sync_changes(url, &token).await
+        {
+            let mut state = self.state.lock().await;
+            // If another sync finished first, its objects are already newer
+            if let Some(collection) = state.collections.get_mut(url)
+                && collection.sync_token.as_deref() == Some(token.as_str())
+            {
+                for (href, data) in changes {
+                    match data {
+                        Some(data) => collection.objects.insert(href, data),
+                        None => collection.objects.remove(&href),
+                    };
+                }
+                collection.sync_token = new_token;
+                collection.synced_at = Some(Instant::now());
+                collection.last_synced = Utc::now();
+            }
+            return Ok(());
+        }
+
+        // Take the token before querying, so changes made in between are
+        // picked up by the next incremental sync
+        let sync_token = self
+            .propfind(url, "0", "<D:sync-token/>")
+            .await
+            .ok()
+            .and_then(|r| r.into_iter().next())
+            .and_then(|r| r.sync_token);
+
+        let objects = self.query_window(url, now, window).await?;
+        self.state.lock().await.collections.insert(
+            url.to_string(),
+            Collection {
+                sync_token,
+                objects,
+                window_day: today,
+                window,
+                synced_at: Some(Instant::now()),
+                last_synced: Utc::now(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Fetch every event overlapping the query window with a `calendar-query` report.
+    async fn query_window(
+        &self,
+        url: &str,
+        now: DateTime<Local>,
+        window: QueryWindow,
+    ) -> Result<HashMap<String, String>, CalendarError> {
+        let (start, end) = window.around(now);
+        let body = format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+             <C:calendar-query xmlns:D=\"{DAV}\" xmlns:C=\"{CALDAV}\">\
+             <D:prop><D:getetag/><C:calendar-data/></D:prop>\
+             <C:filter><C:comp-filter name=\"VCALENDAR\"><C:comp-filter name=\"VEVENT\">\
+             <C:time-range start=\"{}\" end=\"{}\"/>\
+             </C:comp-filter></C:comp-filter></C:filter>\
+             </C:calendar-query>",
+            caldav_time(start),
+            caldav_time(end),
+        );
+        let (multistatus, response) = self.multistatus("REPORT", url, "1", body).await?;
+        Ok(multistatus
+            .responses
+            .into_iter()
+            .filter_map(|r| r.resolved(&response.url))
+            .filter_map(|r| Some((r.href, r.calendar_data?)))
+            .collect())
+    }
+
+    /// Ask for the changes since `token` with a `sync-collection` report.
+    ///
+    /// Returns each changed href with its new data (`None` if it was deleted)
+    /// and the token for the next sync. Fails if the server rejects the
+    /// token, in which case a full query is needed.
+    async fn sync_changes(
+        &self,
+        url: &str,
+        token: &str,
+    ) -> Result<(Vec<(String, Option<String>)>, Option<String>), CalendarError> {
+        let body = format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+             <D:sync-collection xmlns:D=\"{DAV}\" xmlns:C=\"{CALDAV}\">\
+             <D:sync-token>{}</D:sync-token><D:sync-level>1</D:sync-level>\
+             <D:prop><D:getetag/><C:calendar-data/></D:prop>\
+             </D:sync-collection>",
+            xml_escape(token),
+        );
+        // RFC 6578 requires depth 0; the sync level is given in the body
+        let (multistatus, response) = self.multistatus("REPORT", url, "0", body).await?;
+        let collection_url = response.url.clone();
+
+        let mut changes = Vec::new();
+        let mut missing_data = Vec::new();
+        for r in multistatus.responses {
+            let Some(r) = r.resolved(&response.url) else {
+                continue;
+            };
+            if r.href == collection_url {
+                continue;
+            }
+            if r.removed {
+                changes.push((r.href, None));
+            } else if let Some(data) = r.calendar_data {
+                changes.push((r.href, Some(data)));
+            } else {
+                missing_data.push(r.href);
+            }
+        }
+
+        // Some servers only report etags, so fetch the changed objects separately
+        if !missing_data.is_empty() {
+            changes.extend(
+                self.multiget(url, &missing_data)
+                    .await?
+                    .into_iter()
+                    .map(|(href, data)| (href, Some(data))),
+            );
+        }
+
+        Ok((changes, multistatus.sync_token))
+    }
+
+    /// Fetch specific objects with a `calendar-multiget` report.
+    async fn multiget(
+        &self,
+        url: &str,
+        hrefs: &[String],
+    ) -> Result<Vec<(String, String)>, CalendarError> {
+        let hrefs: String = hrefs
+            .iter()
+            .map(|href| format!("<D:href>{}</D:href>", xml_escape(href)))
+            .collect();
+        let body = format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+             <C:calendar-multiget xmlns:D=\"{DAV}\" xmlns:C=\"{CALDAV}\">\
+             <D:prop><D:getetag/><C:calendar-data/></D:prop>{hrefs}\
+             </C:calendar-multiget>"
+        );
+        let (multistatus, response) = self.multistatus("REPORT", url, "1", body).await?;
+        Ok(multistatus
+            .responses
+            .into_iter()
+            .filter_map(|r| r.resolved(&response.url))
+            .filter_map(|r| Some((r.href, r.calendar_data?)))
+            .collect())
+    }
+}
+
+impl CalendarSource for CalDavSource {
+    fn list_calendars(&self) -> BoxFuture<'_, Vec<CalendarInfo>> {
+        Box::pin(async {
+            let calendars = self.calendars().await.unwrap_or_default();
+            let state = self.state.lock().await;
+            calendars
+                .into_iter()
+                .map(|calendar| {
+                    let uid = calendar_uid(&calendar.url);
+                    CalendarInfo {
+                        color: calendar.color.or_else(|| Some(fallback_color(&uid))),
+                        last_synced: state
+                            .collections
+                            .get(&calendar.url)
+                            .map(|c| c.last_synced.to_rfc3339()),
+                        uid,
+                        display_name: calendar.display_name,
+                        backend: Some("caldav".to_string()),
+                    }
+                })
+                .collect()
+        })
+    }
+
+    fn fetch_meetings<'a>(&'a self, query: &'a MeetingQuery) -> BoxFuture<'a, SourceFetch> {
+        Box::pin(async move {
+            let mut user_emails: Vec<String> = query
+                .additional_emails
+                .iter()
+                .filter(|e| !e.trim().is_empty())
+                .cloned()
+                .collect();
+            // The account name is usually the user's address on the server
+            if self.account.username.contains('@') {
+                user_emails.push(self.account.username.trim().to_string());
+            }
+
+            let window = FetchWindow::new(Local::now(), query.window);
+
+            let calendars: Vec<RemoteCalendar> = match self.calendars().await {
+                Ok(calendars) => calendars
+                    .into_iter()
+                    .filter(|c| {
+                        query.enabled_uids.is_empty()
+                            || query.enabled_uids.contains(&calendar_uid(&c.url))
+                    })
+                    .collect(),
+                Err(error) => return SourceFetch::failed(error),
+            };
+
+            let mut fetch = SourceFetch::default();
+            for calendar in calendars {
+                let uid = calendar_uid(&calendar.url);
+                // Events from the last successful sync are still shown
+                if let Err(error) = self
+                    .sync_calendar(&calendar.url, window.now, query.window)
+                    .await
+                {
+                    fetch.errors.insert(uid.clone(), error);
+                }
+                let state = self.state.lock().await;
+                let Some(collection) = state.collections.get(&calendar.url) else {
+                    continue;
+                };
+                let ics_objects: Vec<String> = collection.objects.values().cloned().collect();
+                fetch.add_calendar(&ics_objects, &uid, &window, &user_emails);
+            }
+
+            // Incremental syncs can bring in events outside the window
+            fetch.meetings.retain(|(_, m)| m.start < window.end);
+            fetch
+        })
+    }
+
+    fn refresh<'a>(&'a self, _enabled_uids: &'a [String]) -> BoxFuture<'a, ()> {
+        Box::pin(async {
+            // Sync on the next fetch instead of waiting for the interval
+            for collection in self.state.lock().await.collections.values_mut() {
+                collection.synced_at = None;
+            }
+        })
+    }
+
+    fn discover(&self) -> BoxFuture<'_, ()> {
+        Box::pin(async {
+            self.state.lock().await.calendars = None;
+            // Look the password up again in case it was added or changed
+            *self.credentials.lock().await = None;
+        })
+    }
+
+    fn watch_changes(
+        &self,
+        _enabled_uids: Vec<String>,
+        _window: QueryWindow,
+        _sender: tokio::sync::mpsc::Sender<()>,
+    ) -> BoxFuture<'static, ()> {
+        // CalDAV has no push notifications; changes are picked up by polling
+        Box::pin(async {})
+    }
+}
+
+/// Calendar UID for a collection URL.
+fn calendar_uid(url: &str) -> String {
+    format!("{UID_PREFIX}{url}")
+}
+
+/// Normalize the configured account URL, defaulting to HTTPS.
+fn account_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    if url.is_empty() {
+        return None;
+    }
+    if url.contains("://") {
+        Some(url.to_string())
+    } else {
+        Some(format!("https://{url}"))
+    }
+}
+
+/// Format a time as a CalDAV UTC timestamp (e.g. `20250115T093000Z`).
+fn caldav_time(time: DateTime<Local>) -> String {
+    time.with_timezone(&Utc)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+/// Escape text for use inside an XML element.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Turn a listed collection into a calendar, if it holds events.
+fn remote_calendar(response: &DavResponse) -> Option<RemoteCalendar> {
+    if !response.is_calendar || !response.supports_events {
+        return None;
+    }
+    let display_name = response.display_name.clone().unwrap_or_else(|| {
+        response
+            .href
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    });
+    Some(RemoteCalendar {
+        url: response.href.clone(),
+        display_name,
+        color: response.color.as_deref().and_then(normalize_hex_color),
+    })
+}
+
+/// A parsed WebDAV `multistatus` reply.
+#[derive(Debug, Default)]
+struct Multistatus {
+    responses: Vec<DavResponse>,
+    /// New token from a `sync-collection` report.
+    sync_token: Option<String>,
+}
+
+/// The properties we care about from one `response` element.
+#[derive(Debug, Default)]
+struct DavResponse {
+    href: String,
+    /// The resource is gone (`sync-collection` reports deletions as 404).
+    removed: bool,
+    is_calendar: bool,
+    /// Whether the calendar accepts events. Servers that don't list the
+    /// supported components are assumed to.
+    supports_events: bool,
+    display_name: Option<String>,
+    color: Option<String>,
+    sync_token: Option<String>,
+    current_user_principal: Option<String>,
+    calendar_home_set: Option<String>,
+    calendar_data: Option<String>,
+}
+
+impl DavResponse {
+    /// Resolve the hrefs in this response against the URL it came from.
+    fn resolved(mut self, base: &str) -> Option<Self> {
+        let base = url::Url::parse(base).ok()?;
+        let resolve = |href: &str| base.join(href).ok().map(String::from);
+        self.href = resolve(&self.href)?;
+        self.current_user_principal = self.current_user_principal.as_deref().and_then(resolve);
+        self.calendar_home_set = self.calendar_home_set.as_deref().and_then(resolve);
+        Some(self)
+    }
+}
+
+/// Parse a WebDAV `multistatus` document.
+fn parse_multistatus(xml: &str) -> Option<Multistatus> {
+    let doc = roxmltree::Document::parse(xml).ok()?;
+    let root = doc.root_element();
+    if !is_element(root, DAV, "multistatus") {
+        return None;
+    }
+
+    let mut multistatus = Multistatus {
+        sync_token: child(root, DAV, "sync-token").and_then(text),
+        ..Default::default()
+    };
+
+    for node in root.children().filter(|n| is_element(*n, DAV, "response")) {
+        let mut response = DavResponse {
+            href: child(node, DAV, "href").and_then(text).unwrap_or_default(),
+            removed: child(node, DAV, "status")
+                .and_then(|s| s.text())
+                .is_some_and(|s| s.contains(" 404")),
+            supports_events: true,
+            ..Default::default()
+        };
+
+        // Only read properties the server found; missing ones come back as 404
+        for propstat in node.children().filter(|n| is_element(*n, DAV, "propstat")) {
+            let found = child(propstat, DAV, "status")
+                .and_then(|s| s.text())
+                .is_none_or(|s| s.contains(" 200"));
+            let Some(prop) = child(propstat, DAV, "prop").filter(|_| found) else {
+                continue;
+            };
+            for p in prop.children().filter(roxmltree::Node::is_element) {
+                let tag = p.tag_name();
+                match (tag.namespace().unwrap_or_default(), tag.name()) {
+                    (DAV, "resourcetype") => {
+                        response.is_calendar = child(p, CALDAV, "calendar").is_some();
+                    }
+                    (DAV, "displayname") => response.display_name = text(p),
+                    (DAV, "sync-token") => response.sync_token = text(p),
+                    (DAV, "current-user-principal") => {
+                        response.current_user_principal = child(p, DAV, "href").and_then(text);
+                    }
+                    (CALDAV, "calendar-home-set") => {
+                        response.calendar_home_set = child(p, DAV, "href").and_then(text);
+                    }
+                    (CALDAV, "supported-calendar-component-set") => {
+                        response.supports_events = p.children().any(|c| {
+                            is_element(c, CALDAV, "comp") && c.attribute("name") == Some("VEVENT")
+                        });
+                    }
+                    (CALDAV, "calendar-data") => response.calendar_data = text(p),
+                    (APPLE_ICAL, "calendar-color") => response.color = text(p),
+                    _ => {}
+                }
+            }
+        }
+
+        multistatus.responses.push(response);
+    }
+
+    Some(multistatus)
+}
+
+/// Whether a node is the element `namespace:name`.
+fn is_element(node: roxmltree::Node<'_, '_>, namespace: &str, name: &str) -> bool {
+    node.is_element()
+        && node.tag_name().name() == name
+        && node.tag_name().namespace() == Some(namespace)
+}
+
+/// The first child element `namespace:name` of a node.
+fn child<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    namespace: &str,
+    name: &str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    node.children().find(|c| is_element(*c, namespace, name))
+}
+
+/// The trimmed text of a node, if not empty.
+fn text(node: roxmltree::Node<'_, '_>) -> Option<String> {
+    let text: String = node
+        .descendants()
+        .filter(roxmltree::Node::is_text)
+        .filter_map(|n| n.text())
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::http::{serve, test_response};
+    use crate::calendar::tests::event_ics;
+
+    /// A minimal local CalDAV server with a single "Work" calendar.
+    async fn start_stand_in() -> String {
+        serve(|head, body| {
+            let mut request_line = head.split_whitespace();
+            let method = request_line.next().unwrap_or_default();
+            let path = request_line.next().unwrap_or_default();
+            match respond(method, path, body) {
+                xml if xml.is_empty() => test_response("404 Not Found", &[], ""),
+                xml => test_response(
+                    "207 Multi-Status",
+                    &[("Content-Type", "application/xml")],
+                    &xml,
+                ),
+            }
+        })
+        .await
+    }
+
+    fn multistatus(responses: &str) -> String {
+        format!(
+            "<?xml version=\"1.0\"?>\
+             <d:multistatus xmlns:d=\"DAV:\" xmlns:cal=\"urn:ietf:params:xml:ns:caldav\" \
+             xmlns:ical=\"http://apple.com/ns/ical/\">{responses}</d:multistatus>"
+        )
+    }
+
+    fn event_response(href: &str, uid: &str, summary: &str) -> String {
+        format!(
+            "<d:response><d:href>{href}</d:href><d:propstat><d:prop>\
+             <d:getetag>\"1\"</d:getetag>\
+             <cal:calendar-data>{}</cal:calendar-data>\
+             </d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>",
+            event_ics(uid, summary),
+        )
+    }
+
+    fn respond(method: &str, path: &str, body: &str) -> String {
+        match (method, path) {
+            ("PROPFIND", "/") => multistatus(
+                "<d:response><d:href>/</d:href><d:propstat><d:prop>\
+                 <d:current-user-principal><d:href>/principals/alice/</d:href></d:current-user-principal>\
+                 <d:resourcetype><d:collection/></d:resourcetype>\
+                 </d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>",
+            ),
+            ("PROPFIND", "/principals/alice/") => multistatus(
+                "<d:response><d:href>/principals/alice/</d:href><d:propstat><d:prop>\
+                 <cal:calendar-home-set><d:href>/calendars/alice/</d:href></cal:calendar-home-set>\
+                 </d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>",
+            ),
+            ("PROPFIND", "/calendars/alice/") => multistatus(
+                "<d:response><d:href>/calendars/alice/</d:href><d:propstat><d:prop>\
+                 <d:resourcetype><d:collection/></d:resourcetype>\
+                 </d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>\
+                 <d:response><d:href>/calendars/alice/work/</d:href><d:propstat><d:prop>\
+                 <d:resourcetype><d:collection/><cal:calendar/></d:resourcetype>\
+                 <d:displayname>Work</d:displayname>\
+                 <ical:calendar-color>#FF0000FF</ical:calendar-color>\
+                 <cal:supported-calendar-component-set><cal:comp name=\"VEVENT\"/></cal:supported-calendar-component-set>\
+                 </d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>\
+                 <d:response><d:href>/calendars/alice/tasks/</d:href><d:propstat><d:prop>\
+                 <d:resourcetype><d:collection/><cal:calendar/></d:resourcetype>\
+                 <d:displayname>Tasks</d:displayname>\
+                 <cal:supported-calendar-component-set><cal:comp name=\"VTODO\"/></cal:supported-calendar-component-set>\
+                 </d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>",
+            ),
+            ("PROPFIND", "/calendars/alice/work/") => multistatus(
+                "<d:response><d:href>/calendars/alice/work/</d:href><d:propstat><d:prop>\
+                 <d:sync-token>token-1</d:sync-token>\
+                 </d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat></d:response>",
+            ),
+            ("REPORT", "/calendars/alice/work/") if body.contains("calendar-query") => multistatus(
+                &event_response("/calendars/alice/work/planning.ics", "planning", "Planning"),
+            ),
+            ("REPORT", "/calendars/alice/work/") if body.contains("token-1") => {
+                format!(
+                    "<?xml version=\"1.0\"?>\
+                     <d:multistatus xmlns:d=\"DAV:\" xmlns:cal=\"urn:ietf:params:xml:ns:caldav\">\
+                     <d:response><d:href>/calendars/alice/work/planning.ics</d:href>\
+                     <d:status>HTTP/1.1 404 Not Found</d:status></d:response>\
+                     {}<d:sync-token>token-2</d:sync-token></d:multistatus>",
+                    event_response("/calendars/alice/work/retro.ics", "retro", "Retro")
+                )
+            }
+            _ => String::new(),
+        }
+    }
+
+    fn source_for(url: String) -> CalDavSource {
+        let source = CalDavSource::new(CalDavAccount {
+            url,
+            username: String::new(),
+        });
+        // Skip the Secret Service lookup
+        *source.credentials.try_lock().unwrap() = Some(None);
+        source
+    }
+
+    #[test]
+    fn test_caldav_discovers_event_calendars() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let url = rt.block_on(start_stand_in());
+        let source = source_for(url.clone());
+
+        let calendars = rt.block_on(source.list_calendars());
+        assert_eq!(calendars.len(), 1);
+        assert_eq!(calendars[0].display_name, "Work");
+        assert_eq!(calendars[0].color.as_deref(), Some("#ff0000"));
+        assert_eq!(
+            calendars[0].uid,
+            calendar_uid(&format!("{url}calendars/alice/work/"))
+        );
+        assert_eq!(calendars[0].backend.as_deref(), Some("caldav"));
+    }
+
+    #[test]
+    fn test_caldav_fetches_then_syncs_incrementally() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let url = rt.block_on(start_stand_in());
+        let source = source_for(url);
+        let titles = |source: &CalDavSource| {
+            let fetch = rt.block_on(source.fetch_meetings(&MeetingQuery::default()));
+            fetch
+                .meetings
+                .into_iter()
+                .map(|(_, m)| m.title)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(titles(&source), vec!["Planning"]);
+
+        // Within the sync interval the server isn't asked again
+        assert_eq!(titles(&source), vec!["Planning"]);
+
+        // After a refresh, the sync-collection report removes one event and adds another
+        rt.block_on(source.refresh(&[]));
+        assert_eq!(titles(&source), vec!["Retro"]);
+    }
+
+    #[test]
+    fn test_parse_multistatus_skips_missing_props() {
+        let xml = multistatus(
+            "<d:response><d:href>/cal/</d:href>\
+             <d:propstat><d:prop><d:displayname>Home</d:displayname></d:prop>\
+             <d:status>HTTP/1.1 200 OK</d:status></d:propstat>\
+             <d:propstat><d:prop><ical:calendar-color>#00FF00</ical:calendar-color></d:prop>\
+             <d:status>HTTP/1.1 404 Not Found</d:status></d:propstat></d:response>",
+        );
+        let parsed = parse_multistatus(&xml).unwrap();
+        assert_eq!(parsed.responses.len(), 1);
+        assert_eq!(parsed.responses[0].display_name.as_deref(), Some("Home"));
+        assert_eq!(parsed.responses[0].color, None);
+        assert!(!parsed.responses[0].removed);
+    }
+}
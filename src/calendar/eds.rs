@@ -0,0 +1,1718 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//
+// Evolution Data Server calendar source, talking to EDS over the session bus.
+
+use super::{
+    AttendanceStatus, CalendarDiagnostics, CalendarError, CalendarInfo, CalendarSource,
+    EventStatus, FetchWindow, FilterReason, Meeting, MeetingQuery, QueryWindow, SourceFetch,
+    dedup_and_sort_meetings, parse_ical_datetime, parse_ics_objects, should_include_meeting,
+};
+use chrono::{DateTime, Local, NaiveDate};
+use futures_util::StreamExt;
+use futures_util::future::{BoxFuture, join_all};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use zbus::{Connection, zvariant};
+
+/// How long to wait before reopening a calendar view that failed or went away.
+const VIEW_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How long one calendar may take to answer before it's left out of an update.
+const CALENDAR_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Calendar source backed by Evolution Data Server.
+///
+/// Covers everything EDS knows about: local calendars, GNOME Online Accounts,
+/// and any `CalDAV`/webcal calendars configured through Evolution.
+///
+/// While [`CalendarSource::watch_changes`] runs, each watched calendar has a
+/// live EDS calendar view whose signals keep an in-memory copy of its events
+/// up to date, so fetching meetings doesn't have to query EDS at all.
+/// Calendars without a ready view are queried with `GetObjectList` instead.
+/// Parsed meetings are kept per calendar and reused until the calendar's
+/// `Revision` changes.
+#[derive(Debug, Default)]
+pub struct EdsSource {
+    client: Arc<EdsClient>,
+    live: Arc<Mutex<LiveStore>>,
+    parsed: Mutex<HashMap<String, ParsedCalendar>>,
+}
+
+/// Meetings parsed from one calendar, reused until its `Revision` changes.
+#[derive(Debug)]
+struct ParsedCalendar {
+    revision: String,
+    /// The live view's change count when the meetings were parsed.
+    live_changes: Option<u64>,
+    /// End of the time range the meetings were fetched for.
+    range_end: DateTime<Local>,
+    /// The query window the range was worked out from.
+    window: QueryWindow,
+    /// The additional addresses attendance was worked out with.
+    additional_emails: Vec<String>,
+    meetings: Vec<(bool, Meeting)>,
+    /// Number of objects that couldn't be parsed.
+    invalid_events: usize,
+}
+
+/// A session bus connection shared by all EDS requests, along with the
+/// calendars opened through it, keyed by source UID.
+///
+/// Opening a calendar makes EDS look up or start its backend, so the proxies
+/// are kept until the source is removed or a call on them fails.
+#[derive(Debug, Default)]
+struct EdsClient {
+    connection: tokio::sync::OnceCell<Connection>,
+    calendars: Mutex<HashMap<String, zbus::Proxy<'static>>>,
+}
+
+impl EdsClient {
+    /// The shared connection, connecting on first use.
+    async fn connection(&self) -> Option<&Connection> {
+        self.connection
+            .get_or_try_init(Connection::session)
+            .await
+            .ok()
+    }
+
+    /// The calendar for a source, opening it if it isn't open yet.
+    async fn calendar(&self, source_uid: &str) -> Result<zbus::Proxy<'static>, CalendarError> {
+        let cached = self
+            .calendars
+            .lock()
+            .ok()
+            .and_then(|calendars| calendars.get(source_uid).cloned());
+        if let Some(proxy) = cached {
+            return Ok(proxy);
+        }
+
+        let conn = self.connection().await.ok_or(CalendarError::Unreachable)?;
+        let proxy = open_calendar(conn, source_uid).await?;
+        if let Ok(mut calendars) = self.calendars.lock() {
+            calendars.insert(source_uid.to_string(), proxy.clone());
+        }
+        Ok(proxy)
+    }
+
+    /// Drop a calendar whose proxy stopped working, so it's reopened next time.
+    fn forget_calendar(&self, source_uid: &str) {
+        if let Ok(mut calendars) = self.calendars.lock() {
+            calendars.remove(source_uid);
+        }
+    }
+
+    /// Drop the calendars whose sources no longer exist.
+    fn retain_calendars(&self, source_uids: &[String]) {
+        if let Ok(mut calendars) = self.calendars.lock() {
+            calendars.retain(|uid, _| source_uids.contains(uid));
+        }
+    }
+
+    /// Run `task` on each calendar concurrently, returning the results by
+    /// source UID. Calendars that don't answer within [`CALENDAR_TIMEOUT`]
+    /// fail with [`CalendarError::TimedOut`], so one hung backend can't hold
+    /// up the others.
+    async fn for_each_calendar<T, F, Fut>(
+        &self,
+        source_uids: &[String],
+        task: F,
+    ) -> Vec<(String, Result<T, CalendarError>)>
+    where
+        F: Fn(zbus::Proxy<'static>) -> Fut,
+        Fut: Future<Output = Result<T, CalendarError>>,
+    {
+        join_all(source_uids.iter().map(|source_uid| async {
+            let result = tokio::time::timeout(CALENDAR_TIMEOUT, async {
+                let calendar = self.calendar(source_uid).await?;
+                let result = task(calendar).await;
+                if result.is_err() {
+                    self.forget_calendar(source_uid);
+                }
+                result
+            })
+            .await;
+            let result = result.unwrap_or(Err(CalendarError::TimedOut));
+            (source_uid.clone(), result)
+        }))
+        .await
+    }
+}
+
+/// Calendar objects received from live views, keyed by source UID.
+#[derive(Debug, Default)]
+struct LiveStore {
+    calendars: HashMap<String, LiveCalendar>,
+}
+
+/// The objects a calendar view has reported so far.
+#[derive(Debug, Default)]
+struct LiveCalendar {
+    /// Calendar objects keyed by UID and RECURRENCE-ID, as EDS identifies them.
+    objects: HashMap<String, String>,
+    /// The calendar's own address, used to find the user among attendees.
+    email: Option<String>,
+    /// Whether the view has finished reporting the initial set of objects.
+    complete: bool,
+    /// Number of signals applied, so cached meetings can tell the objects
+    /// changed before the calendar's `Revision` catches up.
+    changes: u64,
+}
+
+impl CalendarSource for EdsSource {
+    fn list_calendars(&self) -> BoxFuture<'_, Vec<CalendarInfo>> {
+        Box::pin(async {
+            get_calendars_from_dbus(&self.client)
+                .await
+                .unwrap_or_default()
+        })
+    }
+
+    fn fetch_meetings<'a>(&'a self, query: &'a MeetingQuery) -> BoxFuture<'a, SourceFetch> {
+        Box::pin(async move {
+            get_meetings_from_dbus(
+                &self.client,
+                &query.enabled_uids,
+                &query.additional_emails,
+                query.window,
+                &self.live,
+                &self.parsed,
+            )
+            .await
+        })
+    }
+
+    fn refresh<'a>(&'a self, enabled_uids: &'a [String]) -> BoxFuture<'a, ()> {
+        Box::pin(refresh_calendars(&self.client, enabled_uids))
+    }
+
+    fn discover(&self) -> BoxFuture<'_, ()> {
+        Box::pin(refresh_source_backends(&self.client))
+    }
+
+    fn can_respond(&self, calendar_uid: &str) -> bool {
+        // Every EDS calendar that was read has been opened through the client
+        self.client
+            .calendars
+            .lock()
+            .is_ok_and(|calendars| calendars.contains_key(calendar_uid))
+    }
+
+    fn respond<'a>(
+        &'a self,
+        meeting: &'a Meeting,
+        response: AttendanceStatus,
+        additional_emails: &'a [String],
+    ) -> BoxFuture<'a, Result<(), CalendarError>> {
+        Box::pin(respond_to_invitation(
+            &self.client,
+            meeting,
+            response,
+            additional_emails,
+        ))
+    }
+
+    fn diagnose<'a>(&'a self, query: &'a MeetingQuery) -> BoxFuture<'a, Vec<CalendarDiagnostics>> {
+        Box::pin(diagnose_calendars(
+            &self.client,
+            &query.additional_emails,
+            query.window,
+        ))
+    }
+
+    fn watch_changes(
+        &self,
+        enabled_uids: Vec<String>,
+        window: QueryWindow,
+        sender: tokio::sync::mpsc::Sender<()>,
+    ) -> BoxFuture<'static, ()> {
+        Box::pin(watch_calendar_changes(
+            Arc::clone(&self.client),
+            enabled_uids,
+            window,
+            sender,
+            Arc::clone(&self.live),
+        ))
+    }
+
+    fn watch_calendar_list(&self, sender: tokio::sync::mpsc::Sender<()>) -> BoxFuture<'static, ()> {
+        Box::pin(watch_source_changes(Arc::clone(&self.client), sender))
+    }
+}
+
+/// Ask EDS to re-discover calendars from all collection/account backends.
+///
+/// Calls `RefreshBackend` on the `SourceManager` for every source that has a
+/// `[Collection]` section (e.g. `CalDAV` or GOA accounts). This triggers
+/// server-side discovery of new calendars that were added to the account.
+async fn refresh_source_backends(client: &EdsClient) {
+    use zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+    let Some(conn) = client.connection().await else {
+        return;
+    };
+
+    let Ok(proxy) = zbus::Proxy::new(
+        conn,
+        "org.gnome.evolution.dataserver.Sources5",
+        "/org/gnome/evolution/dataserver/SourceManager",
+        "org.gnome.evolution.dataserver.SourceManager",
+    )
+    .await
+    else {
+        return;
+    };
+
+    // Also get managed objects to find collection UIDs
+    let Ok(om_proxy) = zbus::Proxy::new(
+        conn,
+        "org.gnome.evolution.dataserver.Sources5",
+        "/org/gnome/evolution/dataserver/SourceManager",
+        "org.freedesktop.DBus.ObjectManager",
+    )
+    .await
+    else {
+        return;
+    };
+
+    let Ok(reply) = om_proxy.call_method("GetManagedObjects", &()).await else {
+        return;
+    };
+    let Ok(objects) =
+        reply.body::<HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>>()
+    else {
+        return;
+    };
+
+    for (_path, interfaces) in objects {
+        let Some(source_props) = interfaces.get("org.gnome.evolution.dataserver.Source") else {
+            continue;
+        };
+
+        // Check for [Collection] section in the Data property
+        let is_collection = source_props.get("Data").is_some_and(|v| {
+            if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
+                s.contains("[Collection]")
+            } else {
+                false
+            }
+        });
+
+        if !is_collection {
+            continue;
+        }
+
+        let Some(uid) = source_props.get("UID").and_then(|v| {
+            if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
+                Some(s.to_string())
+            } else {
+                None
+            }
+        }) else {
+            continue;
+        };
+
+        let _ = proxy.call_method("RefreshBackend", &(uid.as_str(),)).await;
+    }
+}
+
+/// Refresh all calendars by triggering an upstream sync with remote servers.
+/// This calls the Refresh D-Bus method on each calendar, which forces EDS to
+/// fetch the latest data from CalDAV/Google/etc servers.
+/// If `enabled_uids` is empty, all calendars are refreshed.
+async fn refresh_calendars(client: &EdsClient, enabled_uids: &[String]) {
+    let Some(conn) = client.connection().await else {
+        return;
+    };
+
+    // Get calendar source UIDs
+    let Some(mut source_uids) = get_calendar_source_uids(conn).await else {
+        return;
+    };
+
+    // Filter to only enabled calendars if specified
+    if !enabled_uids.is_empty() {
+        source_uids.retain(|uid| enabled_uids.contains(uid));
+    }
+
+    // Call Refresh on each calendar (fire and forget - don't wait for the sync)
+    client
+        .for_each_calendar(&source_uids, |calendar| async move {
+            // Not every backend supports Refresh, so errors are expected here
+            let _ = calendar.call_method("Refresh", &()).await;
+            Ok(())
+        })
+        .await;
+}
+
+/// Keep a live EDS calendar view open for each calendar, applying its
+/// `ObjectsAdded` / `ObjectsModified` / `ObjectsRemoved` signals to the store
+/// and notifying the channel after each change.
+///
+/// Runs until cancelled. Dropping the future disposes of the views; the
+/// calendars they were opened on stay open in the client.
+async fn watch_calendar_changes(
+    client: Arc<EdsClient>,
+    enabled_uids: Vec<String>,
+    window: QueryWindow,
+    sender: tokio::sync::mpsc::Sender<()>,
+    store: Arc<Mutex<LiveStore>>,
+) {
+    let Some(conn) = client.connection().await else {
+        return;
+    };
+
+    // Get calendar source UIDs
+    let Some(mut source_uids) = get_calendar_source_uids(conn).await else {
+        return;
+    };
+
+    // Filter to enabled calendars if specified
+    if !enabled_uids.is_empty() {
+        source_uids.retain(|uid| enabled_uids.contains(uid));
+    }
+
+    // Polled together rather than spawned, so cancelling this future stops every view
+    join_all(
+        source_uids
+            .into_iter()
+            .map(|uid| run_calendar_view(&client, uid, window, &sender, &store)),
+    )
+    .await;
+}
+
+/// Which view signal a message came from.
+#[derive(Debug, Clone, Copy)]
+enum ViewSignal {
+    Added,
+    Modified,
+    Removed,
+    Complete,
+}
+
+/// Removes a calendar's objects from the store when its view stops, so
+/// fetches fall back to querying EDS rather than reading stale objects.
+struct LiveCalendarGuard<'a> {
+    store: &'a Mutex<LiveStore>,
+    source_uid: &'a str,
+}
+
+impl Drop for LiveCalendarGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut store) = self.store.lock() {
+            store.calendars.remove(self.source_uid);
+        }
+    }
+}
+
+/// Run a live view of one calendar, reopening it when it goes away and at
+/// midnight, when the time range it covers has to move forward.
+async fn run_calendar_view(
+    client: &EdsClient,
+    source_uid: String,
+    window: QueryWindow,
+    sender: &tokio::sync::mpsc::Sender<()>,
+    store: &Mutex<LiveStore>,
+) {
+    loop {
+        let guard = LiveCalendarGuard {
+            store,
+            source_uid: &source_uid,
+        };
+        let rolled_over = watch_calendar_view(client, &source_uid, window, sender, store).await;
+        drop(guard);
+
+        if !rolled_over {
+            tokio::time::sleep(VIEW_RETRY_DELAY).await;
+        }
+    }
+}
+
+/// Open a view of one calendar and apply its signals to the store.
+///
+/// Returns `true` when the view was closed because the day changed, or
+/// `false` if it couldn't be opened or EDS stopped sending signals.
+async fn watch_calendar_view(
+    client: &EdsClient,
+    source_uid: &str,
+    window: QueryWindow,
+    sender: &tokio::sync::mpsc::Sender<()>,
+    store: &Mutex<LiveStore>,
+) -> bool {
+    let Some(conn) = client.connection().await else {
+        return false;
+    };
+    let Ok(calendar_proxy) = client.calendar(source_uid).await else {
+        return false;
+    };
+
+    let email = calendar_proxy
+        .get_property::<String>("CalEmailAddress")
+        .await
+        .ok()
+        .filter(|e| !e.is_empty());
+
+    let now = Local::now();
+    let (range_start, range_end) = fetch_range(now, window);
+    let sexp = time_range_sexp(range_start, range_end);
+
+    let Ok(reply) = calendar_proxy
+        .call_method("CreateView", &(sexp.as_str(),))
+        .await
+    else {
+        // The cached calendar may be stale, so open it again next time
+        client.forget_calendar(source_uid);
+        return false;
+    };
+    let Ok(view_path) = reply.body::<zvariant::OwnedObjectPath>() else {
+        return false;
+    };
+    let Ok(view_proxy) = zbus::Proxy::new(
+        conn,
+        calendar_proxy.destination().to_owned(),
+        view_path,
+        "org.gnome.evolution.dataserver.CalendarView",
+    )
+    .await
+    else {
+        return false;
+    };
+    let view = ViewGuard(view_proxy);
+
+    // Subscribe before starting, so the initial objects aren't missed
+    let mut streams = Vec::new();
+    for (name, kind) in [
+        ("ObjectsAdded", ViewSignal::Added),
+        ("ObjectsModified", ViewSignal::Modified),
+        ("ObjectsRemoved", ViewSignal::Removed),
+        ("Complete", ViewSignal::Complete),
+    ] {
+        let Ok(stream) = view.0.receive_signal(name).await else {
+            return false;
+        };
+        streams.push(stream.map(move |message| (kind, message)).boxed());
+    }
+    let mut signals = futures_util::stream::select_all(streams);
+
+    if let Ok(mut store) = store.lock() {
+        store.calendars.insert(
+            source_uid.to_string(),
+            LiveCalendar {
+                email,
+                ..Default::default()
+            },
+        );
+    }
+    if view.0.call_method("Start", &()).await.is_err() {
+        return false;
+    }
+
+    let today = now.date_naive();
+    loop {
+        let Some(until_tomorrow) = time_until_next_day(today) else {
+            break true;
+        };
+        let Ok(next) = tokio::time::timeout(until_tomorrow, signals.next()).await else {
+            break true;
+        };
+        let Some((kind, message)) = next else {
+            break false;
+        };
+
+        if let Ok(mut store) = store.lock()
+            && let Some(calendar) = store.calendars.get_mut(source_uid)
+        {
+            apply_view_signal(calendar, kind, &message);
+            calendar.changes += 1;
+        }
+        let _ = sender.try_send(());
+    }
+}
+
+/// Disposes of a calendar view when dropped, including when the watch is
+/// cancelled. The connection is shared, so EDS wouldn't notice otherwise.
+struct ViewGuard(zbus::Proxy<'static>);
+
+impl Drop for ViewGuard {
+    fn drop(&mut self) {
+        let view = self.0.clone();
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move {
+                let _ = view.call_method("Dispose", &()).await;
+            });
+        }
+    }
+}
+
+/// Apply one view signal to a calendar's objects.
+fn apply_view_signal(calendar: &mut LiveCalendar, kind: ViewSignal, message: &zbus::Message) {
+    match kind {
+        ViewSignal::Added | ViewSignal::Modified => {
+            let Ok(objects) = message.body::<Vec<String>>() else {
+                return;
+            };
+            for object in objects {
+                if let Some(key) = object_key(&object) {
+                    calendar.objects.insert(key, object);
+                }
+            }
+        }
+        ViewSignal::Removed => {
+            let Ok(ids) = message.body::<Vec<String>>() else {
+                return;
+            };
+            for id in ids {
+                calendar.objects.remove(&removed_object_key(&id));
+            }
+        }
+        ViewSignal::Complete => calendar.complete = true,
+    }
+}
+
+/// Identify a calendar object the way EDS does in `ObjectsRemoved`:
+/// its UID, followed by a newline and the RECURRENCE-ID for overrides.
+fn object_key(ics: &str) -> Option<String> {
+    // Unfold continuation lines first, so long UIDs aren't cut short
+    let unfolded = ics
+        .replace("\r\n ", "")
+        .replace("\r\n\t", "")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+
+    let mut uid = None;
+    let mut rid = None;
+    for line in unfolded.lines() {
+        let line = line.trim_end_matches('\r');
+        if let Some(value) = line.strip_prefix("UID:") {
+            uid.get_or_insert_with(|| value.to_string());
+        } else if line.starts_with("RECURRENCE-ID")
+            && let Some((_, value)) = line.split_once(':')
+        {
+            rid.get_or_insert_with(|| value.to_string());
+        }
+    }
+
+    let uid = uid?;
+    Some(match rid {
+        Some(rid) if !rid.is_empty() => format!("{uid}\n{rid}"),
+        _ => uid,
+    })
+}
+
+/// Turn an `ObjectsRemoved` ID, "<uid>\n<rid>" with a possibly empty RID,
+/// into the key used by [`object_key`].
+fn removed_object_key(id: &str) -> String {
+    match id.split_once('\n') {
+        Some((uid, rid)) if !rid.is_empty() => format!("{uid}\n{rid}"),
+        Some((uid, _)) => uid.to_string(),
+        None => id.to_string(),
+    }
+}
+
+/// Reply to an invitation by setting the user's `PARTSTAT` on the event and
+/// saving it with `ModifyObjects`, which has EDS send the reply to the organizer.
+///
+/// For a recurring event the reply only covers the meeting's own occurrence,
+/// saved as a detached instance, so declining one occurrence leaves the rest
+/// of the series alone.
+async fn respond_to_invitation(
+    client: &EdsClient,
+    meeting: &Meeting,
+    response: AttendanceStatus,
+    additional_emails: &[String],
+) -> Result<(), CalendarError> {
+    let partstat = partstat_value(response).ok_or(CalendarError::NotInvited)?;
+    let calendar = client.calendar(&meeting.calendar_uid).await?;
+    if !calendar
+        .get_property::<bool>("Writable")
+        .await
+        .unwrap_or(true)
+    {
+        return Err(CalendarError::ReadOnly);
+    }
+
+    let query_failed = |e: zbus::Error| CalendarError::QueryFailed(e.to_string());
+    let ics = calendar
+        .call_method("GetObject", &(meeting.event_uid(), ""))
+        .await
+        .map_err(query_failed)?
+        .body::<String>()
+        .map_err(query_failed)?;
+
+    let mut user_emails: Vec<String> = additional_emails
+        .iter()
+        .filter(|e| !e.trim().is_empty())
+        .cloned()
+        .collect();
+    if let Ok(email) = calendar.get_property::<String>("CalEmailAddress").await
+        && !email.is_empty()
+    {
+        user_emails.push(email);
+    }
+
+    let (object, mod_type) = reply_object(&split_events(&ics), meeting, &user_emails, partstat)
+        .ok_or(CalendarError::NotInvited)?;
+
+    // No operation flags, so EDS goes ahead with sending the reply
+    calendar
+        .call_method("ModifyObjects", &(vec![object], mod_type, 0u32))
+        .await
+        .map_err(|e| CalendarError::WriteFailed(e.to_string()))?;
+    Ok(())
+}
+
+/// The object to save for a reply to `meeting`, and the `ModifyObjects` mode
+/// to save it with. A plain event is replied to as a whole. For a recurring
+/// event, the occurrence's detached instance is updated, or one is made from
+/// the series if the occurrence hasn't been detached yet.
+fn reply_object(
+    events: &[String],
+    meeting: &Meeting,
+    user_emails: &[String],
+    partstat: &str,
+) -> Option<(String, &'static str)> {
+    let is_detached = |event: &&String| object_key(event).is_some_and(|key| key.contains('\n'));
+    let Some(series) = events.iter().find(|e| !is_detached(e)) else {
+        // Only detached instances the user was invited to on their own
+        let occurrence = events.iter().find(|e| is_occurrence(e, meeting))?;
+        return Some((set_partstat(occurrence, user_emails, partstat)?, "this"));
+    };
+    if !is_recurring(series) {
+        return Some((set_partstat(series, user_emails, partstat)?, "all"));
+    }
+
+    let object = match events
+        .iter()
+        .filter(is_detached)
+        .find(|e| is_occurrence(e, meeting))
+    {
+        Some(occurrence) => set_partstat(occurrence, user_emails, partstat)?,
+        None => detach_occurrence(&set_partstat(series, user_emails, partstat)?, meeting)?,
+    };
+    Some((object, "this"))
+}
+
+/// The name of a content line's property, like `DTSTART` for
+/// `DTSTART;TZID=Europe/Berlin:20260105T100000`.
+fn property_name(line: &str) -> &str {
+    line.split([';', ':']).next().unwrap_or(line)
+}
+
+/// Whether an event repeats, through an `RRULE` or `RDATE` of its own.
+fn is_recurring(event: &str) -> bool {
+    event.lines().any(|line| {
+        let name = property_name(line.trim_end_matches('\r'));
+        name.eq_ignore_ascii_case("RRULE") || name.eq_ignore_ascii_case("RDATE")
+    })
+}
+
+/// Whether a detached instance's `RECURRENCE-ID` is the meeting's occurrence.
+fn is_occurrence(event: &str, meeting: &Meeting) -> bool {
+    let Some(series_start) = meeting.series_start() else {
+        return false;
+    };
+    event.lines().any(|line| {
+        let line = line.trim_end_matches('\r');
+        if !property_name(line).eq_ignore_ascii_case("RECURRENCE-ID") {
+            return false;
+        }
+        let tzid = split_unquoted(line, ':')[0]
+            .split(';')
+            .find_map(|p| p.strip_prefix("TZID="))
+            .map(|tzid| tzid.trim_matches('"').to_string());
+        parse_ical_datetime(line, tzid.as_deref()) == Some(series_start)
+    })
+}
+
+/// Turn a recurring event into a detached instance for the meeting's
+/// occurrence: its recurrence rules give way to a `RECURRENCE-ID`, and its
+/// start and end become the occurrence's.
+fn detach_occurrence(series: &str, meeting: &Meeting) -> Option<String> {
+    let series_start = meeting.series_start()?;
+    let (recurrence_id, start, end) = if meeting.is_all_day {
+        let date = |t: DateTime<Local>| format!(";VALUE=DATE:{}", t.format("%Y%m%d"));
+        (date(series_start), date(meeting.start), date(meeting.end))
+    } else {
+        let utc = |t: DateTime<Local>| {
+            format!(
+                ":{}",
+                t.with_timezone(&chrono::Utc).format("%Y%m%dT%H%M%SZ")
+            )
+        };
+        (utc(series_start), utc(meeting.start), utc(meeting.end))
+    };
+
+    let mut depth = 0;
+    let mut lines = Vec::new();
+    for line in series.lines() {
+        let line = line.trim_end_matches('\r');
+        let name = property_name(line);
+        if name.eq_ignore_ascii_case("BEGIN") {
+            depth += 1;
+        } else if name.eq_ignore_ascii_case("END") {
+            depth -= 1;
+        } else if depth == 1
+            && [
+                "DTSTART",
+                "DTEND",
+                "DURATION",
+                "RRULE",
+                "RDATE",
+                "EXRULE",
+                "EXDATE",
+                "RECURRENCE-ID",
+            ]
+            .iter()
+            .any(|p| name.eq_ignore_ascii_case(p))
+        {
+            continue;
+        }
+        lines.push(line.to_string());
+        if depth == 1 && lines.len() == 1 {
+            lines.push(format!("RECURRENCE-ID{recurrence_id}"));
+            lines.push(format!("DTSTART{start}"));
+            lines.push(format!("DTEND{end}"));
+        }
+    }
+    Some(lines.join("\r\n"))
+}
+
+/// The `PARTSTAT` value for a reply, or `None` if it isn't one.
+fn partstat_value(response: AttendanceStatus) -> Option<&'static str> {
+    match response {
+        AttendanceStatus::Accepted => Some("ACCEPTED"),
+        AttendanceStatus::Tentative => Some("TENTATIVE"),
+        AttendanceStatus::Declined => Some("DECLINED"),
+        AttendanceStatus::NeedsAction | AttendanceStatus::None => None,
+    }
+}
+
+/// Split an object from `GetObject` into its VEVENTs. Recurring events with
+/// detached instances come back as a VCALENDAR holding all of them.
+fn split_events(ics: &str) -> Vec<String> {
+    let mut events = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    let mut depth = 0;
+    for line in ics.lines() {
+        let line = line.trim_end_matches('\r');
+        if line.eq_ignore_ascii_case("BEGIN:VEVENT") && current.is_none() {
+            current = Some(Vec::new());
+        }
+        let Some(lines) = current.as_mut() else {
+            continue;
+        };
+        lines.push(line);
+        if line
+            .get(..6)
+            .is_some_and(|p| p.eq_ignore_ascii_case("BEGIN:"))
+        {
+            depth += 1;
+        } else if line
+            .get(..4)
+            .is_some_and(|p| p.eq_ignore_ascii_case("END:"))
+        {
+            depth -= 1;
+            if depth == 0 {
+                events.push(lines.join("\r\n"));
+                current = None;
+            }
+        }
+    }
+    events
+}
+
+/// Set the `PARTSTAT` of the user's ATTENDEE entry in an event and drop its
+/// RSVP request. Returns `None` if none of `user_emails` is an attendee.
+fn set_partstat(event: &str, user_emails: &[String], partstat: &str) -> Option<String> {
+    let unfolded = event
+        .replace("\r\n ", "")
+        .replace("\r\n\t", "")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+
+    let mut found = false;
+    let mut alarm_depth = 0;
+    let mut lines = Vec::new();
+    for line in unfolded.lines() {
+        let line = line.trim_end_matches('\r');
+        // Attendees of email alarms are recipients, not invitees
+        if line.eq_ignore_ascii_case("BEGIN:VALARM") {
+            alarm_depth += 1;
+        } else if line.eq_ignore_ascii_case("END:VALARM") {
+            alarm_depth -= 1;
+        }
+
+        let mut parts = split_unquoted(line, ':');
+        let value = if parts.len() > 1 {
+            parts.split_off(1).join(":")
+        } else {
+            String::new()
+        };
+        let mut params = split_unquoted(&parts[0], ';');
+        if alarm_depth > 0 || !params[0].eq_ignore_ascii_case("ATTENDEE") {
+            lines.push(line.to_string());
+            continue;
+        }
+
+        let address = value
+            .split_once(':')
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("mailto"))
+            .map(|(_, address)| address.to_string());
+        let email_param = params.iter().find_map(|p| {
+            let (name, value) = p.split_once('=')?;
+            name.eq_ignore_ascii_case("EMAIL")
+                .then(|| value.trim_matches('"').to_string())
+        });
+        let is_user = [address, email_param].into_iter().flatten().any(|email| {
+            user_emails
+                .iter()
+                .any(|ue| ue.trim().eq_ignore_ascii_case(&email))
+        });
+        if !is_user {
+            lines.push(line.to_string());
+            continue;
+        }
+
+        params.retain(|p| {
+            let name = p.split_once('=').map_or(p.as_str(), |(name, _)| name);
+            !name.eq_ignore_ascii_case("PARTSTAT") && !name.eq_ignore_ascii_case("RSVP")
+        });
+        params.push(format!("PARTSTAT={partstat}"));
+        lines.push(format!("{}:{value}", params.join(";")));
+        found = true;
+    }
+
+    found.then(|| lines.join("\r\n"))
+}
+
+/// Split a content line at each `separator` that isn't inside a quoted
+/// parameter value.
+fn split_unquoted(line: &str, separator: char) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut quoted = false;
+    for c in line.chars() {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        if c == separator && !quoted {
+            parts.push(String::new());
+        } else if let Some(part) = parts.last_mut() {
+            part.push(c);
+        }
+    }
+    parts
+}
+
+/// The time range fetched from calendars: the query window, widened to cover
+/// the whole day, so that views and parsed meetings only go stale at midnight.
+fn fetch_range(now: DateTime<Local>, window: QueryWindow) -> (DateTime<Local>, DateTime<Local>) {
+    let (query_start, query_end) = window.around(now);
+    let day_start = now
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|t| t.and_local_timezone(Local).earliest())
+        .unwrap_or(now);
+    (
+        query_start.min(day_start - window.look_behind),
+        query_end + chrono::Duration::days(1),
+    )
+}
+
+/// How long until the local day after `today` begins.
+fn time_until_next_day(today: NaiveDate) -> Option<std::time::Duration> {
+    let tomorrow = today
+        .succ_opt()?
+        .and_hms_opt(0, 0, 0)?
+        .and_local_timezone(Local)
+        .earliest()?;
+    (tomorrow - Local::now()).to_std().ok()
+}
+
+/// Build the S-expression EDS uses to select events in a time range.
+/// `occur-in-time-range?` also matches recurring events with instances in the range.
+fn time_range_sexp(start: DateTime<Local>, end: DateTime<Local>) -> String {
+    // EDS expects UTC timestamps
+    format!(
+        "(occur-in-time-range? (make-time \"{}\") (make-time \"{}\"))",
+        start.with_timezone(&chrono::Utc).format("%Y%m%dT%H%M%SZ"),
+        end.with_timezone(&chrono::Utc).format("%Y%m%dT%H%M%SZ")
+    )
+}
+
+/// Open a calendar through the `CalendarFactory` and initialize its backend.
+async fn open_calendar(
+    conn: &Connection,
+    source_uid: &str,
+) -> Result<zbus::Proxy<'static>, CalendarError> {
+    let open_failed = |e: zbus::Error| CalendarError::OpenFailed(e.to_string());
+
+    let factory_proxy = zbus::Proxy::new(
+        conn,
+        "org.gnome.evolution.dataserver.Calendar8",
+        "/org/gnome/evolution/dataserver/CalendarFactory",
+        "org.gnome.evolution.dataserver.CalendarFactory",
+    )
+    .await
+    .map_err(|_| CalendarError::Unreachable)?;
+
+    let reply = factory_proxy
+        .call_method("OpenCalendar", &(source_uid,))
+        .await
+        .map_err(open_failed)?;
+    let (calendar_path, bus_name) = reply.body::<(String, String)>().map_err(open_failed)?;
+
+    let calendar_proxy = zbus::Proxy::new(
+        conn,
+        bus_name,
+        calendar_path,
+        "org.gnome.evolution.dataserver.Calendar",
+    )
+    .await
+    .map_err(open_failed)?;
+
+    // Initialize the backend (required before any calendar operations)
+    let _ = calendar_proxy.call_method("Open", &()).await;
+    Ok(calendar_proxy)
+}
+
+/// Watch for new or removed calendar sources via the `ObjectManager`'s
+/// `InterfacesAdded` / `InterfacesRemoved` signals on the EDS `SourceManager`.
+///
+/// Sends to the channel whenever the set of sources changes, so the app can
+/// immediately refresh its calendar list and meetings.
+///
+/// Calendars opened by the client are dropped when their source is removed.
+async fn watch_source_changes(client: Arc<EdsClient>, sender: tokio::sync::mpsc::Sender<()>) {
+    let Some(conn) = client.connection().await else {
+        return;
+    };
+
+    let Ok(proxy) = zbus::Proxy::new(
+        conn,
+        "org.gnome.evolution.dataserver.Sources5",
+        "/org/gnome/evolution/dataserver/SourceManager",
+        "org.freedesktop.DBus.ObjectManager",
+    )
+    .await
+    else {
+        return;
+    };
+
+    // Listen for InterfacesAdded (new source) and InterfacesRemoved (deleted source),
+    // merging whichever streams we were able to subscribe to
+    let mut streams = Vec::new();
+    for (name, removed) in [("InterfacesAdded", false), ("InterfacesRemoved", true)] {
+        if let Ok(stream) = proxy.receive_signal(name).await {
+            streams.push(stream.map(move |_| removed).boxed());
+        }
+    }
+    let mut changes = futures_util::stream::select_all(streams);
+
+    while let Some(removed) = changes.next().await {
+        if removed && let Some(source_uids) = get_calendar_source_uids(conn).await {
+            client.retain_calendars(&source_uids);
+        }
+        let _ = sender.try_send(());
+    }
+}
+
+async fn get_meetings_from_dbus(
+    client: &EdsClient,
+    enabled_uids: &[String],
+    additional_emails: &[String],
+    query_window: QueryWindow,
+    live: &Mutex<LiveStore>,
+    parsed: &Mutex<HashMap<String, ParsedCalendar>>,
+) -> SourceFetch {
+    // Evolution Data Server workflow:
+    // 1. Get calendar source UIDs from D-Bus SourceManager
+    // 2. Reuse the meetings of calendars whose Revision hasn't changed
+    // 3. Take the objects of calendars with a ready live view from the store
+    // 4. Query the other calendars concurrently with GetObjectList
+    // 5. Parse the iCalendar objects
+
+    // Step 1: Get calendar source UIDs from D-Bus SourceManager
+    let Some(conn) = client.connection().await else {
+        return SourceFetch::failed(CalendarError::Unreachable);
+    };
+    let Some(mut source_uids) = get_calendar_source_uids(conn).await else {
+        return SourceFetch::failed(CalendarError::Unreachable);
+    };
+
+    // Filter to only enabled calendars if a filter is specified
+    if !enabled_uids.is_empty() {
+        source_uids.retain(|uid| enabled_uids.contains(uid));
+    }
+
+    let now = Local::now();
+    let (query_start, query_end) = query_window.around(now);
+    let (range_start, range_end) = fetch_range(now, query_window);
+    // Parse up to the end of the fetch range so reused meetings stay complete
+    let window = FetchWindow {
+        end: range_end,
+        ..FetchWindow::new(now, query_window)
+    };
+
+    // Step 2: Reuse parsed meetings while the revision matches and they still
+    // cover the query window. Revisions come from the proxies' property cache,
+    // which EDS keeps current, so this doesn't usually touch the bus.
+    let revisions: HashMap<String, String> = client
+        .for_each_calendar(&source_uids, |calendar| async move {
+            calendar
+                .get_property::<String>("Revision")
+                .await
+                .map_err(|e| CalendarError::QueryFailed(e.to_string()))
+        })
+        .await
+        .into_iter()
+        .filter_map(|(uid, revision)| Some((uid, revision.ok()?)))
+        .collect();
+
+    let live_changes: HashMap<String, u64> = live
+        .lock()
+        .map(|store| {
+            store
+                .calendars
+                .iter()
+                .filter(|(_, c)| c.complete)
+                .map(|(uid, c)| (uid.clone(), c.changes))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut fetch = SourceFetch::default();
+    let mut stale_uids = Vec::new();
+    if let Ok(mut parsed) = parsed.lock() {
+        parsed.retain(|uid, _| source_uids.contains(uid));
+        for source_uid in &source_uids {
+            match parsed.get(source_uid) {
+                Some(cached)
+                    if revisions.get(source_uid) == Some(&cached.revision)
+                        && live_changes.get(source_uid) == cached.live_changes.as_ref()
+                        && cached.range_end >= query_end
+                        && cached.window == query_window
+                        && cached.additional_emails == additional_emails =>
+                {
+                    fetch.meetings.extend(cached.meetings.iter().cloned());
+                    fetch.calendars_read += 1;
+                    if cached.invalid_events > 0 {
+                        fetch.errors.insert(
+                            source_uid.clone(),
+                            CalendarError::InvalidEvents(cached.invalid_events),
+                        );
+                    }
+                }
+                _ => stale_uids.push(source_uid.clone()),
+            }
+        }
+    }
+
+    // Step 3: Use the live view's copy when it has finished loading
+    let mut calendars = Vec::new();
+    let mut unloaded_uids = Vec::new();
+    for source_uid in stale_uids {
+        let live_calendar = live.lock().ok().and_then(|store| {
+            let calendar = store.calendars.get(&source_uid).filter(|c| c.complete)?;
+            Some((
+                calendar.objects.values().cloned().collect::<Vec<_>>(),
+                calendar.email.clone(),
+            ))
+        });
+        match live_calendar {
+            Some(live_calendar) => calendars.push((source_uid, live_calendar)),
+            None => unloaded_uids.push(source_uid),
+        }
+    }
+
+    // Step 4: Otherwise query the calendars for events using GetObjectList
+    let query = time_range_sexp(range_start, range_end);
+    let query = query.as_str();
+    let query_failed = |e: zbus::Error| CalendarError::QueryFailed(e.to_string());
+    let queried = client
+        .for_each_calendar(&unloaded_uids, |calendar| async move {
+            let reply = calendar
+                .call_method("GetObjectList", &(query,))
+                .await
+                .map_err(query_failed)?;
+            let ics_objects = reply.body::<Vec<String>>().map_err(query_failed)?;
+            // The CalEmailAddress identifies the user in ATTENDEE fields
+            let cal_email = calendar
+                .get_property::<String>("CalEmailAddress")
+                .await
+                .ok();
+            Ok((ics_objects, cal_email))
+        })
+        .await;
+    for (source_uid, result) in queried {
+        match result {
+            Ok(objects) => calendars.push((source_uid, objects)),
+            Err(error) => {
+                fetch.errors.insert(source_uid, error);
+            }
+        }
+    }
+
+    for (source_uid, (ics_objects, cal_email)) in calendars {
+        // Combine CalEmailAddress with additional_emails for user identification
+        // Filter out empty strings from additional_emails
+        let mut user_emails: Vec<String> = additional_emails
+            .iter()
+            .filter(|e| !e.trim().is_empty())
+            .cloned()
+            .collect();
+        if let Some(email) = cal_email
+            && !email.is_empty()
+            && !user_emails.iter().any(|e| e.eq_ignore_ascii_case(&email))
+        {
+            user_emails.push(email);
+        }
+
+        // Step 5: Parse iCalendar objects and extract meetings using calcard
+        let mut meetings = Vec::new();
+        let invalid_events = parse_ics_objects(
+            &ics_objects,
+            &source_uid,
+            &window,
+            &user_emails,
+            &mut meetings,
+        )
+        .invalid;
+        fetch.meetings.extend(meetings.iter().cloned());
+        fetch.calendars_read += 1;
+        if invalid_events > 0 {
+            fetch.errors.insert(
+                source_uid.clone(),
+                CalendarError::InvalidEvents(invalid_events),
+            );
+        }
+
+        if let Some(revision) = revisions.get(&source_uid)
+            && let Ok(mut parsed) = parsed.lock()
+        {
+            let live_changes = live_changes.get(&source_uid).copied();
+            parsed.insert(
+                source_uid,
+                ParsedCalendar {
+                    revision: revision.clone(),
+                    live_changes,
+                    range_end,
+                    window: query_window,
+                    additional_emails: additional_emails.to_vec(),
+                    meetings,
+                    invalid_events,
+                },
+            );
+        }
+    }
+
+    // Reused meetings were parsed earlier and fetches cover the whole day,
+    // so apply the current query window to everything
+    fetch.meetings.retain(|(_, m)| {
+        should_include_meeting(m.start, m.end, now, query_start) && m.start < query_end
+    });
+    fetch
+}
+
+/// Query every EDS calendar directly, bypassing the live views and parsed
+/// cache, and report what each one returned.
+async fn diagnose_calendars(
+    client: &EdsClient,
+    additional_emails: &[String],
+    query_window: QueryWindow,
+) -> Vec<CalendarDiagnostics> {
+    let Some(calendars) = get_calendars_from_dbus(client).await else {
+        return Vec::new();
+    };
+
+    let window = FetchWindow::new(Local::now(), query_window);
+    let (range_start, range_end) = fetch_range(window.now, query_window);
+    let query = time_range_sexp(range_start, range_end);
+    let query = query.as_str();
+    let query_failed = |e: zbus::Error| CalendarError::QueryFailed(e.to_string());
+
+    let uids: Vec<String> = calendars.iter().map(|cal| cal.uid.clone()).collect();
+    let mut results: HashMap<String, _> = client
+        .for_each_calendar(&uids, |calendar| async move {
+            let revision = calendar.get_property::<String>("Revision").await.ok();
+            let started = std::time::Instant::now();
+            let reply = calendar
+                .call_method("GetObjectList", &(query,))
+                .await
+                .map_err(query_failed)?;
+            let ics_objects = reply.body::<Vec<String>>().map_err(query_failed)?;
+            let duration = started.elapsed();
+            let cal_email = calendar
+                .get_property::<String>("CalEmailAddress")
+                .await
+                .ok();
+            Ok((revision, duration, ics_objects, cal_email))
+        })
+        .await
+        .into_iter()
+        .collect();
+
+    calendars
+        .into_iter()
+        .map(|cal| {
+            let mut diagnostics = CalendarDiagnostics {
+                uid: cal.uid,
+                display_name: cal.display_name,
+                backend: cal.backend,
+                revision: None,
+                result: Ok(()),
+                duration: None,
+                raw_objects: 0,
+                invalid_objects: 0,
+                instances: 0,
+                filtered: std::collections::BTreeMap::new(),
+                meetings: Vec::new(),
+            };
+            let (revision, duration, ics_objects, cal_email) =
+                match results.remove(&diagnostics.uid) {
+                    Some(Ok(result)) => result,
+                    Some(Err(error)) => {
+                        diagnostics.result = Err(error);
+                        return diagnostics;
+                    }
+                    None => {
+                        diagnostics.result = Err(CalendarError::TimedOut);
+                        return diagnostics;
+                    }
+                };
+
+            let mut user_emails: Vec<String> = additional_emails
+                .iter()
+                .filter(|e| !e.trim().is_empty())
+                .cloned()
+                .collect();
+            user_emails.extend(cal_email.filter(|email| !email.is_empty()));
+
+            let mut meetings = Vec::new();
+            let stats = parse_ics_objects(
+                &ics_objects,
+                &diagnostics.uid,
+                &window,
+                &user_emails,
+                &mut meetings,
+            );
+            let parsed = meetings.len();
+            let mut meetings = dedup_and_sort_meetings(meetings, usize::MAX);
+            let duplicates = parsed - meetings.len();
+            let before_cancelled = meetings.len();
+            meetings.retain(|m| m.status != EventStatus::Cancelled);
+            let cancelled = before_cancelled - meetings.len();
+            let before_window_end = meetings.len();
+            meetings.retain(|m| m.start < window.end);
+            let after_window = before_window_end - meetings.len();
+
+            let mut filtered = stats.filtered;
+            for (reason, count) in [
+                (FilterReason::Duplicate, duplicates),
+                (FilterReason::Cancelled, cancelled),
+                (FilterReason::AfterWindow, after_window),
+            ] {
+                if count > 0 {
+                    *filtered.entry(reason).or_default() += count;
+                }
+            }
+
+            diagnostics.revision = revision;
+            diagnostics.duration = Some(duration);
+            diagnostics.raw_objects = ics_objects.len();
+            diagnostics.invalid_objects = stats.invalid;
+            diagnostics.instances = stats.instances;
+            diagnostics.filtered = filtered;
+            diagnostics.meetings = meetings;
+            diagnostics
+        })
+        .collect()
+}
+
+/// Get calendar source UIDs from Evolution Data Server via D-Bus
+///
+/// This queries the `SourceManager`'s `ObjectManager` interface to discover
+/// all sources, including those from GNOME Online Accounts which are
+/// not stored as files in ~/.config/evolution/sources/
+async fn get_calendar_source_uids(conn: &Connection) -> Option<Vec<String>> {
+    use zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+    let source_manager_proxy = zbus::Proxy::new(
+        conn,
+        "org.gnome.evolution.dataserver.Sources5",
+        "/org/gnome/evolution/dataserver/SourceManager",
+        "org.freedesktop.DBus.ObjectManager",
+    )
+    .await
+    .ok()?;
+
+    // GetManagedObjects returns a{oa{sa{sv}}} - dict of object paths to interface properties
+    let reply = source_manager_proxy
+        .call_method("GetManagedObjects", &())
+        .await
+        .ok()?;
+
+    let objects: HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>> =
+        reply.body().ok()?;
+
+    let mut source_uids = Vec::new();
+
+    for (_path, interfaces) in objects {
+        // Look for the Source interface
+        if let Some(source_props) = interfaces.get("org.gnome.evolution.dataserver.Source") {
+            // Get the UID
+            let uid = source_props.get("UID").and_then(|v| {
+                if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
+                    Some(s.to_string())
+                } else {
+                    None
+                }
+            });
+
+            // Get the Data (source configuration) and check for [Calendar] section
+            let has_calendar = source_props.get("Data").is_some_and(|v| {
+                if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
+                    s.contains("[Calendar]")
+                } else {
+                    false
+                }
+            });
+
+            if let Some(uid) = uid
+                && has_calendar
+            {
+                source_uids.push(uid);
+            }
+        }
+    }
+
+    Some(source_uids)
+}
+
+/// Get calendar info (UID and display name) from Evolution Data Server via D-Bus
+async fn get_calendars_from_dbus(client: &EdsClient) -> Option<Vec<CalendarInfo>> {
+    use zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+    let conn = client.connection().await?;
+    let source_manager_proxy = zbus::Proxy::new(
+        conn,
+        "org.gnome.evolution.dataserver.Sources5",
+        "/org/gnome/evolution/dataserver/SourceManager",
+        "org.freedesktop.DBus.ObjectManager",
+    )
+    .await
+    .ok()?;
+
+    let reply = source_manager_proxy
+        .call_method("GetManagedObjects", &())
+        .await
+        .ok()?;
+
+    let objects: HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>> =
+        reply.body().ok()?;
+
+    let mut calendars = Vec::new();
+
+    for (_path, interfaces) in objects {
+        if let Some(source_props) = interfaces.get("org.gnome.evolution.dataserver.Source") {
+            // Get the UID
+            let uid = source_props.get("UID").and_then(|v| {
+                if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
+                    Some(s.to_string())
+                } else {
+                    None
+                }
+            });
+
+            // Get the Data field and extract DisplayName and check for [Calendar]
+            let data = source_props.get("Data").and_then(|v| {
+                if let Some(Value::Str(s)) = v.downcast_ref::<Value>() {
+                    Some(s.to_string())
+                } else {
+                    None
+                }
+            });
+
+            if let (Some(uid), Some(data)) = (uid, data)
+                && data.contains("[Calendar]")
+            {
+                let display_name = parse_display_name(&data).unwrap_or_else(|| uid.clone());
+                let color = parse_color(&data);
+                let backend = parse_backend_name(&data);
+                calendars.push(CalendarInfo {
+                    uid,
+                    display_name,
+                    color,
+                    last_synced: None, // Will be filled in below
+                    backend,
+                });
+            }
+        }
+    }
+
+    // Fetch last_synced (Revision) for each calendar
+    let uids: Vec<String> = calendars.iter().map(|cal| cal.uid.clone()).collect();
+    let revisions: HashMap<String, String> = client
+        .for_each_calendar(&uids, |calendar| async move {
+            // Get the Revision property (format: "2026-01-08T04:19:20Z(0)")
+            calendar
+                .get_property::<String>("Revision")
+                .await
+                .map_err(|e| CalendarError::QueryFailed(e.to_string()))
+        })
+        .await
+        .into_iter()
+        .filter_map(|(uid, revision)| Some((uid, revision.ok()?)))
+        .collect();
+    for cal in &mut calendars {
+        if let Some(revision) = revisions.get(&cal.uid) {
+            // Extract just the timestamp part before the parentheses
+            let timestamp = revision.split('(').next().unwrap_or(revision);
+            cal.last_synced = Some(timestamp.to_string());
+        }
+    }
+
+    // Sort by display name for consistent ordering
+    calendars.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    Some(calendars)
+}
+
+/// Parse `DisplayName` from INI-format source data
+fn parse_display_name(data: &str) -> Option<String> {
+    // Look for DisplayName= line (without locale suffix like DisplayName[en])
+    for line in data.lines() {
+        let line = line.trim();
+        if line.starts_with("DisplayName=") {
+            return Some(line.strip_prefix("DisplayName=")?.to_string());
+        }
+    }
+    None
+}
+
+/// Parse Color from INI-format source data (in [Calendar] section)
+fn parse_color(data: &str) -> Option<String> {
+    // Look for Color= specifically in the [Calendar] section, since other sections
+    // (like [WebDAV Backend]) may have an empty Color= field
+    let mut in_calendar_section = false;
+    for line in data.lines() {
+        let line = line.trim();
+        if line == "[Calendar]" {
+            in_calendar_section = true;
+        } else if line.starts_with('[') {
+            in_calendar_section = false;
+        } else if in_calendar_section && line.starts_with("Color=") {
+            let color = line.strip_prefix("Color=")?.to_string();
+            if !color.is_empty() {
+                return Some(color);
+            }
+        }
+    }
+    None
+}
+
+/// Parse `BackendName` from INI-format source data (in [Calendar] section)
+fn parse_backend_name(data: &str) -> Option<String> {
+    // Look for BackendName= in the [Calendar] section
+    let mut in_calendar_section = false;
+    for line in data.lines() {
+        let line = line.trim();
+        if line == "[Calendar]" {
+            in_calendar_section = true;
+        } else if line.starts_with('[') {
+            in_calendar_section = false;
+        } else if in_calendar_section && line.starts_with("BackendName=") {
+            return Some(line.strip_prefix("BackendName=")?.to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fetch_range_covers_query_window_all_day() {
+        use chrono::TimeZone;
+
+        let morning = Local.with_ymd_and_hms(2026, 3, 10, 0, 15, 0).unwrap();
+        let evening = Local.with_ymd_and_hms(2026, 3, 10, 23, 45, 0).unwrap();
+        for window in [
+            QueryWindow::default(),
+            QueryWindow {
+                look_behind: chrono::Duration::hours(4),
+                look_ahead: chrono::Duration::days(7),
+            },
+        ] {
+            let (range_start, range_end) = fetch_range(morning, window);
+
+            // Fetched just after midnight, the range still covers the evening's window
+            assert!(range_start <= window.around(morning).0);
+            assert!(range_start <= window.around(evening).0);
+            assert!(range_end >= window.around(evening).1);
+        }
+    }
+
+    // Tests for live view object keys
+    #[test]
+    fn test_object_key_matches_removed_ids() {
+        let series =
+            "BEGIN:VEVENT\r\nUID:weekly-sync@exa\r\n mple.com\r\nSUMMARY:Sync\r\nEND:VEVENT\r\n";
+        let moved = "BEGIN:VEVENT\r\nUID:weekly-sync@example.com\r\n\
+                     RECURRENCE-ID;TZID=Europe/Berlin:20260105T100000\r\nEND:VEVENT\r\n";
+
+        assert_eq!(
+            object_key(series),
+            Some(removed_object_key("weekly-sync@example.com\n"))
+        );
+        assert_eq!(
+            object_key(moved),
+            Some(removed_object_key(
+                "weekly-sync@example.com\n20260105T100000"
+            ))
+        );
+        assert_eq!(object_key("BEGIN:VEVENT\r\nEND:VEVENT\r\n"), None);
+    }
+
+    #[test]
+    fn test_split_events_separates_detached_instances() {
+        let ics = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\n\
+                   BEGIN:VEVENT\r\nUID:sync\r\n\
+                   BEGIN:VALARM\r\nACTION:DISPLAY\r\nEND:VALARM\r\nEND:VEVENT\r\n\
+                   BEGIN:VEVENT\r\nUID:sync\r\nRECURRENCE-ID:20260105T100000Z\r\nEND:VEVENT\r\n\
+                   END:VCALENDAR\r\n";
+        let events = split_events(ics);
+        assert_eq!(events.len(), 2);
+        assert!(events[0].starts_with("BEGIN:VEVENT") && events[0].ends_with("END:VEVENT"));
+        assert!(events[0].contains("END:VALARM"));
+        assert_eq!(
+            object_key(&events[1]).as_deref(),
+            Some("sync\n20260105T100000Z")
+        );
+
+        // A plain event comes back as just the VEVENT
+        assert_eq!(split_events("BEGIN:VEVENT\nUID:one\nEND:VEVENT\n").len(), 1);
+    }
+
+    #[test]
+    fn test_set_partstat_updates_only_the_user() {
+        let event = "BEGIN:VEVENT\r\nUID:review\r\n\
+                     ATTENDEE;CN=\"Lima; Ana\";PARTSTAT=ACCEPTED:mailto:ana@example.com\r\n\
+                     ATTENDEE;RSVP=TRUE;PARTSTAT=NEEDS-ACTION;CN=Bo:MAILTO:bo@exam\r\n ple.com\r\n\
+                     BEGIN:VALARM\r\nACTION:EMAIL\r\nATTENDEE:mailto:bo@example.com\r\nEND:VALARM\r\n\
+                     END:VEVENT";
+        let emails = vec!["Bo@Example.com".to_string()];
+
+        let updated = set_partstat(event, &emails, "DECLINED").unwrap();
+        let lines: Vec<&str> = updated.split("\r\n").collect();
+        assert_eq!(
+            lines[2],
+            "ATTENDEE;CN=\"Lima; Ana\";PARTSTAT=ACCEPTED:mailto:ana@example.com"
+        );
+        assert_eq!(
+            lines[3],
+            "ATTENDEE;CN=Bo;PARTSTAT=DECLINED:MAILTO:bo@example.com"
+        );
+        // The alarm's recipient is left alone
+        assert_eq!(lines[6], "ATTENDEE:mailto:bo@example.com");
+
+        assert_eq!(
+            set_partstat(event, &["carol@example.com".to_string()], "ACCEPTED"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_reply_object_covers_one_occurrence() {
+        use crate::calendar::tests::make_test_meeting;
+        use chrono::TimeZone;
+
+        let series = "BEGIN:VEVENT\r\nUID:sync\r\n\
+                      DTSTART;TZID=Europe/Berlin:20260105T100000\r\n\
+                      DTEND;TZID=Europe/Berlin:20260105T110000\r\n\
+                      RRULE:FREQ=WEEKLY\r\nEXDATE;TZID=Europe/Berlin:20260112T100000\r\n\
+                      ATTENDEE;PARTSTAT=NEEDS-ACTION:mailto:bo@example.com\r\n\
+                      BEGIN:VALARM\r\nTRIGGER:-PT15M\r\nDURATION:PT5M\r\nEND:VALARM\r\n\
+                      END:VEVENT"
+            .to_string();
+        let emails = vec!["bo@example.com".to_string()];
+        let start = chrono::Utc
+            .with_ymd_and_hms(2026, 1, 19, 9, 0, 0)
+            .unwrap()
+            .with_timezone(&Local);
+        let mut meeting = make_test_meeting(None, None);
+        meeting.uid = format!("sync@{}", start.format("%Y%m%dT%H%M%S"));
+        meeting.start = start;
+        meeting.end = start + chrono::Duration::hours(1);
+
+        // An occurrence that hasn't been detached yet is made into one
+        let (object, mod_type) =
+            reply_object(std::slice::from_ref(&series), &meeting, &emails, "DECLINED").unwrap();
+        assert_eq!(mod_type, "this");
+        assert_eq!(
+            object.split("\r\n").collect::<Vec<_>>(),
+            [
+                "BEGIN:VEVENT",
+                "RECURRENCE-ID:20260119T090000Z",
+                "DTSTART:20260119T090000Z",
+                "DTEND:20260119T100000Z",
+                "UID:sync",
+                "ATTENDEE;PARTSTAT=DECLINED:mailto:bo@example.com",
+                "BEGIN:VALARM",
+                "TRIGGER:-PT15M",
+                "DURATION:PT5M",
+                "END:VALARM",
+                "END:VEVENT",
+            ]
+        );
+
+        // An occurrence that was already detached is updated as it is
+        let moved = "BEGIN:VEVENT\r\nUID:sync\r\n\
+                     RECURRENCE-ID;TZID=Europe/Berlin:20260119T100000\r\n\
+                     DTSTART;TZID=Europe/Berlin:20260119T140000\r\nSUMMARY:Moved\r\n\
+                     ATTENDEE;PARTSTAT=ACCEPTED:mailto:bo@example.com\r\nEND:VEVENT"
+            .to_string();
+        let (object, mod_type) =
+            reply_object(&[series, moved], &meeting, &emails, "DECLINED").unwrap();
+        assert_eq!(mod_type, "this");
+        assert!(object.contains("SUMMARY:Moved\r\nATTENDEE;PARTSTAT=DECLINED:"));
+
+        // A plain event is replied to as a whole
+        let single = "BEGIN:VEVENT\r\nUID:sync\r\nDTSTART:20260119T090000Z\r\n\
+                      ATTENDEE:mailto:bo@example.com\r\nEND:VEVENT"
+            .to_string();
+        let (_, mod_type) = reply_object(&[single], &meeting, &emails, "ACCEPTED").unwrap();
+        assert_eq!(mod_type, "all");
+    }
+
+    // Tests for parse_display_name
+    #[test]
+    fn test_parse_display_name_simple() {
+        let data = "[Data Source]\nDisplayName=Work Calendar\nEnabled=true";
+        assert_eq!(parse_display_name(data), Some("Work Calendar".to_string()));
+    }
+
+    #[test]
+    fn test_parse_display_name_with_sections() {
+        let data = "[Data Source]\nDisplayName=Personal\n[Calendar]\nBackendName=local";
+        assert_eq!(parse_display_name(data), Some("Personal".to_string()));
+    }
+
+    #[test]
+    fn test_parse_display_name_missing() {
+        let data = "[Data Source]\nEnabled=true\n[Calendar]";
+        assert_eq!(parse_display_name(data), None);
+    }
+
+    // Tests for parse_color
+    #[test]
+    fn test_parse_color_hex() {
+        let data = "[Calendar]\nColor=#62a0ea\nBackendName=local";
+        assert_eq!(parse_color(data), Some("#62a0ea".to_string()));
+    }
+
+    #[test]
+    fn test_parse_color_missing() {
+        let data = "[Calendar]\nBackendName=local";
+        assert_eq!(parse_color(data), None);
+    }
+}
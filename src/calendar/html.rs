@@ -0,0 +1,280 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//
+// Reads the HTML descriptions that Outlook and Teams put in `X-ALT-DESC`.
+
+use std::borrow::Cow;
+
+/// Readable text and links taken from an HTML description.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HtmlDescription {
+    /// The text, with block elements on their own lines.
+    pub text: String,
+    /// Targets of the web links, in order and without duplicates.
+    pub links: Vec<String>,
+}
+
+/// Elements whose content is never shown.
+const HIDDEN_ELEMENTS: [&str; 4] = ["head", "script", "style", "title"];
+
+/// Elements that start on a new line.
+const BLOCK_ELEMENTS: [&str; 20] = [
+    "address",
+    "blockquote",
+    "br",
+    "div",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "table",
+    "td",
+    "th",
+    "tr",
+    "ul",
+];
+
+/// Parse an HTML document or fragment into text and links.
+///
+/// Invitation HTML is rarely well-formed, so this is deliberately forgiving:
+/// unknown elements are dropped, unclosed ones are ignored, and a `<` that
+/// doesn't start a tag is kept as text.
+#[must_use]
+pub fn parse_html_description(html: &str) -> HtmlDescription {
+    let mut text = String::new();
+    let mut links: Vec<String> = Vec::new();
+    // Element whose content is being skipped, until its end tag
+    let mut hidden: Option<String> = None;
+
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        if hidden.is_none() {
+            push_text(&mut text, &rest[..start]);
+        }
+        rest = &rest[start..];
+
+        // Comments, including Outlook's conditional ones
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map_or("", |end| &after[end + 3..]);
+            continue;
+        }
+        let starts_tag = rest[1..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!');
+        let Some(end) = tag_end(rest).filter(|_| starts_tag) else {
+            if hidden.is_none() {
+                push_text(&mut text, "<");
+            }
+            rest = &rest[1..];
+            continue;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        if let Some(hidden_name) = &hidden {
+            if closing && name == *hidden_name {
+                hidden = None;
+            }
+            continue;
+        }
+        if !closing && !tag.ends_with('/') && HIDDEN_ELEMENTS.contains(&name.as_str()) {
+            hidden = Some(name);
+            continue;
+        }
+        if BLOCK_ELEMENTS.contains(&name.as_str()) {
+            push_newline(&mut text, name == "br");
+        }
+        if !closing
+            && name == "a"
+            && let Some(href) = attribute(tag, "href")
+            && (href.starts_with("https://") || href.starts_with("http://"))
+            && !links.contains(&href)
+        {
+            links.push(href);
+        }
+    }
+    if hidden.is_none() {
+        push_text(&mut text, rest);
+    }
+
+    HtmlDescription {
+        text: tidy_lines(&text),
+        links,
+    }
+}
+
+/// Index of the `>` that ends the tag at the start of `html`, ignoring any
+/// inside quoted attribute values.
+fn tag_end(html: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in html.char_indices().skip(1) {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Value of an attribute in a tag's source, with entities decoded.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    // Lowercasing ASCII keeps byte offsets the same
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find(name) {
+        let start = from + pos;
+        from = start + name.len();
+        if !lower[..start].ends_with(char::is_whitespace) {
+            continue;
+        }
+        let Some(value) = tag[from..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+            _ => value.split(char::is_whitespace).next().unwrap_or_default(),
+        };
+        return Some(decode_entities(value).trim().to_string());
+    }
+    None
+}
+
+/// Append text, collapsing runs of whitespace as a browser would.
+fn push_text(out: &mut String, raw: &str) {
+    for c in decode_entities(raw).chars() {
+        if !c.is_whitespace() {
+            out.push(c);
+        } else if !out.is_empty() && !out.ends_with([' ', '\n']) {
+            out.push(' ');
+        }
+    }
+}
+
+/// Start a new line. Block elements only do so if the text isn't already at
+/// the start of one, while a line break always does.
+fn push_newline(out: &mut String, always: bool) {
+    out.truncate(out.trim_end_matches(' ').len());
+    if always || !(out.is_empty() || out.ends_with('\n')) {
+        out.push('\n');
+    }
+}
+
+/// Trim each line and keep at most one blank line between paragraphs.
+fn tidy_lines(text: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if !line.is_empty() || lines.last().is_some_and(|last| !last.is_empty()) {
+            lines.push(line);
+        }
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Decode character references like `&amp;`, `&nbsp;` and `&#39;`. Unknown
+/// ones are left as they are.
+fn decode_entities(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| {
+                let name = &rest[1..=end];
+                let c = match name {
+                    "amp" => '&',
+                    "lt" => '<',
+                    "gt" => '>',
+                    "quot" => '"',
+                    "apos" => '\'',
+                    "nbsp" => '\u{a0}',
+                    _ => {
+                        let code = match name.strip_prefix('#') {
+                            Some(hex) if hex.starts_with(['x', 'X']) => {
+                                u32::from_str_radix(&hex[1..], 16).ok()
+                            }
+                            Some(decimal) => decimal.parse().ok(),
+                            None => None,
+                        };
+                        char::from_u32(code?)?
+                    }
+                };
+                Some((c, end + 2))
+            });
+        let (c, len) = decoded.unwrap_or(('&', 1));
+        out.push(c);
+        rest = &rest[len..];
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_teams_invitation() {
+        let html = "<html><head><meta http-equiv=\"Content-Type\" content=\"text/html\">\
+            <style>p { margin: 0; }</style></head><body>\
+            <!--[if mso]><p>Outlook only</p><![endif]-->\
+            <div><p>Hi all,&nbsp;see you   there.</p>\
+            <p><a href=\"https://teams.microsoft.com/l/meetup-join/19%3ameeting_abc%40thread.v2/0?context=%7b%7d&amp;x=1\" \
+            target=\"_blank\">Join the meeting now</a><br>\
+            Meeting ID: 123 456 789<br>Passcode: a1b2</p>\
+            <p><a href='https://aka.ms/JoinTeamsMeeting'>Help</a> | \
+            <a href=mailto:someone@example.com>Mail</a> | \
+            <a href=\"https://aka.ms/JoinTeamsMeeting\">Help again</a></p>\
+            </div></body></html>";
+        let parsed = parse_html_description(html);
+        assert_eq!(
+            parsed.text,
+            "Hi all, see you there.\n\
+             Join the meeting now\n\
+             Meeting ID: 123 456 789\n\
+             Passcode: a1b2\n\
+             Help | Mail | Help again"
+        );
+        assert_eq!(
+            parsed.links,
+            vec![
+                "https://teams.microsoft.com/l/meetup-join/19%3ameeting_abc%40thread.v2/0?context=%7b%7d&x=1"
+                    .to_string(),
+                "https://aka.ms/JoinTeamsMeeting".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_html_is_forgiving() {
+        let parsed = parse_html_description("a < b &unknown; &#x41;&#66; <b>bold<p>next");
+        assert_eq!(parsed.text, "a < b &unknown; AB bold\nnext");
+        assert!(parsed.links.is_empty());
+    }
+}
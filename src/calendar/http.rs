@@ -0,0 +1,291 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//
+// Minimal HTTP client for network calendar sources, built on the `curl` command.
+
+use tokio::io::AsyncWriteExt;
+
+/// How long a single request may take before it is abandoned.
+const REQUEST_TIMEOUT_SECS: u32 = 30;
+
+/// Username and password for HTTP basic authentication.
+#[derive(Clone)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An HTTP request to be sent with [`Request::send`].
+#[derive(Debug, Clone)]
+pub struct Request {
+    method: &'static str,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+    credentials: Option<Credentials>,
+}
+
+/// The final response to a request, after following redirects.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    headers: Vec<(String, String)>,
+    pub body: String,
+    /// URL the response came from, which differs from the request URL after a redirect.
+    pub url: String,
+}
+
+impl Request {
+    #[must_use]
+    pub fn new(method: &'static str, url: &str) -> Self {
+        Self {
+            method,
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: None,
+            credentials: None,
+        }
+    }
+
+    #[must_use]
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    #[must_use]
+    pub fn body(mut self, body: String) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    #[must_use]
+    pub fn credentials(mut self, credentials: Option<Credentials>) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Send the request, returning `None` if no response was received.
+    ///
+    /// Everything, including the URL and credentials, is passed to curl on
+    /// stdin so that it doesn't show up in the process list.
+    pub async fn send(self) -> Option<Response> {
+        let mut child = tokio::process::Command::new("curl")
+            .args([
+                "--silent",
+                "--location",
+                "--dump-header",
+                "-",
+                "--write-out",
+            ])
+            .arg("\n%{url_effective}")
+            .args([
+                "--max-time",
+                &REQUEST_TIMEOUT_SECS.to_string(),
+                "--config",
+                "-",
+            ])
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .ok()?;
+
+        let mut stdin = child.stdin.take()?;
+        stdin.write_all(self.curl_config().as_bytes()).await.ok()?;
+        drop(stdin);
+
+        let output = child.wait_with_output().await.ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        parse_curl_output(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Build the curl config file describing this request.
+    fn curl_config(&self) -> String {
+        let mut config = format!(
+            "url = {}\nrequest = {}\n",
+            quote(&self.url),
+            quote(self.method)
+        );
+        for (name, value) in &self.headers {
+            config.push_str(&format!(
+                "header = {}\n",
+                quote(&format!("{name}: {value}"))
+            ));
+        }
+        if let Some(body) = &self.body {
+            config.push_str(&format!("data-raw = {}\n", quote(body)));
+        }
+        if let Some(credentials) = &self.credentials {
+            let user = format!("{}:{}", credentials.username, credentials.password);
+            config.push_str(&format!("user = {}\n", quote(&user)));
+        }
+        config
+    }
+}
+
+impl Response {
+    /// Value of a response header, matched case-insensitively.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Quote a value for a curl config file.
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{escaped}\"")
+}
+
+/// Split curl's output into the last response's status, headers and body.
+///
+/// With `--dump-header -` curl writes the headers of every response it saw
+/// (redirects, `100 Continue`) before the body, and `--write-out` appends the
+/// effective URL on a final line.
+fn parse_curl_output(output: &str) -> Option<Response> {
+    let (rest, url) = output.rsplit_once('\n')?;
+
+    let mut rest = rest;
+    let mut status = None;
+    let mut headers = Vec::new();
+    while rest.starts_with("HTTP/") {
+        let (block, body) = rest.split_once("\r\n\r\n").unwrap_or((rest, ""));
+        let mut lines = block.lines();
+        status = lines
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|code| code.parse().ok());
+        headers = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect();
+        rest = body;
+    }
+
+    Some(Response {
+        status: status?,
+        headers,
+        body: rest.to_string(),
+        url: url.to_string(),
+    })
+}
+
+/// A minimal local HTTP server for testing network sources.
+///
+/// Each connection serves one request. `respond` gets the request head
+/// (request line and headers) and body, and returns the full HTTP response.
+/// Returns the server's base URL.
+#[cfg(test)]
+pub async fn serve(respond: fn(&str, &str) -> String) -> String {
+    use tokio::io::AsyncReadExt;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            // Read the head, then as much body as Content-Length says
+            let (head, body) = loop {
+                let n = stream.read(&mut buf).await.unwrap_or(0);
+                if n == 0 {
+                    break (String::new(), String::new());
+                }
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_ascii_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break (head.to_string(), body.to_string());
+                    }
+                }
+            };
+            let _ = stream.write_all(respond(&head, &body).as_bytes()).await;
+        }
+    });
+    format!("http://{addr}/")
+}
+
+/// Build a complete HTTP response for [`serve`].
+#[cfg(test)]
+pub fn test_response(status: &str, headers: &[(&str, &str)], body: &str) -> String {
+    let mut response = format!("HTTP/1.1 {status}\r\n");
+    for (name, value) in headers {
+        response.push_str(&format!("{name}: {value}\r\n"));
+    }
+    response.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    ));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_curl_output_after_redirect() {
+        let output = "HTTP/1.1 301 Moved Permanently\r\n\
+                      Location: /dav/\r\n\
+                      \r\n\
+                      HTTP/1.1 207 Multi-Status\r\n\
+                      Content-Type: application/xml\r\n\
+                      ETag: \"abc\"\r\n\
+                      \r\n\
+                      <multistatus/>\n\
+                      https://example.com/dav/";
+        let response = parse_curl_output(output).unwrap();
+        assert_eq!(response.status, 207);
+        assert_eq!(response.header("etag"), Some("\"abc\""));
+        assert_eq!(response.header("Location"), None);
+        assert_eq!(response.body, "<multistatus/>");
+        assert_eq!(response.url, "https://example.com/dav/");
+    }
+
+    #[test]
+    fn test_curl_config_quotes_values() {
+        let config = Request::new("REPORT", "https://example.com/cal/")
+            .header("Depth", "1")
+            .body("<a b=\"c\">\n</a>".to_string())
+            .credentials(Some(Credentials {
+                username: "me".to_string(),
+                password: "p\\w\"d".to_string(),
+            }))
+            .curl_config();
+        assert_eq!(
+            config,
+            "url = \"https://example.com/cal/\"\n\
+             request = \"REPORT\"\n\
+             header = \"Depth: 1\"\n\
+             data-raw = \"<a b=\\\"c\\\">\\n</a>\"\n\
+             user = \"me:p\\\\w\\\"d\"\n"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//
+// Calendar source that reads plain `.ics` files and folders of them from disk.
+
+use super::{
+    CalendarError, CalendarHeader, CalendarInfo, CalendarSource, FetchWindow, MeetingQuery,
+    QueryWindow, SourceFetch, expand_home, fallback_color, is_ics_file, list_ics_files,
+    parse_calendar_header,
+};
+use chrono::Local;
+use futures_util::future::BoxFuture;
+use std::path::{Path, PathBuf};
+
+/// Prefix for calendar UIDs from this source, so they can't collide with EDS UIDs.
+const UID_PREFIX: &str = "ics:";
+
+/// Calendar source backed by `.ics` files on disk.
+///
+/// Each configured path is either a single `.ics` file or a folder of them,
+/// and shows up as one calendar. Paths are watched with inotify so edits
+/// appear without waiting for the next poll.
+#[derive(Debug, Default)]
+pub struct IcsFileSource {
+    paths: Vec<PathBuf>,
+}
+
+impl IcsFileSource {
+    /// Create a source for the given paths. A leading `~/` is expanded to the
+    /// home directory and blank entries are ignored.
+    #[must_use]
+    pub fn new(paths: &[String]) -> Self {
+        Self {
+            paths: paths
+                .iter()
+                .map(|p| p.trim())
+                .filter(|p| !p.is_empty())
+                .map(expand_home)
+                .collect(),
+        }
+    }
+
+    /// Paths whose calendars are enabled (all of them if `enabled_uids` is empty).
+    fn enabled_paths(&self, enabled_uids: &[String]) -> Vec<PathBuf> {
+        self.paths
+            .iter()
+            .filter(|p| enabled_uids.is_empty() || enabled_uids.contains(&calendar_uid(p)))
+            .cloned()
+            .collect()
+    }
+}
+
+impl CalendarSource for IcsFileSource {
+    fn list_calendars(&self) -> BoxFuture<'_, Vec<CalendarInfo>> {
+        Box::pin(async {
+            let mut calendars = Vec::new();
+            for path in &self.paths {
+                if let Some(info) = read_calendar_info(path).await {
+                    calendars.push(info);
+                }
+            }
+            calendars
+        })
+    }
+
+    fn fetch_meetings<'a>(&'a self, query: &'a MeetingQuery) -> BoxFuture<'a, SourceFetch> {
+        Box::pin(async move {
+            let user_emails: Vec<String> = query
+                .additional_emails
+                .iter()
+                .filter(|e| !e.trim().is_empty())
+                .cloned()
+                .collect();
+
+            let window = FetchWindow::new(Local::now(), query.window);
+
+            let mut fetch = SourceFetch::default();
+            for path in self.enabled_paths(&query.enabled_uids) {
+                match read_ics_files(&path).await {
+                    Ok(ics_objects) => {
+                        fetch.add_calendar(
+                            &ics_objects,
+                            &calendar_uid(&path),
+                            &window,
+                            &user_emails,
+                        );
+                    }
+                    Err(error) => {
+                        fetch.errors.insert(calendar_uid(&path), error);
+                    }
+                }
+            }
+
+            // Unlike EDS, files aren't pre-filtered to the query window
+            fetch.meetings.retain(|(_, m)| m.start < window.end);
+            fetch
+        })
+    }
+
+    fn refresh<'a>(&'a self, _enabled_uids: &'a [String]) -> BoxFuture<'a, ()> {
+        // Files are always read fresh, so there is nothing to sync
+        Box::pin(async {})
+    }
+
+    fn watch_changes(
+        &self,
+        enabled_uids: Vec<String>,
+        _window: QueryWindow,
+        sender: tokio::sync::mpsc::Sender<()>,
+    ) -> BoxFuture<'static, ()> {
+        Box::pin(watch_paths(self.enabled_paths(&enabled_uids), sender))
+    }
+}
+
+/// Calendar UID for a configured path.
+fn calendar_uid(path: &Path) -> String {
+    format!("{UID_PREFIX}{}", path.display())
+}
+
+/// Build the `CalendarInfo` for a configured path, or `None` if it doesn't exist.
+async fn read_calendar_info(path: &Path) -> Option<CalendarInfo> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+
+    // Single files can name and color themselves; folders use their own name
+    let header = if metadata.is_file() {
+        tokio::fs::read_to_string(path)
+            .await
+            .map(|contents| parse_calendar_header(&contents))
+            .unwrap_or_default()
+    } else {
+        CalendarHeader::default()
+    };
+
+    let uid = calendar_uid(path);
+    let display_name = header.name.unwrap_or_else(|| {
+        path.file_stem().map_or_else(
+            || path.display().to_string(),
+            |s| s.to_string_lossy().to_string(),
+        )
+    });
+    let color = header.color.or_else(|| Some(fallback_color(&uid)));
+    let last_synced = last_modified(path, &metadata).await;
+
+    Some(CalendarInfo {
+        uid,
+        display_name,
+        color,
+        last_synced,
+        backend: Some("ics".to_string()),
+    })
+}
+
+/// Latest modification time of a file, or of the `.ics` files in a folder,
+/// as an RFC 3339 timestamp.
+async fn last_modified(path: &Path, metadata: &std::fs::Metadata) -> Option<String> {
+    let mut latest = metadata.modified().ok();
+    if metadata.is_dir() {
+        for file in list_ics_files(path).await {
+            if let Ok(modified) = tokio::fs::metadata(&file).await.and_then(|m| m.modified()) {
+                latest = latest.max(Some(modified));
+            }
+        }
+    }
+    latest.map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339())
+}
+
+/// Read the contents of a `.ics` file, or of every `.ics` file in a folder.
+/// Files in a folder that can't be read are skipped.
+async fn read_ics_files(path: &Path) -> Result<Vec<String>, CalendarError> {
+    let unreadable = |e: std::io::Error| CalendarError::Unreadable(e.to_string());
+    if !tokio::fs::metadata(path)
+        .await
+        .map_err(unreadable)?
+        .is_dir()
+    {
+        return Ok(vec![
+            tokio::fs::read_to_string(path).await.map_err(unreadable)?,
+        ]);
+    }
+
+    let files = list_ics_files(path).await;
+    let mut contents = Vec::with_capacity(files.len());
+    for file in files {
+        if let Ok(text) = tokio::fs::read_to_string(&file).await {
+            contents.push(text);
+        }
+    }
+    Ok(contents)
+}
+
+/// Watch the given paths for changes and notify the channel.
+///
+/// Single files are watched through their parent folder, because most editors
+/// and sync tools replace files rather than writing them in place.
+async fn watch_paths(paths: Vec<PathBuf>, sender: tokio::sync::mpsc::Sender<()>) {
+    use notify::{EventKind, RecursiveMode, Watcher};
+
+    if paths.is_empty() {
+        return;
+    }
+
+    let watched = paths.clone();
+    let Ok(mut watcher) =
+        notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+            let Ok(event) = result else {
+                return;
+            };
+            // Ignore reads (including our own), only react to writes
+            if !matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) {
+                return;
+            }
+            let relevant = event.paths.iter().any(|changed| {
+                watched.iter().any(|path| {
+                    changed == path || (changed.parent() == Some(path) && is_ics_file(changed))
+                })
+            });
+            if relevant {
+                let _ = sender.try_send(());
+            }
+        })
+    else {
+        return;
+    };
+
+    let mut watching_any = false;
+    for path in &paths {
+        let target = if path.is_dir() {
+            path.as_path()
+        } else {
+            match path.parent() {
+                Some(parent) => parent,
+                None => continue,
+            }
+        };
+        if watcher.watch(target, RecursiveMode::NonRecursive).is_ok() {
+            watching_any = true;
+        }
+    }
+
+    if watching_any {
+        // Keep the watcher alive until this task is cancelled
+        std::future::pending::<()>().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::tests::{event_ics, temp_dir};
+
+    #[test]
+    fn test_ics_source_reads_folder() {
+        let dir = temp_dir("ics");
+        std::fs::write(dir.join("a.ics"), event_ics("a", "Planning")).unwrap();
+        std::fs::write(dir.join("b.ics"), event_ics("b", "Retro")).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a calendar").unwrap();
+
+        let source = IcsFileSource::new(&[dir.display().to_string()]);
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        let calendars = rt.block_on(source.list_calendars());
+        assert_eq!(calendars.len(), 1);
+        assert_eq!(calendars[0].uid, calendar_uid(&dir));
+        assert!(calendars[0].color.is_some());
+
+        let meetings = rt
+            .block_on(source.fetch_meetings(&MeetingQuery::default()))
+            .meetings;
+        let mut titles: Vec<&str> = meetings.iter().map(|(_, m)| m.title.as_str()).collect();
+        titles.sort_unstable();
+        assert_eq!(titles, vec!["Planning", "Retro"]);
+        assert!(
+            meetings
+                .iter()
+                .all(|(_, m)| m.calendar_uid == calendar_uid(&dir))
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // Once the folder is gone, the calendar reports why it can't be read
+        let fetch = rt.block_on(source.fetch_meetings(&MeetingQuery::default()));
+        assert!(fetch.is_failure());
+        assert!(matches!(
+            fetch.errors.get(&calendar_uid(&dir)),
+            Some(CalendarError::Unreadable(_))
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//
+// Password lookup through the freedesktop Secret Service (GNOME Keyring, KWallet, ...).
+
+use futures_util::StreamExt;
+use std::collections::HashMap;
+use zbus::{Connection, zvariant};
+
+/// How long to wait for the user to answer an unlock prompt.
+const UNLOCK_PROMPT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Look up the password stored under the given attributes.
+///
+/// If the matching item is locked, the Secret Service is asked to unlock it,
+/// which may show a prompt. Returns `None` if there is no Secret Service, no
+/// matching item, or the user dismisses the prompt.
+pub async fn lookup_password(attributes: &HashMap<&str, &str>) -> Option<String> {
+    use zvariant::{OwnedObjectPath, OwnedValue};
+
+    let conn = Connection::session().await.ok()?;
+    let service = zbus::Proxy::new(
+        &conn,
+        "org.freedesktop.secrets",
+        "/org/freedesktop/secrets",
+        "org.freedesktop.Secret.Service",
+    )
+    .await
+    .ok()?;
+
+    // A "plain" session transfers the secret unencrypted over the session bus
+    let reply = service
+        .call_method("OpenSession", &("plain", zvariant::Value::from("")))
+        .await
+        .ok()?;
+    let (_output, session) = reply.body::<(OwnedValue, OwnedObjectPath)>().ok()?;
+
+    let (mut unlocked, locked) = search_items(&service, attributes).await?;
+    if unlocked.is_empty() && !locked.is_empty() && unlock(&conn, &service, &locked).await {
+        (unlocked, _) = search_items(&service, attributes).await?;
+    }
+    let item = unlocked.into_iter().next()?;
+
+    let reply = service
+        .call_method("GetSecrets", &(vec![&item], &session))
+        .await
+        .ok()?;
+    let secrets = reply
+        .body::<HashMap<OwnedObjectPath, (OwnedObjectPath, Vec<u8>, Vec<u8>, String)>>()
+        .ok()?;
+    let (_session, _params, value, _content_type) = secrets.into_values().next()?;
+
+    String::from_utf8(value).ok()
+}
+
+/// Find items by attributes, returning the unlocked and locked matches.
+async fn search_items(
+    service: &zbus::Proxy<'_>,
+    attributes: &HashMap<&str, &str>,
+) -> Option<(
+    Vec<zvariant::OwnedObjectPath>,
+    Vec<zvariant::OwnedObjectPath>,
+)> {
+    let reply = service
+        .call_method("SearchItems", &(attributes,))
+        .await
+        .ok()?;
+    reply.body().ok()
+}
+
+/// Unlock the given items, prompting the user if needed.
+/// Returns whether the items were unlocked.
+async fn unlock(
+    conn: &Connection,
+    service: &zbus::Proxy<'_>,
+    items: &[zvariant::OwnedObjectPath],
+) -> bool {
+    use zvariant::{OwnedObjectPath, OwnedValue};
+
+    let Ok(reply) = service.call_method("Unlock", &(items,)).await else {
+        return false;
+    };
+    let Ok((_unlocked, prompt)) = reply.body::<(Vec<OwnedObjectPath>, OwnedObjectPath)>() else {
+        return false;
+    };
+
+    // "/" means everything was unlocked without asking the user
+    if prompt.as_str() == "/" {
+        return true;
+    }
+
+    let Ok(prompt_proxy) = zbus::Proxy::new(
+        conn,
+        "org.freedesktop.secrets",
+        prompt.as_str(),
+        "org.freedesktop.Secret.Prompt",
+    )
+    .await
+    else {
+        return false;
+    };
+    let Ok(mut completed) = prompt_proxy.receive_signal("Completed").await else {
+        return false;
+    };
+    if prompt_proxy.call_method("Prompt", &("",)).await.is_err() {
+        return false;
+    }
+
+    let Ok(Some(signal)) = tokio::time::timeout(UNLOCK_PROMPT_TIMEOUT, completed.next()).await
+    else {
+        return false;
+    };
+    signal
+        .body::<(bool, OwnedValue)>()
+        .is_ok_and(|(dismissed, _result)| !dismissed)
+}
@@ -0,0 +1,425 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//
+// Calendar source for vdir collections, as written by vdirsyncer and read by khal.
+
+use super::{
+    CalendarInfo, CalendarSource, FetchWindow, MeetingQuery, QueryWindow, SourceFetch, expand_home,
+    fallback_color, is_ics_file, list_ics_files, normalize_hex_color,
+};
+use chrono::Local;
+use futures_util::future::BoxFuture;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Prefix for calendar UIDs from this source, so they can't collide with EDS UIDs.
+const UID_PREFIX: &str = "vdir:";
+
+/// Metadata file holding a collection's display name.
+const DISPLAYNAME_FILE: &str = "displayname";
+
+/// Metadata file holding a collection's color.
+const COLOR_FILE: &str = "color";
+
+/// Calendar source backed by vdir collections.
+///
+/// A vdir collection is a folder with one `.ics` file per event, plus optional
+/// `displayname` and `color` files. Each configured path is either a collection
+/// or a folder of collections (like vdirsyncer's storage path); each collection
+/// shows up as one calendar.
+///
+/// File contents are cached by modification time, so a fetch after a sync only
+/// re-reads the events that changed.
+#[derive(Debug, Default)]
+pub struct VdirSource {
+    roots: Vec<PathBuf>,
+    files: Mutex<HashMap<PathBuf, CachedFile>>,
+}
+
+/// An event file as last read from disk.
+#[derive(Debug)]
+struct CachedFile {
+    modified: SystemTime,
+    contents: String,
+}
+
+impl VdirSource {
+    /// Create a source for the given paths. A leading `~/` is expanded to the
+    /// home directory and blank entries are ignored.
+    #[must_use]
+    pub fn new(paths: &[String]) -> Self {
+        Self {
+            roots: paths
+                .iter()
+                .map(|p| p.trim())
+                .filter(|p| !p.is_empty())
+                .map(expand_home)
+                .collect(),
+            files: Mutex::default(),
+        }
+    }
+
+    /// All collections under the configured paths.
+    async fn collections(&self) -> Vec<PathBuf> {
+        let mut collections = Vec::new();
+        for root in &self.roots {
+            collections.extend(find_collections(root).await);
+        }
+        collections
+    }
+
+    /// Collections whose calendars are enabled (all of them if `enabled_uids` is empty).
+    async fn enabled_collections(&self, enabled_uids: &[String]) -> Vec<PathBuf> {
+        self.collections()
+            .await
+            .into_iter()
+            .filter(|c| enabled_uids.is_empty() || enabled_uids.contains(&calendar_uid(c)))
+            .collect()
+    }
+
+    /// Read the event files in a collection, reusing cached contents for
+    /// files that haven't been modified since they were last read.
+    async fn read_collection(&self, collection: &Path) -> Vec<String> {
+        let mut contents = Vec::new();
+        let mut seen = Vec::new();
+
+        for file in list_ics_files(collection).await {
+            let Ok(modified) = tokio::fs::metadata(&file).await.and_then(|m| m.modified()) else {
+                continue;
+            };
+
+            let cached = self.files.lock().ok().and_then(|files| {
+                files
+                    .get(&file)
+                    .filter(|f| f.modified == modified)
+                    .map(|f| f.contents.clone())
+            });
+
+            let text = match cached {
+                Some(text) => text,
+                None => {
+                    let Ok(text) = tokio::fs::read_to_string(&file).await else {
+                        continue;
+                    };
+                    if let Ok(mut files) = self.files.lock() {
+                        files.insert(
+                            file.clone(),
+                            CachedFile {
+                                modified,
+                                contents: text.clone(),
+                            },
+                        );
+                    }
+                    text
+                }
+            };
+
+            contents.push(text);
+            seen.push(file);
+        }
+
+        // Forget events that were deleted from this collection
+        if let Ok(mut files) = self.files.lock() {
+            files.retain(|path, _| path.parent() != Some(collection) || seen.contains(path));
+        }
+
+        contents
+    }
+}
+
+impl CalendarSource for VdirSource {
+    fn list_calendars(&self) -> BoxFuture<'_, Vec<CalendarInfo>> {
+        Box::pin(async {
+            let mut calendars = Vec::new();
+            for collection in self.collections().await {
+                calendars.push(read_collection_info(&collection).await);
+            }
+            calendars
+        })
+    }
+
+    fn fetch_meetings<'a>(&'a self, query: &'a MeetingQuery) -> BoxFuture<'a, SourceFetch> {
+        Box::pin(async move {
+            let user_emails: Vec<String> = query
+                .additional_emails
+                .iter()
+                .filter(|e| !e.trim().is_empty())
+                .cloned()
+                .collect();
+
+            let window = FetchWindow::new(Local::now(), query.window);
+
+            let mut fetch = SourceFetch::default();
+            for collection in self.enabled_collections(&query.enabled_uids).await {
+                let ics_objects = self.read_collection(&collection).await;
+                fetch.add_calendar(
+                    &ics_objects,
+                    &calendar_uid(&collection),
+                    &window,
+                    &user_emails,
+                );
+            }
+
+            // Unlike EDS, files aren't pre-filtered to the query window
+            fetch.meetings.retain(|(_, m)| m.start < window.end);
+            fetch
+        })
+    }
+
+    fn refresh<'a>(&'a self, _enabled_uids: &'a [String]) -> BoxFuture<'a, ()> {
+        // Syncing is up to vdirsyncer, we only read what it writes
+        Box::pin(async {})
+    }
+
+    fn watch_changes(
+        &self,
+        _enabled_uids: Vec<String>,
+        _window: QueryWindow,
+        sender: tokio::sync::mpsc::Sender<()>,
+    ) -> BoxFuture<'static, ()> {
+        Box::pin(watch_roots(self.roots.clone(), is_event_change, sender))
+    }
+
+    fn watch_calendar_list(&self, sender: tokio::sync::mpsc::Sender<()>) -> BoxFuture<'static, ()> {
+        Box::pin(watch_roots(
+            self.roots.clone(),
+            is_collection_change,
+            sender,
+        ))
+    }
+}
+
+/// Calendar UID for a collection folder.
+fn calendar_uid(collection: &Path) -> String {
+    format!("{UID_PREFIX}{}", collection.display())
+}
+
+/// Whether a folder is a vdir collection: it has metadata files or events.
+async fn is_collection(dir: &Path) -> bool {
+    if tokio::fs::metadata(dir.join(DISPLAYNAME_FILE))
+        .await
+        .is_ok()
+        || tokio::fs::metadata(dir.join(COLOR_FILE)).await.is_ok()
+    {
+        return true;
+    }
+    !list_ics_files(dir).await.is_empty()
+}
+
+/// Find the collections at a configured path: the path itself if it is a
+/// collection, otherwise its non-hidden subfolders that are collections.
+async fn find_collections(root: &Path) -> Vec<PathBuf> {
+    if !root.is_dir() {
+        return Vec::new();
+    }
+    if is_collection(root).await {
+        return vec![root.to_path_buf()];
+    }
+
+    let Ok(mut entries) = tokio::fs::read_dir(root).await else {
+        return Vec::new();
+    };
+
+    let mut collections = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        // vdirsyncer keeps its own state in hidden folders
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if !hidden && path.is_dir() && is_collection(&path).await {
+            collections.push(path);
+        }
+    }
+    collections.sort();
+    collections
+}
+
+/// Read a collection's `displayname` and `color` files into a `CalendarInfo`.
+async fn read_collection_info(collection: &Path) -> CalendarInfo {
+    let uid = calendar_uid(collection);
+
+    let display_name = read_metadata(collection, DISPLAYNAME_FILE)
+        .await
+        .unwrap_or_else(|| {
+            collection.file_name().map_or_else(
+                || collection.display().to_string(),
+                |s| s.to_string_lossy().to_string(),
+            )
+        });
+    let color = read_metadata(collection, COLOR_FILE)
+        .await
+        .and_then(|c| normalize_hex_color(&c))
+        .or_else(|| Some(fallback_color(&uid)));
+
+    // The newest event file tells us when vdirsyncer last wrote to the collection
+    let mut latest = None;
+    for file in list_ics_files(collection).await {
+        if let Ok(modified) = tokio::fs::metadata(&file).await.and_then(|m| m.modified()) {
+            latest = latest.max(Some(modified));
+        }
+    }
+    let last_synced = latest.map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339());
+
+    CalendarInfo {
+        uid,
+        display_name,
+        color,
+        last_synced,
+        backend: Some("vdir".to_string()),
+    }
+}
+
+/// Read a single-line metadata file from a collection, if present and non-empty.
+async fn read_metadata(collection: &Path, name: &str) -> Option<String> {
+    let contents = tokio::fs::read_to_string(collection.join(name))
+        .await
+        .ok()?;
+    let value = contents.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Whether a filesystem event changed an event file.
+fn is_event_change(event: &notify::Event) -> bool {
+    event.paths.iter().any(|p| is_ics_file(p))
+}
+
+/// Whether a filesystem event may have added, removed or renamed a collection.
+fn is_collection_change(event: &notify::Event) -> bool {
+    use notify::EventKind;
+
+    event.paths.iter().any(|p| {
+        let is_metadata = p
+            .file_name()
+            .is_some_and(|name| name == DISPLAYNAME_FILE || name == COLOR_FILE);
+        let is_folder =
+            !is_ics_file(p) && matches!(event.kind, EventKind::Create(_) | EventKind::Remove(_));
+        is_metadata || is_folder
+    })
+}
+
+/// Watch the configured paths recursively, notifying the channel for every
+/// write that `is_relevant` accepts.
+async fn watch_roots(
+    roots: Vec<PathBuf>,
+    is_relevant: fn(&notify::Event) -> bool,
+    sender: tokio::sync::mpsc::Sender<()>,
+) {
+    use notify::{EventKind, RecursiveMode, Watcher};
+
+    if roots.is_empty() {
+        return;
+    }
+
+    let Ok(mut watcher) =
+        notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+            let Ok(event) = result else {
+                return;
+            };
+            // Ignore reads (including our own), only react to writes
+            if !matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) {
+                return;
+            }
+            if is_relevant(&event) {
+                let _ = sender.try_send(());
+            }
+        })
+    else {
+        return;
+    };
+
+    let mut watching_any = false;
+    for root in &roots {
+        if watcher.watch(root, RecursiveMode::Recursive).is_ok() {
+            watching_any = true;
+        }
+    }
+
+    if watching_any {
+        // Keep the watcher alive until this task is cancelled
+        std::future::pending::<()>().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::tests::{event_ics, temp_dir};
+
+    #[test]
+    fn test_vdir_collections_with_metadata() {
+        let root = temp_dir("vdir-metadata");
+        let work = root.join("work");
+        let home = root.join("home");
+        std::fs::create_dir_all(&work).unwrap();
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::create_dir_all(root.join(".status")).unwrap();
+        std::fs::write(work.join(DISPLAYNAME_FILE), "Work\n").unwrap();
+        std::fs::write(work.join(COLOR_FILE), "#3A87ADFF\n").unwrap();
+        std::fs::write(work.join("standup.ics"), event_ics("standup", "Standup")).unwrap();
+        std::fs::write(home.join("dentist.ics"), event_ics("dentist", "Dentist")).unwrap();
+
+        let source = VdirSource::new(&[root.display().to_string()]);
+        let rt = tokio::runtime::Runtime::new().unwrap();
+
+        let calendars = rt.block_on(source.list_calendars());
+        assert_eq!(calendars.len(), 2);
+        let home_info = &calendars[0];
+        let work_info = &calendars[1];
+        assert_eq!(home_info.display_name, "home");
+        assert!(home_info.color.is_some());
+        assert_eq!(work_info.display_name, "Work");
+        assert_eq!(work_info.color.as_deref(), Some("#3a87ad"));
+        assert_eq!(work_info.uid, calendar_uid(&work));
+
+        // Only the enabled collection is read
+        let query = MeetingQuery {
+            enabled_uids: vec![calendar_uid(&work)],
+            ..Default::default()
+        };
+        let meetings = rt.block_on(source.fetch_meetings(&query)).meetings;
+        assert_eq!(meetings.len(), 1);
+        assert_eq!(meetings[0].1.title, "Standup");
+        assert_eq!(meetings[0].1.calendar_uid, calendar_uid(&work));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_vdir_rereads_changed_and_removed_files() {
+        let collection = temp_dir("vdir-changes");
+        let planning = collection.join("planning.ics");
+        let retro = collection.join("retro.ics");
+        std::fs::write(&planning, event_ics("planning", "Planning")).unwrap();
+        std::fs::write(&retro, event_ics("retro", "Retro")).unwrap();
+
+        let source = VdirSource::new(&[collection.display().to_string()]);
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let titles = |source: &VdirSource| {
+            let fetch = rt.block_on(source.fetch_meetings(&MeetingQuery::default()));
+            let mut titles: Vec<String> =
+                fetch.meetings.into_iter().map(|(_, m)| m.title).collect();
+            titles.sort_unstable();
+            titles
+        };
+
+        assert_eq!(titles(&source), vec!["Planning", "Retro"]);
+
+        // Rewrite one event with a newer modification time and delete the other
+        std::fs::write(&planning, event_ics("planning", "Planning (moved)")).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&planning)
+            .unwrap()
+            .set_modified(SystemTime::now() + std::time::Duration::from_secs(60))
+            .unwrap();
+        std::fs::remove_file(&retro).unwrap();
+
+        assert_eq!(titles(&source), vec!["Planning (moved)"]);
+        assert_eq!(source.files.lock().unwrap().len(), 1);
+
+        std::fs::remove_dir_all(&collection).unwrap();
+    }
+}
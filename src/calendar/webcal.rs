@@ -0,0 +1,436 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//
+// Calendar source for read-only iCalendar subscriptions (webcal:// or https:// URLs).
+
+use super::http::Request;
+use super::{
+    CalendarError, CalendarInfo, CalendarSource, FetchWindow, MeetingQuery, QueryWindow,
+    SourceFetch, fallback_color, parse_calendar_header,
+};
+use chrono::{DateTime, Local, Utc};
+use futures_util::future::BoxFuture;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use tokio::sync::Mutex;
+
+/// Prefix for calendar UIDs from this source, so they can't collide with EDS UIDs.
+const UID_PREFIX: &str = "webcal:";
+
+/// Folder under the XDG cache dir where the last good copy of each feed is kept.
+const CACHE_PREFIX: &str = "com.dangrover.next-meeting-app";
+
+/// Calendar source for subscribed iCalendar feeds.
+///
+/// Each URL is one read-only calendar. Feeds are downloaded at most once per
+/// poll interval using conditional requests (`ETag` / `Last-Modified`), and
+/// the last good copy is kept on disk so calendars show up immediately after
+/// a restart and survive the server being unreachable.
+#[derive(Debug)]
+pub struct WebcalSource {
+    urls: Vec<String>,
+    poll_interval: Duration,
+    /// Folder for the on-disk copies, or `None` to keep feeds in memory only.
+    cache_dir: Option<PathBuf>,
+    feeds: Mutex<HashMap<String, Feed>>,
+}
+
+/// The last good copy of a feed and what's needed to ask whether it changed.
+#[derive(Debug, Default, Clone)]
+struct Feed {
+    contents: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// When the server was last asked for the feed, or `None` to ask on the next fetch.
+    checked_at: Option<SystemTime>,
+    /// When the feed contents last changed.
+    updated_at: Option<DateTime<Utc>>,
+    /// Why the last download failed, if it did. Not saved to disk.
+    error: Option<CalendarError>,
+}
+
+impl WebcalSource {
+    /// Create a source for the given feed URLs, polled every `poll_interval_minutes`.
+    /// Blank entries are ignored.
+    #[must_use]
+    pub fn new(urls: &[String], poll_interval_minutes: u8) -> Self {
+        Self {
+            urls: urls
+                .iter()
+                .map(|u| u.trim())
+                .filter(|u| !u.is_empty())
+                .map(String::from)
+                .collect(),
+            poll_interval: Duration::from_secs(u64::from(poll_interval_minutes.max(1)) * 60),
+            cache_dir: xdg::BaseDirectories::with_prefix(CACHE_PREFIX)
+                .place_cache_file("webcal")
+                .ok(),
+            feeds: Mutex::default(),
+        }
+    }
+
+    /// URLs whose calendars are enabled (all of them if `enabled_uids` is empty).
+    fn enabled_urls(&self, enabled_uids: &[String]) -> Vec<&String> {
+        self.urls
+            .iter()
+            .filter(|u| enabled_uids.is_empty() || enabled_uids.contains(&calendar_uid(u)))
+            .collect()
+    }
+
+    /// The current copy of a feed, from memory or disk.
+    async fn feed(&self, url: &str) -> Feed {
+        let mut feeds = self.feeds.lock().await;
+        if let Some(feed) = feeds.get(url) {
+            return feed.clone();
+        }
+        let feed = self.load_from_disk(url).await.unwrap_or_default();
+        feeds.insert(url.to_string(), feed.clone());
+        feed
+    }
+
+    /// Download a feed if the poll interval has passed, keeping the old copy
+    /// if the server is unreachable, unchanged, or returns something unusable.
+    async fn update_feed(&self, url: &str) {
+        let mut feed = self.feed(url).await;
+        let now = SystemTime::now();
+        if feed
+            .checked_at
+            .and_then(|t| now.duration_since(t).ok())
+            .is_some_and(|elapsed| elapsed < self.poll_interval)
+        {
+            return;
+        }
+
+        let mut request = Request::new("GET", &http_url(url));
+        if feed.contents.is_some() {
+            if let Some(etag) = &feed.etag {
+                request = request.header("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &feed.last_modified {
+                request = request.header("If-Modified-Since", last_modified);
+            }
+        }
+
+        let error = match request.send().await {
+            None => Some(CalendarError::Unreachable),
+            Some(response) if response.status == 200 => {
+                if response.body.trim_start().starts_with("BEGIN:VCALENDAR") {
+                    feed.etag = response.header("ETag").map(String::from);
+                    feed.last_modified = response.header("Last-Modified").map(String::from);
+                    if feed.contents.as_deref() != Some(response.body.as_str()) {
+                        feed.contents = Some(response.body);
+                        feed.updated_at = Some(Utc::now());
+                    }
+                    None
+                } else {
+                    Some(CalendarError::InvalidData)
+                }
+            }
+            Some(response) if response.status == 304 => None,
+            Some(response) if matches!(response.status, 401 | 403) => {
+                Some(CalendarError::AuthFailed)
+            }
+            Some(response) => Some(CalendarError::HttpStatus(response.status)),
+        };
+
+        if error.is_none() {
+            feed.checked_at = Some(now);
+            self.save_to_disk(url, &feed).await;
+        }
+        // Without a check time, a failed download is tried again on the next fetch
+        feed.error = error;
+        self.feeds.lock().await.insert(url.to_string(), feed);
+    }
+
+    /// Path of the on-disk copy of a feed, without extension.
+    fn cache_path(&self, url: &str) -> Option<PathBuf> {
+        Some(
+            self.cache_dir
+                .as_ref()?
+                .join(format!("{:016x}", fnv1a(url))),
+        )
+    }
+
+    /// Load a feed saved by [`Self::save_to_disk`].
+    async fn load_from_disk(&self, url: &str) -> Option<Feed> {
+        let path = self.cache_path(url)?;
+        let contents = tokio::fs::read_to_string(path.with_extension("ics"))
+            .await
+            .ok()?;
+        let meta_path = path.with_extension("meta");
+        let meta = tokio::fs::read_to_string(&meta_path)
+            .await
+            .unwrap_or_default();
+
+        let mut feed = Feed {
+            contents: Some(contents),
+            // The metadata file is rewritten on every check
+            checked_at: tokio::fs::metadata(&meta_path)
+                .await
+                .and_then(|m| m.modified())
+                .ok(),
+            ..Default::default()
+        };
+        for line in meta.lines() {
+            match line.split_once(": ") {
+                Some(("etag", value)) => feed.etag = Some(value.to_string()),
+                Some(("last-modified", value)) => feed.last_modified = Some(value.to_string()),
+                Some(("updated", value)) => {
+                    feed.updated_at = DateTime::parse_from_rfc3339(value)
+                        .ok()
+                        .map(|t| t.with_timezone(&Utc));
+                }
+                _ => {}
+            }
+        }
+        Some(feed)
+    }
+
+    /// Save a feed's contents and validators so they survive a restart.
+    async fn save_to_disk(&self, url: &str, feed: &Feed) {
+        let (Some(path), Some(contents)) = (self.cache_path(url), &feed.contents) else {
+            return;
+        };
+        if tokio::fs::create_dir_all(path.parent().unwrap_or(&path))
+            .await
+            .is_err()
+        {
+            return;
+        }
+
+        let mut meta = format!("url: {url}\n");
+        if let Some(etag) = &feed.etag {
+            meta.push_str(&format!("etag: {etag}\n"));
+        }
+        if let Some(last_modified) = &feed.last_modified {
+            meta.push_str(&format!("last-modified: {last_modified}\n"));
+        }
+        if let Some(updated_at) = feed.updated_at {
+            meta.push_str(&format!("updated: {}\n", updated_at.to_rfc3339()));
+        }
+
+        let _ = tokio::fs::write(path.with_extension("ics"), contents).await;
+        let _ = tokio::fs::write(path.with_extension("meta"), meta).await;
+    }
+}
+
+impl CalendarSource for WebcalSource {
+    fn list_calendars(&self) -> BoxFuture<'_, Vec<CalendarInfo>> {
+        Box::pin(async {
+            let mut calendars = Vec::new();
+            for url in &self.urls {
+                let feed = self.feed(url).await;
+                let header = feed
+                    .contents
+                    .as_deref()
+                    .map(parse_calendar_header)
+                    .unwrap_or_default();
+                let uid = calendar_uid(url);
+                calendars.push(CalendarInfo {
+                    display_name: header.name.unwrap_or_else(|| feed_name(url)),
+                    color: header.color.or_else(|| Some(fallback_color(&uid))),
+                    last_synced: feed.updated_at.map(|t| t.to_rfc3339()),
+                    uid,
+                    backend: Some("webcal".to_string()),
+                });
+            }
+            calendars
+        })
+    }
+
+    fn fetch_meetings<'a>(&'a self, query: &'a MeetingQuery) -> BoxFuture<'a, SourceFetch> {
+        Box::pin(async move {
+            let user_emails: Vec<String> = query
+                .additional_emails
+                .iter()
+                .filter(|e| !e.trim().is_empty())
+                .cloned()
+                .collect();
+
+            let window = FetchWindow::new(Local::now(), query.window);
+
+            let mut fetch = SourceFetch::default();
+            for url in self.enabled_urls(&query.enabled_uids) {
+                self.update_feed(url).await;
+                let feed = self.feed(url).await;
+                // The last good copy is still shown when a download fails
+                if let Some(error) = feed.error {
+                    fetch.errors.insert(calendar_uid(url), error);
+                }
+                if let Some(contents) = feed.contents {
+                    fetch.add_calendar(&[contents], &calendar_uid(url), &window, &user_emails);
+                }
+            }
+
+            // Unlike EDS, feeds aren't pre-filtered to the query window
+            fetch.meetings.retain(|(_, m)| m.start < window.end);
+            fetch
+        })
+    }
+
+    fn refresh<'a>(&'a self, enabled_uids: &'a [String]) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            // Download again on the next fetch instead of waiting for the interval
+            let mut feeds = self.feeds.lock().await;
+            for url in self.enabled_urls(enabled_uids) {
+                if let Some(feed) = feeds.get_mut(url) {
+                    feed.checked_at = None;
+                }
+            }
+        })
+    }
+
+    fn watch_changes(
+        &self,
+        _enabled_uids: Vec<String>,
+        _window: QueryWindow,
+        _sender: tokio::sync::mpsc::Sender<()>,
+    ) -> BoxFuture<'static, ()> {
+        // Feeds are polled, there is nothing to watch
+        Box::pin(async {})
+    }
+}
+
+/// Calendar UID for a feed URL.
+fn calendar_uid(url: &str) -> String {
+    format!("{UID_PREFIX}{url}")
+}
+
+/// The URL to download a feed from. `webcal://` is a hint for calendar apps,
+/// the feed itself is served over HTTPS.
+fn http_url(url: &str) -> String {
+    match url.strip_prefix("webcal://") {
+        Some(rest) => format!("https://{rest}"),
+        None => url.to_string(),
+    }
+}
+
+/// A readable name for a feed that doesn't name itself: its file name, or host.
+fn feed_name(url: &str) -> String {
+    let Ok(parsed) = url::Url::parse(&http_url(url)) else {
+        return url.to_string();
+    };
+    parsed
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|name| !name.is_empty())
+        .map(|name| name.trim_end_matches(".ics").to_string())
+        .or_else(|| parsed.host_str().map(String::from))
+        .unwrap_or_else(|| url.to_string())
+}
+
+/// 64-bit FNV-1a hash, used for stable cache file names.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::http::{serve, test_response};
+    use crate::calendar::tests::temp_dir;
+
+    fn feed_body() -> String {
+        let start = Local::now() + chrono::Duration::days(1);
+        let end = start + chrono::Duration::hours(1);
+        format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nX-WR-CALNAME:On-call\r\n\
+             BEGIN:VEVENT\r\nUID:shift\r\nSUMMARY:On-call shift\r\n\
+             DTSTART:{}\r\nDTEND:{}\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+            start.format("%Y%m%dT%H%M%S"),
+            end.format("%Y%m%dT%H%M%S"),
+        )
+    }
+
+    /// Serves the feed with an ETag, and 304 when the client already has it.
+    /// Any other path fails, like an unreachable server.
+    fn respond(head: &str, _body: &str) -> String {
+        if !head.starts_with("GET /oncall.ics ") {
+            return test_response("500 Internal Server Error", &[], "");
+        }
+        if head.to_ascii_lowercase().contains("if-none-match: \"v1\"") {
+            test_response("304 Not Modified", &[("ETag", "\"v1\"")], "")
+        } else {
+            test_response("200 OK", &[("ETag", "\"v1\"")], &feed_body())
+        }
+    }
+
+    fn source_for(urls: &[String], cache_dir: &std::path::Path) -> WebcalSource {
+        WebcalSource {
+            cache_dir: Some(cache_dir.to_path_buf()),
+            ..WebcalSource::new(urls, 10)
+        }
+    }
+
+    #[test]
+    fn test_http_url() {
+        assert_eq!(
+            http_url("webcal://example.com/cal.ics"),
+            "https://example.com/cal.ics"
+        );
+        assert_eq!(http_url("http://localhost/a.ics"), "http://localhost/a.ics");
+    }
+
+    #[test]
+    fn test_feed_name() {
+        assert_eq!(feed_name("webcal://example.com/holidays.ics"), "holidays");
+        assert_eq!(feed_name("https://example.com/"), "example.com");
+    }
+
+    #[test]
+    fn test_webcal_conditional_poll_and_disk_copy() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let base = rt.block_on(serve(respond));
+        let url = format!("{base}oncall.ics");
+        let cache_dir = temp_dir("webcal");
+
+        let source = source_for(std::slice::from_ref(&url), &cache_dir);
+        let meetings = rt
+            .block_on(source.fetch_meetings(&MeetingQuery::default()))
+            .meetings;
+        assert_eq!(meetings.len(), 1);
+        assert_eq!(meetings[0].1.title, "On-call shift");
+        assert_eq!(meetings[0].1.calendar_uid, calendar_uid(&url));
+
+        let calendars = rt.block_on(source.list_calendars());
+        assert_eq!(calendars[0].display_name, "On-call");
+        assert_eq!(calendars[0].backend.as_deref(), Some("webcal"));
+
+        // A refresh sends the ETag back; the 304 keeps the copy we have
+        rt.block_on(source.refresh(&[]));
+        let meetings = rt
+            .block_on(source.fetch_meetings(&MeetingQuery::default()))
+            .meetings;
+        assert_eq!(meetings.len(), 1);
+
+        // A new source starts from the disk copy, without waiting for the server
+        let restarted = source_for(std::slice::from_ref(&url), &cache_dir);
+        let feed = rt.block_on(restarted.feed(&url));
+        assert_eq!(feed.etag.as_deref(), Some("\"v1\""));
+        assert!(feed.checked_at.is_some());
+        assert!(feed.contents.is_some_and(|c| c.contains("On-call shift")));
+
+        // An unreachable feed keeps serving the last good copy
+        let broken_url = format!("{base}missing.ics");
+        let broken = source_for(std::slice::from_ref(&broken_url), &cache_dir);
+        std::fs::copy(
+            broken.cache_path(&url).unwrap().with_extension("ics"),
+            broken
+                .cache_path(&broken_url)
+                .unwrap()
+                .with_extension("ics"),
+        )
+        .unwrap();
+        let fetch = rt.block_on(broken.fetch_meetings(&MeetingQuery::default()));
+        assert_eq!(fetch.meetings.len(), 1);
+        assert_eq!(
+            fetch.errors.get(&calendar_uid(&broken_url)),
+            Some(&CalendarError::HttpStatus(500))
+        );
+        assert!(!fetch.is_failure());
+
+        std::fs::remove_dir_all(&cache_dir).unwrap();
+    }
+}
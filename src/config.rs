@@ -1,7 +1,9 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::calendar::{Meeting, extract_meeting_urls};
 use cosmic::cosmic_config::{self, CosmicConfigEntry, cosmic_config_derive::CosmicConfigEntry};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// How to display the meeting time in the panel
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
@@ -61,6 +63,38 @@ pub enum InProgressMeeting {
     Within15m,
     /// Show meetings that started within 30 minutes
     Within30m,
+    /// Show meetings that started within an hour
+    Within60m,
+    /// Show meetings until they end, as far back as the look-behind window
+    UntilEnd,
+}
+
+impl InProgressMeeting {
+    /// Every option, in the order they're offered.
+    pub const ALL: [Self; 7] = [
+        Self::Off,
+        Self::Within5m,
+        Self::Within10m,
+        Self::Within15m,
+        Self::Within30m,
+        Self::Within60m,
+        Self::UntilEnd,
+    ];
+
+    /// How long after its start a meeting stays visible, or `None` if it is
+    /// only limited by the look-behind window.
+    #[must_use]
+    pub fn minutes(self) -> Option<u32> {
+        match self {
+            Self::Off => Some(0),
+            Self::Within5m => Some(5),
+            Self::Within10m => Some(10),
+            Self::Within15m => Some(15),
+            Self::Within30m => Some(30),
+            Self::Within60m => Some(60),
+            Self::UntilEnd => None,
+        }
+    }
 }
 
 /// Filter events by how far in the future they are
@@ -79,6 +113,35 @@ pub enum TimeUntilFilter {
     Within1Day,
     /// Show events within 2 days (48 hours)
     Within2Days,
+    /// Show events within 7 days
+    Within7Days,
+}
+
+impl TimeUntilFilter {
+    /// Every option, in the order they're offered.
+    pub const ALL: [Self; 7] = [
+        Self::All,
+        Self::Within3Hours,
+        Self::Within6Hours,
+        Self::SameDay,
+        Self::Within1Day,
+        Self::Within2Days,
+        Self::Within7Days,
+    ];
+
+    /// The furthest ahead a shown meeting can start, in hours, or `None` for
+    /// everything in the look-ahead window.
+    #[must_use]
+    pub fn hours(self) -> Option<u32> {
+        match self {
+            Self::All => None,
+            Self::Within3Hours => Some(3),
+            Self::Within6Hours => Some(6),
+            Self::SameDay | Self::Within1Day => Some(24),
+            Self::Within2Days => Some(48),
+            Self::Within7Days => Some(7 * 24),
+        }
+    }
 }
 
 /// Action to take when opening the calendar app
@@ -93,6 +156,229 @@ pub enum CalendarAppAction {
     OpenUrl,
 }
 
+/// A CalDAV account that the applet reads from directly, without EDS.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct CalDavAccount {
+    /// Server URL: the server itself, a calendar home, or a single calendar.
+    pub url: String,
+    /// Username for the server. The password is read from the Secret Service.
+    pub username: String,
+}
+
+/// Video call service a meeting provider stands for
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum ProviderKind {
+    GoogleMeet,
+    Zoom,
+    Teams,
+    Webex,
+    Jitsi,
+    Whereby,
+    Around,
+    /// Added by the user, or migrated from the old flat pattern list
+    #[default]
+    Custom,
+}
+
+impl ProviderKind {
+    /// Whether links can be rewritten to open the provider's desktop app.
+    #[must_use]
+    pub fn has_native_app(self) -> bool {
+        matches!(self, Self::Zoom | Self::Teams | Self::Jitsi)
+    }
+
+    /// Rewrite a meeting link to the scheme the provider's desktop app handles,
+    /// e.g. `https://acme.zoom.us/j/123?pwd=x` to
+    /// `zoommtg://acme.zoom.us/join?action=join&confno=123&pwd=x`.
+    /// Returns `None` if the provider has no app or the link isn't a meeting.
+    #[must_use]
+    pub fn native_url(self, url: &str) -> Option<String> {
+        let parsed = url::Url::parse(url).ok()?;
+        let host = parsed.host_str()?;
+        match self {
+            Self::Zoom => {
+                let id = parsed.path().strip_prefix("/j/")?.trim_end_matches('/');
+                let pwd = parsed
+                    .query_pairs()
+                    .find(|(k, _)| k == "pwd")
+                    .map(|(_, pwd)| format!("&pwd={pwd}"))
+                    .unwrap_or_default();
+                Some(format!(
+                    "zoommtg://{host}/join?action=join&confno={id}{pwd}"
+                ))
+            }
+            Self::Teams => {
+                let path = parsed.path();
+                if !path.starts_with("/l/") {
+                    return None;
+                }
+                Some(match parsed.query() {
+                    Some(query) => format!("msteams:{path}?{query}"),
+                    None => format!("msteams:{path}"),
+                })
+            }
+            Self::Jitsi => Some(format!("jitsi-meet://{host}{}", parsed.path())),
+            Self::GoogleMeet | Self::Webex | Self::Whereby | Self::Around | Self::Custom => None,
+        }
+    }
+}
+
+/// A named meeting provider: how to recognise its links and how to open them.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MeetingProvider {
+    pub kind: ProviderKind,
+    /// Name shown in settings. Empty for custom providers that haven't been named.
+    pub name: String,
+    /// Icon shown next to the Join button.
+    pub icon: String,
+    /// Regex patterns that detect the provider's links in locations and descriptions.
+    pub patterns: Vec<String>,
+    /// Whether to open links in the provider's desktop app instead of the browser.
+    pub open_in_app: bool,
+    /// Command to open links with, where `{url}` stands for the link, e.g.
+    /// `chromium --profile-directory=Work {url}`. Empty to use the default browser.
+    #[serde(default)]
+    pub launch_command: String,
+}
+
+impl MeetingProvider {
+    fn built_in(kind: ProviderKind, name: &str, icon: &str, patterns: &[&str]) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            icon: icon.to_string(),
+            patterns: patterns.iter().map(ToString::to_string).collect(),
+            open_in_app: false,
+            launch_command: String::new(),
+        }
+    }
+
+    /// An unnamed custom provider with the given patterns.
+    #[must_use]
+    pub fn custom(patterns: Vec<String>) -> Self {
+        Self {
+            kind: ProviderKind::Custom,
+            name: String::new(),
+            icon: DEFAULT_PROVIDER_ICON.to_string(),
+            patterns,
+            open_in_app: false,
+            launch_command: String::new(),
+        }
+    }
+
+    /// Whether a link belongs to this provider. Invalid patterns never match.
+    #[must_use]
+    pub fn matches(&self, url: &str) -> bool {
+        self.patterns
+            .iter()
+            .filter_map(|p| regex::Regex::new(p).ok())
+            .any(|re| re.is_match(url))
+    }
+
+    /// The address to open for one of this provider's links: the desktop app
+    /// link when that's enabled and possible, otherwise the link itself.
+    #[must_use]
+    pub fn launch_url(&self, url: &str) -> String {
+        self.open_in_app
+            .then(|| self.kind.native_url(url))
+            .flatten()
+            .unwrap_or_else(|| url.to_string())
+    }
+
+    /// The program and arguments to open a link with, or `None` to use the
+    /// default browser. The link is appended if the command has no `{url}`.
+    #[must_use]
+    pub fn join_command(&self, url: &str) -> Option<Vec<String>> {
+        let mut args: Vec<String> = self
+            .launch_command
+            .split_whitespace()
+            .map(|arg| arg.replace("{url}", url))
+            .collect();
+        if args.is_empty() {
+            return None;
+        }
+        if !self.launch_command.contains("{url}") {
+            args.push(url.to_string());
+        }
+        Some(args)
+    }
+}
+
+/// Icon for custom providers, and for built-in ones whose app icon isn't installed.
+pub const DEFAULT_PROVIDER_ICON: &str = "camera-web-symbolic";
+
+/// The providers known out of the box.
+#[must_use]
+pub fn default_meeting_providers() -> Vec<MeetingProvider> {
+    vec![
+        MeetingProvider::built_in(
+            ProviderKind::GoogleMeet,
+            "Google Meet",
+            "google-meet",
+            &[r"https://meet\.google\.com/[a-z-]+"],
+        ),
+        MeetingProvider::built_in(
+            ProviderKind::Zoom,
+            "Zoom",
+            "us.zoom.Zoom",
+            &[r"https://[a-z0-9]+\.zoom\.us/j/[0-9]+(?:\?pwd=[A-Za-z0-9.]+)?"],
+        ),
+        MeetingProvider::built_in(
+            ProviderKind::Teams,
+            "Microsoft Teams",
+            "com.github.IsmaelMartinez.teams_for_linux",
+            &[
+                r"https://teams\.microsoft\.com/l/meetup-join/[^\s]+",
+                r"https://teams\.live\.com/meet/[^\s]+",
+            ],
+        ),
+        MeetingProvider::built_in(
+            ProviderKind::Webex,
+            "Webex",
+            "webex",
+            &[
+                r"https://[a-z0-9]+\.webex\.com/[^\s]+/j\.php\?MTID=[^\s]+",
+                r"https://[a-z0-9]+\.webex\.com/meet/[^\s]+",
+            ],
+        ),
+        MeetingProvider::built_in(
+            ProviderKind::Jitsi,
+            "Jitsi Meet",
+            "org.jitsi.jitsi-meet",
+            &[r"https://meet\.jit\.si/[^\s]+"],
+        ),
+        MeetingProvider::built_in(
+            ProviderKind::Whereby,
+            "Whereby",
+            "whereby",
+            &[r"https://whereby\.com/[^\s]+"],
+        ),
+        MeetingProvider::built_in(
+            ProviderKind::Around,
+            "Around",
+            "around",
+            &[r"https://(?:meet\.)?around\.co/r/[^\s]+"],
+        ),
+    ]
+}
+
+/// Patterns that `meeting_url_patterns` held by default before providers
+/// existed. They're covered by the built-in providers, so aren't migrated.
+const LEGACY_DEFAULT_PATTERNS: [&str; 6] = [
+    r"https://meet\.google\.com/[a-z-]+",
+    r"https://[a-z0-9]+\.zoom\.us/j/[0-9]+",
+    r"https://teams\.microsoft\.com/l/meetup-join/[^\s]+",
+    r"https://teams\.live\.com/meet/[^\s]+",
+    r"https://[a-z0-9]+\.webex\.com/[^\s]+/j\.php\?MTID=[^\s]+",
+    r"https://[a-z0-9]+\.webex\.com/meet/[^\s]+",
+];
+
+/// Look-behind windows offered in settings, in minutes.
+pub const LOOK_BEHIND_OPTIONS: [u32; 4] = [30, 60, 120, 240];
+
+/// Look-ahead windows offered in settings, in days.
+pub const LOOK_AHEAD_OPTIONS: [u32; 6] = [1, 7, 14, 30, 60, 90];
+
 #[derive(Debug, Clone, CosmicConfigEntry, Eq, PartialEq)]
 #[version = 1]
 #[allow(clippy::struct_excessive_bools)]
@@ -120,12 +406,20 @@ pub struct Config {
     pub panel_calendar_indicator: bool,
     /// Show calendar color indicator in popup.
     pub popup_calendar_indicator: bool,
-    /// Regex patterns to detect meeting URLs in location/description.
+    /// Legacy: regex patterns from before meeting providers. Moved into custom
+    /// providers when the config is loaded.
     pub meeting_url_patterns: Vec<String>,
+    /// Meeting providers, used to detect meeting links and to open them.
+    pub meeting_providers: Vec<MeetingProvider>,
+    /// Link picked for each event with several meeting links, keyed by the
+    /// event's `UID` so the choice holds for every occurrence of a series.
+    pub preferred_meeting_links: BTreeMap<String, String>,
     /// Whether to show all-day events.
     pub show_all_day_events: bool,
     /// Filter events by attendance status.
     pub event_status_filter: EventStatusFilter,
+    /// Whether to hide events marked as free (`TRANSP:TRANSPARENT`).
+    pub hide_free_events: bool,
     /// Additional email addresses to identify the user in ATTENDEE fields.
     /// Used in addition to the `CalEmailAddress` from each calendar.
     pub additional_emails: Vec<String>,
@@ -133,6 +427,11 @@ pub struct Config {
     pub show_in_progress: InProgressMeeting,
     /// Filter events by how far in the future they are.
     pub time_until_filter: TimeUntilFilter,
+    /// How far back meetings are fetched, in minutes. Bounds how long
+    /// in-progress meetings can be shown.
+    pub look_behind_minutes: u32,
+    /// How far ahead meetings are fetched, in days.
+    pub look_ahead_days: u32,
     /// Whether to show the "Open Calendar" menu item.
     pub show_calendar_button: bool,
     /// Action to take when opening the calendar.
@@ -143,6 +442,14 @@ pub struct Config {
     pub calendar_app_url: String,
     /// Whether to hide the applet from the panel when there are no meetings.
     pub hide_when_no_meetings: bool,
+    /// Paths to `.ics` files or folders of `.ics` files to read as calendars.
+    pub ics_calendar_paths: Vec<String>,
+    /// Paths to vdir collections (e.g. synced by vdirsyncer), or folders of them.
+    pub vdir_paths: Vec<String>,
+    /// CalDAV accounts to read from directly.
+    pub caldav_accounts: Vec<CalDavAccount>,
+    /// Read-only iCalendar subscription URLs (`webcal://` or `https://`).
+    pub webcal_urls: Vec<String>,
 }
 
 impl Default for Config {
@@ -159,28 +466,268 @@ impl Default for Config {
             panel_show_location: false,
             panel_calendar_indicator: false,
             popup_calendar_indicator: true,
-            meeting_url_patterns: vec![
-                // Google Meet
-                r"https://meet\.google\.com/[a-z-]+".to_string(),
-                // Zoom
-                r"https://[a-z0-9]+\.zoom\.us/j/[0-9]+".to_string(),
-                // Microsoft Teams
-                r"https://teams\.microsoft\.com/l/meetup-join/[^\s]+".to_string(),
-                r"https://teams\.live\.com/meet/[^\s]+".to_string(),
-                // Webex
-                r"https://[a-z0-9]+\.webex\.com/[^\s]+/j\.php\?MTID=[^\s]+".to_string(),
-                r"https://[a-z0-9]+\.webex\.com/meet/[^\s]+".to_string(),
-            ],
+            meeting_url_patterns: Vec::new(),
+            meeting_providers: default_meeting_providers(),
+            preferred_meeting_links: BTreeMap::new(),
             show_all_day_events: true,
             event_status_filter: EventStatusFilter::default(),
+            hide_free_events: false,
             additional_emails: Vec::new(),
             show_in_progress: InProgressMeeting::default(),
             time_until_filter: TimeUntilFilter::default(),
+            look_behind_minutes: 30,
+            look_ahead_days: 30,
             show_calendar_button: true,
             calendar_app_action: CalendarAppAction::default(),
             calendar_app_command: "gnome-calendar".to_string(),
             calendar_app_url: "https://calendar.google.com".to_string(),
             hide_when_no_meetings: false,
+            ics_calendar_paths: Vec::new(),
+            vdir_paths: Vec::new(),
+            caldav_accounts: Vec::new(),
+            webcal_urls: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Move patterns from the old flat list into custom providers, one each,
+    /// skipping the old defaults that the built-in providers already cover.
+    /// Returns whether anything changed and the config should be saved.
+    pub fn migrate_url_patterns(&mut self) -> bool {
+        if self.meeting_url_patterns.is_empty() {
+            return false;
+        }
+        for pattern in std::mem::take(&mut self.meeting_url_patterns) {
+            let known = LEGACY_DEFAULT_PATTERNS.contains(&pattern.as_str())
+                || self
+                    .meeting_providers
+                    .iter()
+                    .any(|p| p.patterns.contains(&pattern));
+            if !known && !pattern.trim().is_empty() {
+                self.meeting_providers
+                    .push(MeetingProvider::custom(vec![pattern]));
+            }
+        }
+        true
+    }
+
+    /// Patterns of every provider, for finding meeting links.
+    #[must_use]
+    pub fn url_patterns(&self) -> Vec<String> {
+        self.meeting_providers
+            .iter()
+            .flat_map(|p| p.patterns.iter().cloned())
+            .collect()
+    }
+
+    /// The first provider a meeting link belongs to.
+    #[must_use]
+    pub fn provider_for_url(&self, url: &str) -> Option<&MeetingProvider> {
+        self.meeting_providers.iter().find(|p| p.matches(url))
+    }
+
+    /// Meeting links found in a meeting, best first.
+    #[must_use]
+    pub fn meeting_links(&self, meeting: &Meeting) -> Vec<String> {
+        extract_meeting_urls(meeting, &self.url_patterns())
+    }
+
+    /// The link to join a meeting with straight away: the only one, or the one
+    /// picked earlier for the meeting's series. `None` if the user has to choose.
+    #[must_use]
+    pub fn join_link(&self, meeting: &Meeting, links: &[String]) -> Option<String> {
+        match links {
+            [] => None,
+            [only] => Some(only.clone()),
+            _ => self
+                .preferred_meeting_links
+                .get(meeting.event_uid())
+                .filter(|url| links.contains(url))
+                .cloned(),
+        }
+    }
+
+    /// The in-progress options that fit in the look-behind window.
+    #[must_use]
+    pub fn in_progress_options(&self) -> Vec<InProgressMeeting> {
+        InProgressMeeting::ALL
+            .into_iter()
+            .filter(|o| o.minutes().is_none_or(|m| m <= self.look_behind_minutes))
+            .collect()
+    }
+
+    /// The time-until options that fit in the look-ahead window.
+    #[must_use]
+    pub fn time_until_options(&self) -> Vec<TimeUntilFilter> {
+        TimeUntilFilter::ALL
+            .into_iter()
+            .filter(|o| o.hours().is_none_or(|h| h <= self.look_ahead_days * 24))
+            .collect()
+    }
+
+    /// Replace filter choices that no longer fit the fetch window with the
+    /// widest option that does, so they never ask for meetings that weren't fetched.
+    pub fn clamp_filters_to_window(&mut self) {
+        if !self.in_progress_options().contains(&self.show_in_progress) {
+            self.show_in_progress = self
+                .in_progress_options()
+                .into_iter()
+                .rfind(|o| o.minutes().is_some())
+                .unwrap_or(InProgressMeeting::Off);
         }
+        if !self.time_until_options().contains(&self.time_until_filter) {
+            self.time_until_filter = self
+                .time_until_options()
+                .into_iter()
+                .rfind(|o| o.hours().is_some())
+                .unwrap_or(TimeUntilFilter::All);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_native_url_rewrites() {
+        assert_eq!(
+            ProviderKind::Zoom
+                .native_url("https://acme.zoom.us/j/85512345678?pwd=abc123")
+                .as_deref(),
+            Some("zoommtg://acme.zoom.us/join?action=join&confno=85512345678&pwd=abc123")
+        );
+        assert_eq!(
+            ProviderKind::Teams
+                .native_url("https://teams.microsoft.com/l/meetup-join/19%3ameeting%40thread.v2/0?context=%7b%7d")
+                .as_deref(),
+            Some("msteams:/l/meetup-join/19%3ameeting%40thread.v2/0?context=%7b%7d")
+        );
+        assert_eq!(
+            ProviderKind::Jitsi
+                .native_url("https://meet.jit.si/WeeklySync")
+                .as_deref(),
+            Some("jitsi-meet://meet.jit.si/WeeklySync")
+        );
+        // Links that aren't meetings, and providers without an app, stay as they are
+        assert!(
+            ProviderKind::Zoom
+                .native_url("https://zoom.us/pricing")
+                .is_none()
+        );
+        assert!(
+            ProviderKind::GoogleMeet
+                .native_url("https://meet.google.com/abc-defg-hij")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_launch_url_respects_open_in_app() {
+        let config = Config::default();
+        let url = "https://acme.zoom.us/j/123";
+        let zoom = config.provider_for_url(url).unwrap();
+        assert_eq!(zoom.kind, ProviderKind::Zoom);
+        assert_eq!(zoom.launch_url(url), url);
+
+        let zoom = MeetingProvider {
+            open_in_app: true,
+            ..zoom.clone()
+        };
+        assert_eq!(
+            zoom.launch_url(url),
+            "zoommtg://acme.zoom.us/join?action=join&confno=123"
+        );
+    }
+
+    #[test]
+    fn test_join_command() {
+        let url = "https://teams.microsoft.com/l/meetup-join/abc";
+        let mut provider = MeetingProvider::custom(Vec::new());
+        assert!(provider.join_command(url).is_none());
+
+        provider.launch_command = "chromium --profile-directory=Work {url}".to_string();
+        assert_eq!(
+            provider.join_command(url),
+            Some(vec![
+                "chromium".to_string(),
+                "--profile-directory=Work".to_string(),
+                url.to_string(),
+            ])
+        );
+
+        // Without a placeholder the link goes last
+        provider.launch_command = "firefox --new-window".to_string();
+        assert_eq!(
+            provider.join_command(url),
+            Some(vec![
+                "firefox".to_string(),
+                "--new-window".to_string(),
+                url.to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_migrate_url_patterns() {
+        let mut config = Config {
+            meeting_url_patterns: vec![
+                r"https://meet\.google\.com/[a-z-]+".to_string(),
+                r"https://bbb\.example\.org/rooms/[^\s]+".to_string(),
+                String::new(),
+            ],
+            ..Config::default()
+        };
+        assert!(config.migrate_url_patterns());
+        assert!(config.meeting_url_patterns.is_empty());
+
+        // Only the user's own pattern becomes a provider
+        let custom: Vec<&MeetingProvider> = config
+            .meeting_providers
+            .iter()
+            .filter(|p| p.kind == ProviderKind::Custom)
+            .collect();
+        assert_eq!(custom.len(), 1);
+        assert_eq!(
+            custom[0].patterns,
+            vec![r"https://bbb\.example\.org/rooms/[^\s]+".to_string()]
+        );
+        assert!(
+            config
+                .provider_for_url("https://bbb.example.org/rooms/abc-123")
+                .is_some_and(|p| p.kind == ProviderKind::Custom)
+        );
+
+        // Nothing left to migrate the next time
+        assert!(!config.migrate_url_patterns());
+    }
+
+    #[test]
+    fn test_join_link_uses_preferred_link() {
+        use crate::calendar::tests::make_test_meeting;
+
+        let zoom = "https://example.zoom.us/j/123".to_string();
+        let meet = "https://meet.google.com/abc-defg-hij".to_string();
+        let mut meeting = make_test_meeting(None, None);
+        meeting.uid = "standup@20260105T100000".to_string();
+        let mut config = Config::default();
+
+        assert_eq!(config.join_link(&meeting, &[]), None);
+        assert_eq!(
+            config.join_link(&meeting, std::slice::from_ref(&zoom)),
+            Some(zoom.clone())
+        );
+        // Several links and no pick yet: the user has to choose
+        let links = [zoom.clone(), meet.clone()];
+        assert_eq!(config.join_link(&meeting, &links), None);
+
+        // A pick holds for every occurrence, as long as the link is still there
+        config
+            .preferred_meeting_links
+            .insert("standup".to_string(), meet.clone());
+        meeting.uid = "standup@20260112T100000".to_string();
+        assert_eq!(config.join_link(&meeting, &links), Some(meet));
+        assert_eq!(config.join_link(&meeting, &[zoom.clone(), zoom]), None);
     }
 }
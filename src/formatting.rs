@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::calendar::{CalendarError, FilterReason, Participant, ParticipantKind, ParticipantRole};
+use crate::config::{InProgressMeeting, MeetingProvider, TimeUntilFilter};
 use crate::fl;
 use cosmic::cosmic_config::ConfigGet;
 
@@ -90,10 +92,141 @@ pub fn format_backend_name(backend: &str) -> &'static str {
         "webcal" => "Web calendar",
         "contacts" => "Contacts",
         "birthdays" => "Birthdays",
+        "ics" => "Calendar file",
+        "vdir" => "vdir",
         _ => "Remote",
     }
 }
 
+/// Describe why a calendar or source couldn't be read
+pub fn format_calendar_error(error: &CalendarError) -> String {
+    match error {
+        CalendarError::Unreachable => fl!("error-unreachable"),
+        CalendarError::OpenFailed(message) => fl!("error-open-failed", message = message),
+        CalendarError::QueryFailed(message) => fl!("error-query-failed", message = message),
+        CalendarError::TimedOut => fl!("error-timed-out"),
+        CalendarError::AuthFailed => fl!("error-auth-failed"),
+        CalendarError::HttpStatus(status) => fl!("error-http-status", status = status),
+        CalendarError::InvalidUrl => fl!("error-invalid-url"),
+        CalendarError::Unreadable(message) => fl!("error-unreadable", message = message),
+        CalendarError::InvalidData => fl!("error-invalid-data"),
+        CalendarError::InvalidEvents(count) => fl!("error-invalid-events", count = count),
+        CalendarError::ReadOnly => fl!("error-read-only"),
+        CalendarError::NotInvited => fl!("error-not-invited"),
+        CalendarError::WriteFailed(message) => fl!("error-write-failed", message = message),
+    }
+}
+
+/// Describe why event instances were left out
+pub fn format_filter_reason(reason: FilterReason) -> String {
+    match reason {
+        FilterReason::NotAnEvent => fl!("filter-reason-not-event"),
+        FilterReason::InvalidTimes => fl!("filter-reason-invalid-times"),
+        FilterReason::Past => fl!("filter-reason-past"),
+        FilterReason::AfterWindow => fl!("filter-reason-after-window"),
+        FilterReason::Duplicate => fl!("filter-reason-duplicate"),
+        FilterReason::AllDay => fl!("filter-reason-all-day"),
+        FilterReason::InProgress => fl!("filter-reason-in-progress"),
+        FilterReason::TimeUntil => fl!("filter-reason-time-until"),
+        FilterReason::AttendanceStatus => fl!("filter-reason-attendance"),
+        FilterReason::Cancelled => fl!("filter-reason-cancelled"),
+        FilterReason::Free => fl!("filter-reason-free"),
+    }
+}
+
+/// Describe what kind of participant someone is, if they aren't simply a
+/// required attendee
+pub fn format_participant_role(participant: &Participant) -> Option<String> {
+    match (participant.kind, participant.role) {
+        (ParticipantKind::Room, _) => Some(fl!("participant-room")),
+        (ParticipantKind::Resource, _) => Some(fl!("participant-resource")),
+        (ParticipantKind::Group, _) => Some(fl!("participant-group")),
+        (_, ParticipantRole::Chair) => Some(fl!("participant-chair")),
+        (_, ParticipantRole::Optional) => Some(fl!("participant-optional")),
+        (_, ParticipantRole::NonParticipant) => Some(fl!("participant-non-participant")),
+        (_, ParticipantRole::Required) => None,
+    }
+}
+
+/// Name of a meeting provider, with a placeholder for unnamed custom ones
+pub fn format_provider_name(provider: &MeetingProvider) -> String {
+    if provider.name.trim().is_empty() {
+        fl!("provider-custom")
+    } else {
+        provider.name.clone()
+    }
+}
+
+/// Label for a meeting link: its provider's name, or the host it points to
+pub fn format_meeting_link(provider: Option<&MeetingProvider>, url: &str) -> String {
+    match provider {
+        Some(provider) => format_provider_name(provider),
+        None => url::Url::parse(url)
+            .ok()
+            .and_then(|parsed| parsed.host_str().map(str::to_string))
+            .unwrap_or_else(|| url.to_string()),
+    }
+}
+
+/// A link without its scheme, shortened in the middle so it fits on one line
+pub fn format_short_url(url: &str) -> String {
+    const MAX_CHARS: usize = 40;
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let chars: Vec<char> = url.chars().collect();
+    if chars.len() <= MAX_CHARS {
+        return url.to_string();
+    }
+    let head = MAX_CHARS * 2 / 3;
+    let tail = MAX_CHARS - head - 1;
+    let mut short: String = chars[..head].iter().collect();
+    short.push('…');
+    short.extend(&chars[chars.len() - tail..]);
+    short
+}
+
+/// Label for an in-progress meeting option
+pub fn format_in_progress(option: InProgressMeeting) -> String {
+    match option {
+        InProgressMeeting::Off => fl!("in-progress-off"),
+        InProgressMeeting::Within5m => fl!("in-progress-5m"),
+        InProgressMeeting::Within10m => fl!("in-progress-10m"),
+        InProgressMeeting::Within15m => fl!("in-progress-15m"),
+        InProgressMeeting::Within30m => fl!("in-progress-30m"),
+        InProgressMeeting::Within60m => fl!("in-progress-60m"),
+        InProgressMeeting::UntilEnd => fl!("in-progress-until-end"),
+    }
+}
+
+/// Label for a time-until filter option
+pub fn format_time_until(filter: TimeUntilFilter) -> String {
+    match filter {
+        TimeUntilFilter::All => fl!("time-until-all"),
+        TimeUntilFilter::Within3Hours => fl!("time-until-3h"),
+        TimeUntilFilter::Within6Hours => fl!("time-until-6h"),
+        TimeUntilFilter::SameDay => fl!("time-until-same-day"),
+        TimeUntilFilter::Within1Day => fl!("time-until-1d"),
+        TimeUntilFilter::Within2Days => fl!("time-until-2d"),
+        TimeUntilFilter::Within7Days => fl!("time-until-7d"),
+    }
+}
+
+/// Label for a look-behind window length
+pub fn format_look_behind(minutes: u32) -> String {
+    if minutes % 60 == 0 {
+        fl!("window-hours", hours = minutes / 60)
+    } else {
+        fl!("window-minutes", minutes = minutes)
+    }
+}
+
+/// Label for a look-ahead window length
+pub fn format_look_ahead(days: u32) -> String {
+    fl!("window-days", days = days)
+}
+
 /// Format an ISO 8601 timestamp as a relative "Updated X ago" string
 pub fn format_last_updated(iso_timestamp: &str) -> String {
     use chrono::{DateTime, Utc};
@@ -163,6 +296,26 @@ mod tests {
         assert!(parse_hex_color("").is_none());
     }
 
+    #[test]
+    fn test_format_short_url() {
+        assert_eq!(format_short_url("https://zoom.us/j/123"), "zoom.us/j/123");
+        let short = format_short_url(
+            "https://teams.microsoft.com/l/meetup-join/19%3ameeting_abcdefghijklmnop%40thread.v2/0",
+        );
+        assert_eq!(short.chars().count(), 40);
+        assert!(short.starts_with("teams.microsoft.com/l/"));
+        assert!(short.ends_with("thread.v2/0"));
+    }
+
+    #[test]
+    fn test_format_meeting_link_without_provider() {
+        assert_eq!(
+            format_meeting_link(None, "https://meet.example.org/room"),
+            "meet.example.org"
+        );
+        assert_eq!(format_meeting_link(None, "not a url"), "not a url");
+    }
+
     #[test]
     fn test_parse_hex_color_invalid_chars() {
         assert!(parse_hex_color("#gggggg").is_none());
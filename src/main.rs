@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 mod app;
+mod cache;
 mod calendar;
 mod config;
 mod formatting;
@@ -32,31 +33,39 @@ fn join_next_meeting() -> i32 {
 
     const APP_ID: &str = "com.dangrover.next-meeting-app";
 
-    // Load config to get enabled calendars and URL patterns
-    let config = cosmic::cosmic_config::Config::new(APP_ID, config::Config::VERSION)
+    // Load config to get enabled calendars and meeting providers
+    let mut config = cosmic::cosmic_config::Config::new(APP_ID, config::Config::VERSION)
         .ok()
         .map(|ctx| config::Config::get_entry(&ctx).unwrap_or_else(|(_e, c)| c))
         .unwrap_or_default();
+    config.migrate_url_patterns();
 
     // Build tokio runtime to run async calendar fetch
     let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
 
-    let meetings = rt.block_on(calendar::get_upcoming_meetings(
-        &config.enabled_calendar_uids,
-        1, // Just need the first meeting
-        &config.additional_emails,
-    ));
+    let sources = calendar::SourceSet::from_config(&config);
+    let query = calendar::MeetingQuery {
+        enabled_uids: config.enabled_calendar_uids.clone(),
+        additional_emails: config.additional_emails.clone(),
+        window: calendar::QueryWindow::from_config(&config),
+    };
+    let meetings = rt.block_on(sources.upcoming_meetings(&query, 1)).meetings; // Just need the first meeting
 
     // Get the first meeting
     let Some(meeting) = meetings.first() else {
         return 1; // No meetings found
     };
 
-    // Extract meeting URL
-    let Some(url) = calendar::extract_meeting_url(meeting, &config.meeting_url_patterns) else {
+    // Pick the link the applet would join with. With several links and none
+    // picked yet there's no menu to choose from, so go with the best one
+    let links = config.meeting_links(meeting);
+    let Some(url) = config
+        .join_link(meeting, &links)
+        .or_else(|| links.first().cloned())
+    else {
         return 1; // No URL in meeting
     };
 
-    // Open the URL (exit 0 on success, 1 on failure)
-    i32::from(!app::open_url(&url))
+    // Open the URL through its provider (exit 0 on success, 1 on failure)
+    i32::from(!app::join_meeting(&config, &url))
 }
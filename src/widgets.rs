@@ -3,6 +3,7 @@
 // Reusable widget helpers and styles for the applet UI.
 
 use crate::calendar::CalendarInfo;
+use crate::config::DEFAULT_PROVIDER_ICON;
 use crate::fl;
 use crate::formatting::parse_hex_color;
 use cosmic::cosmic_theme;
@@ -29,6 +30,26 @@ pub fn email_input_id(idx: usize) -> id::Id {
     id::Id::new(format!("email_input_{idx}"))
 }
 
+/// Generate a unique ID for a calendar file path input field
+pub fn ics_path_input_id(idx: usize) -> id::Id {
+    id::Id::new(format!("ics_path_input_{idx}"))
+}
+
+/// Generate a unique ID for a vdir path input field
+pub fn vdir_path_input_id(idx: usize) -> id::Id {
+    id::Id::new(format!("vdir_path_input_{idx}"))
+}
+
+/// Generate a unique ID for a CalDAV account URL input field
+pub fn caldav_url_input_id(idx: usize) -> id::Id {
+    id::Id::new(format!("caldav_url_input_{idx}"))
+}
+
+/// Generate a unique ID for a webcal subscription URL input field
+pub fn webcal_url_input_id(idx: usize) -> id::Id {
+    id::Id::new(format!("webcal_url_input_{idx}"))
+}
+
 /// Secondary text style for dimmed/muted text appearance
 pub fn secondary_text_style(theme: &cosmic::Theme) -> cosmic::iced_widget::text::Style {
     cosmic::iced_widget::text::Style {
@@ -118,6 +139,52 @@ pub fn settings_nav_row_with_icon<'a, M: Clone + 'static>(
     .into()
 }
 
+/// Creates an editable list of paths or URLs, each with a text input and a delete button
+pub fn editable_list<'a, M: Clone + 'static>(
+    values: &'a [String],
+    placeholder: &'static str,
+    on_input: fn(usize, String) -> M,
+    on_remove: fn(usize) -> M,
+    input_id: fn(usize) -> id::Id,
+) -> Element<'a, M> {
+    let space = spacing();
+    let mut list = widget::list_column().list_item_padding([space.space_xxs, space.space_xs]);
+
+    for (idx, value) in values.iter().enumerate() {
+        list = list.add(
+            widget::row::with_capacity(2)
+                .push(
+                    widget::text_input(placeholder, value)
+                        .on_input(move |s| on_input(idx, s))
+                        .width(Length::Fill)
+                        .id(input_id(idx)),
+                )
+                .push(
+                    widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                        .extra_small()
+                        .on_press(on_remove(idx)),
+                )
+                .spacing(space.space_xs)
+                .align_y(cosmic::iced::Alignment::Center)
+                .width(Length::Fill),
+        );
+    }
+
+    list.into()
+}
+
+/// Icon for a meeting provider, falling back to a generic camera when the
+/// icon theme doesn't have it (usually because the provider's app isn't installed)
+pub fn provider_icon<'a, M: 'a>(icon_name: &str, size: u16) -> Element<'a, M> {
+    widget::icon::from_name(icon_name.to_string())
+        .fallback(Some(widget::icon::IconFallback::Names(vec![
+            DEFAULT_PROVIDER_ICON.into(),
+        ])))
+        .size(size)
+        .icon()
+        .into()
+}
+
 /// Create a calendar color indicator dot widget with optional tooltip showing calendar name
 pub fn calendar_color_dot<'a, M: 'a>(
     calendar_uid: &str,